@@ -37,6 +37,13 @@ type ModelResponse struct {
 	TotalPages int            `json:"total_pages"`
 }
 
+// seriesExists 检查 series 表中是否存在指定系列
+func seriesExists(id uint) bool {
+	var count int64
+	database.DB.Table("series").Where("id = ?", id).Count(&count)
+	return count > 0
+}
+
 // GetModels 获取模型列表
 func (h *ModelHandler) GetModels(c *gin.Context) {
 	var query ModelListQuery
@@ -53,28 +60,32 @@ func (h *ModelHandler) GetModels(c *gin.Context) {
 
 	// 搜索条件
 	if query.Search != "" {
-		db = db.Where("name ILIKE ?", "%"+query.Search+"%")
+		db = db.Where("models.name ILIKE ?", "%"+query.Search+"%")
 	}
 	if query.Manufacturer != "" {
 		db = db.Joins("JOIN manufacturers ON models.manufacturer_id = manufacturers.id").
 			Where("manufacturers.name ILIKE ?", "%"+query.Manufacturer+"%")
 	}
 	if query.Series != "" {
-		db = db.Where("series ILIKE ?", "%"+query.Series+"%")
+		pattern := "%" + query.Series + "%"
+		db = db.Where("models.series_id IN (SELECT id FROM series WHERE name ILIKE ? OR array_to_string(aliases, ' ') ILIKE ?)", pattern, pattern)
 	}
 	if query.Category != "" {
-		db = db.Where("category = ?", query.Category)
+		db = db.Where("models.category = ?", models.NormalizeCategory(query.Category))
 	}
 	if query.Status != "" {
-		db = db.Where("status = ?", query.Status)
+		db = db.Where("models.status = ?", models.NormalizeStatus(query.Status))
 	}
 
 	// 计算总数
 	var total int64
 	db.Count(&total)
 
-	// 排序
-	orderBy := query.SortBy + " " + query.SortOrder
+	// 关联系列名称，放在计数之后，避免 Count 带上多列的 Select
+	db = db.Scopes(models.WithSeriesName)
+
+	// 排序，series 表也有 name / created_at 等列，需带上表名
+	orderBy := "models." + query.SortBy + " " + query.SortOrder
 	if query.SortBy == "manufacturer" {
 		orderBy = "manufacturers.name " + query.SortOrder
 		db = db.Joins("JOIN manufacturers ON models.manufacturer_id = manufacturers.id")
@@ -116,7 +127,8 @@ func (h *ModelHandler) GetModelByID(c *gin.Context) {
 	}
 
 	var model models.Model
-	if err := database.DB.Preload("Manufacturer").
+	if err := database.DB.Scopes(models.WithSeriesName).
+		Preload("Manufacturer").
 		Preload("Parent").
 		Preload("Children").
 		Preload("PriceHistory").
@@ -143,6 +155,9 @@ func (h *ModelHandler) CreateModel(c *gin.Context) {
 		return
 	}
 
+	model.Status = models.NormalizeStatus(model.Status)
+	model.Category = models.NormalizeCategory(model.Category)
+
 	// 验证厂商是否存在
 	var manufacturer models.Manufacturer
 	if err := database.DB.First(&manufacturer, model.ManufacturerID).Error; err != nil {
@@ -152,6 +167,14 @@ func (h *ModelHandler) CreateModel(c *gin.Context) {
 		return
 	}
 
+	// 验证系列是否存在（如果指定了series_id）
+	if model.SeriesID != nil && !seriesExists(*model.SeriesID) {
+		c.JSON(http.StatusBadRequest, gin.H{
+			"error": "指定的系列不存在",
+		})
+		return
+	}
+
 	// 验证父模型是否存在（如果指定了parent_id）
 	if model.ParentID != nil {
 		var parentModel models.Model
@@ -171,7 +194,7 @@ func (h *ModelHandler) CreateModel(c *gin.Context) {
 	}
 
 	// 重新加载模型以包含关联数据
-	database.DB.Preload("Manufacturer").Preload("Parent").Preload("Children").First(&model, model.ID)
+	database.DB.Scopes(models.WithSeriesName).Preload("Manufacturer").Preload("Parent").Preload("Children").First(&model, model.ID)
 
 	c.JSON(http.StatusCreated, gin.H{
 		"model": model,
@@ -206,6 +229,9 @@ func (h *ModelHandler) UpdateModel(c *gin.Context) {
 		return
 	}
 
+	updateData.Status = models.NormalizeStatus(updateData.Status)
+	updateData.Category = models.NormalizeCategory(updateData.Category)
+
 	// 如果更新厂商ID，验证厂商是否存在
 	if updateData.ManufacturerID != 0 && updateData.ManufacturerID != model.ManufacturerID {
 		var manufacturer models.Manufacturer
@@ -217,6 +243,14 @@ func (h *ModelHandler) UpdateModel(c *gin.Context) {
 		}
 	}
 
+	// 如果更新系列ID，验证系列是否存在
+	if updateData.SeriesID != nil && !seriesExists(*updateData.SeriesID) {
+		c.JSON(http.StatusBadRequest, gin.H{
+			"error": "指定的系列不存在",
+		})
+		return
+	}
+
 	// 如果更新父模型ID，验证父模型是否存在，并且不能自引用
 	if updateData.ParentID != nil {
 		if *updateData.ParentID == model.ID {
@@ -242,7 +276,7 @@ func (h *ModelHandler) UpdateModel(c *gin.Context) {
 	}
 
 	// 重新加载模型以包含关联数据
-	database.DB.Preload("Manufacturer").Preload("Parent").Preload("Children").First(&model, model.ID)
+	database.DB.Scopes(models.WithSeriesName).Preload("Manufacturer").Preload("Parent").Preload("Children").First(&model, model.ID)
 
 	c.JSON(http.StatusOK, gin.H{
 		"model": model,
@@ -303,13 +337,13 @@ func (h *ModelHandler) GetModelVariants(c *gin.Context) {
 
 	// 如果这个模型有父模型，获取所有兄弟模型（包括自己）
 	if model.ParentID != nil {
-		database.DB.Preload("Manufacturer").Where("parent_id = ?", *model.ParentID).Find(&variants)
+		database.DB.Scopes(models.WithSeriesName).Preload("Manufacturer").Where("models.parent_id = ?", *model.ParentID).Find(&variants)
 	} else {
 		// 如果这个模型没有父模型，获取它的所有子模型
-		database.DB.Preload("Manufacturer").Where("parent_id = ?", model.ID).Find(&variants)
+		database.DB.Scopes(models.WithSeriesName).Preload("Manufacturer").Where("models.parent_id = ?", model.ID).Find(&variants)
 		// 同时包含自己
 		var self models.Model
-		database.DB.Preload("Manufacturer").First(&self, model.ID)
+		database.DB.Scopes(models.WithSeriesName).Preload("Manufacturer").First(&self, model.ID)
 		variants = append([]models.Model{self}, variants...)
 	}
 
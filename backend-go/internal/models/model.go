@@ -1,19 +1,24 @@
 package models
 
 import (
+	"strings"
 	"time"
 
 	"gorm.io/gorm"
 )
 
 // Model 高达模型
+//
+// series_id 列与 series 表由 Rust 后端的迁移创建（原 models.series 文本列已删除），AutoMigrate 不处理这两个字段；
+// Series 为只读的系列名称，需通过 WithSeriesName 关联查询得到
 type Model struct {
 	ID             uint       `json:"id" gorm:"primaryKey"`
 	ParentID       *uint      `json:"parent_id" gorm:"index"`
 	ManufacturerID uint       `json:"manufacturer_id" gorm:"not null;index"`
-	Series         *string    `json:"series" gorm:"size:200"`
+	SeriesID       *uint      `json:"series_id" gorm:"-:migration"`
+	Series         *string    `json:"series" gorm:"->;-:migration"`
 	Name           string     `json:"name" gorm:"not null;size:300"`
-	Status         string     `json:"status" gorm:"default:'现货';size:20"`
+	Status         string     `json:"status" gorm:"default:'in_stock';size:20"`
 	Category       string     `json:"category" gorm:"default:'hg';size:20"`
 	ReleaseDate    *time.Time `json:"release_date" gorm:"type:date"`
 	Rating         *float32   `json:"rating" gorm:"type:decimal(2,1);check:rating >= 0 AND rating <= 5"`
@@ -35,6 +40,54 @@ func (Model) TableName() string {
 	return "models"
 }
 
+// knownCategories 等级代码，与 models.category 的 CHECK 约束一致
+var knownCategories = map[string]bool{
+	"hg": true, "rg": true, "mg": true, "pg": true, "sd": true, "mgex": true,
+	"mgsd": true, "re100": true, "fm": true, "eg": true, "hirm": true,
+}
+
+// NormalizeStatus 将中文标签与各种英文写法统一为 in_stock / pre_order / discontinued，
+// 与 Rust 后端的迁移及 models.status 的 CHECK 约束一致；空字符串原样返回，以使用默认值
+func NormalizeStatus(status string) string {
+	switch strings.ToLower(strings.TrimSpace(status)) {
+	case "":
+		return ""
+	case "预售", "pre_order", "pre-order", "preorder", "pre order":
+		return "pre_order"
+	case "下架", "停产", "discontinued":
+		return "discontinued"
+	default:
+		return "in_stock"
+	}
+}
+
+// NormalizeCategory 将等级统一为小写代码，无法识别的归为 other；空字符串原样返回
+func NormalizeCategory(category string) string {
+	trimmed := strings.TrimSpace(category)
+	if trimmed == "" {
+		return ""
+	}
+	compact := strings.ToLower(strings.NewReplacer(" ", "", "/", "").Replace(trimmed))
+	switch {
+	case knownCategories[compact]:
+		return compact
+	case trimmed == "BB战士":
+		return "sd"
+	case compact == "fullmechanics":
+		return "fm"
+	case compact == "entrygrade":
+		return "eg"
+	default:
+		return "other"
+	}
+}
+
+// WithSeriesName 关联 series 表读取系列名称，填充只读的 Series 字段
+func WithSeriesName(db *gorm.DB) *gorm.DB {
+	return db.Select("models.*, series.name AS series").
+		Joins("LEFT JOIN series ON series.id = models.series_id")
+}
+
 // BeforeCreate 创建前钩子
 func (m *Model) BeforeCreate(tx *gorm.DB) error {
 	m.CreatedAt = time.Now()
@@ -5,12 +5,16 @@ use serde::{Deserialize, Serialize};
 pub struct Claims {
     pub sub: String, // user_id
     pub username: String,
+    /// 旧版本签发的 token 不含该字段，按普通用户处理
+    #[serde(default)]
+    pub is_admin: bool,
     pub exp: usize,
 }
 
 pub fn generate_jwt(
     user_id: &str,
     username: &str,
+    is_admin: bool,
     secret: &str,
     expiration: i64,
 ) -> anyhow::Result<String> {
@@ -20,6 +24,7 @@ pub fn generate_jwt(
     let claims = Claims {
         sub: user_id.to_string(),
         username: username.to_string(),
+        is_admin,
         exp: exp as usize,
     };
 
@@ -10,6 +10,10 @@ pub struct Config {
     pub server: ServerConfig,
     pub database: DatabaseConfig,
     pub auth: AuthConfig,
+    pub storage: StorageConfig,
+    pub notifications: NotificationConfig,
+    pub scraper: ScraperConfig,
+    pub status_transitions: StatusTransitionConfig,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -32,6 +36,92 @@ pub struct AuthConfig {
     pub jwt_expiration: i64,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct StorageConfig {
+    pub backend: StorageBackend,
+    /// 对象的对外访问前缀，未设置时本地存储为 `/uploads`，S3 为 `<endpoint>/<bucket>`
+    pub public_url: Option<String>,
+    pub max_image_bytes: usize,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub enum StorageBackend {
+    Local { dir: String },
+    S3(S3Config),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct S3Config {
+    pub endpoint: String,
+    pub bucket: String,
+    pub region: String,
+    pub access_key: String,
+    pub secret_key: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NotificationConfig {
+    /// 分发任务的轮询间隔（秒）
+    pub poll_interval_secs: u64,
+    /// 单个渠道的最大投递次数，超过后标记为失败
+    pub max_attempts: i32,
+    /// 允许 webhook 指向内网、回环与链路本地地址，仅用于可信的自托管环境
+    pub allow_private_webhooks: bool,
+    /// 未配置时不提供邮件渠道
+    pub smtp: Option<SmtpConfig>,
+}
+
+#[derive(Clone, Deserialize)]
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: u16,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub from: String,
+    pub tls: SmtpTls,
+}
+
+// 日志中会打印配置，避免输出 SMTP 密码
+impl std::fmt::Debug for SmtpConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SmtpConfig")
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .field("username", &self.username)
+            .field("from", &self.from)
+            .field("tls", &self.tls)
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub enum SmtpTls {
+    /// 明文连接，用于本地的 MailHog 等测试服务
+    None,
+    StartTls,
+    Tls,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScraperConfig {
+    pub enabled: bool,
+    /// 检查到期价格监控的 cron 表达式，各监控的抓取时间由自身的 `schedule` 决定
+    pub schedule: String,
+    pub request_timeout_secs: u64,
+    pub user_agent: String,
+    /// 连续失败达到该次数后停用监控
+    pub max_failures: i32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct StatusTransitionConfig {
+    pub enabled: bool,
+    /// 执行状态流转任务的 cron 表达式
+    pub schedule: String,
+    /// 现货模型超过该月数没有新价格时标记为疑似停产，为 0 时不标记
+    pub stale_after_months: u32,
+}
+
 impl Config {
     pub fn from_env() -> anyhow::Result<Self> {
         dotenvy::dotenv().ok();
@@ -61,10 +151,111 @@ impl Config {
                 .unwrap_or(24 * 60 * 60), // 24 hours in seconds
         };
 
+        let backend = match env::var("STORAGE_BACKEND")
+            .unwrap_or_else(|_| "local".to_string())
+            .as_str()
+        {
+            "local" => StorageBackend::Local {
+                dir: env::var("STORAGE_LOCAL_DIR").unwrap_or_else(|_| "uploads".to_string()),
+            },
+            "s3" => StorageBackend::S3(S3Config {
+                endpoint: env::var("S3_ENDPOINT")
+                    .map_err(|_| anyhow::anyhow!("S3_ENDPOINT must be set"))?,
+                bucket: env::var("S3_BUCKET")
+                    .map_err(|_| anyhow::anyhow!("S3_BUCKET must be set"))?,
+                region: env::var("S3_REGION").unwrap_or_else(|_| "us-east-1".to_string()),
+                access_key: env::var("S3_ACCESS_KEY")
+                    .map_err(|_| anyhow::anyhow!("S3_ACCESS_KEY must be set"))?,
+                secret_key: env::var("S3_SECRET_KEY")
+                    .map_err(|_| anyhow::anyhow!("S3_SECRET_KEY must be set"))?,
+            }),
+            other => anyhow::bail!("Unknown STORAGE_BACKEND: {}", other),
+        };
+
+        let storage = StorageConfig {
+            backend,
+            public_url: env::var("STORAGE_PUBLIC_URL").ok(),
+            max_image_bytes: env::var("MAX_IMAGE_BYTES")
+                .unwrap_or_else(|_| "10485760".to_string())
+                .parse()
+                .unwrap_or(10 * 1024 * 1024), // 10 MB
+        };
+
+        let smtp = match env::var("SMTP_HOST") {
+            Ok(host) => Some(SmtpConfig {
+                host,
+                port: env::var("SMTP_PORT")
+                    .unwrap_or_else(|_| "587".to_string())
+                    .parse()?,
+                username: env::var("SMTP_USERNAME").ok(),
+                password: env::var("SMTP_PASSWORD").ok(),
+                from: env::var("SMTP_FROM")
+                    .map_err(|_| anyhow::anyhow!("SMTP_FROM must be set when SMTP_HOST is set"))?,
+                tls: match env::var("SMTP_TLS")
+                    .unwrap_or_else(|_| "starttls".to_string())
+                    .as_str()
+                {
+                    "none" => SmtpTls::None,
+                    "starttls" => SmtpTls::StartTls,
+                    "tls" => SmtpTls::Tls,
+                    other => anyhow::bail!("Unknown SMTP_TLS: {}", other),
+                },
+            }),
+            Err(_) => None,
+        };
+
+        let notifications = NotificationConfig {
+            poll_interval_secs: env::var("NOTIFICATION_POLL_INTERVAL")
+                .unwrap_or_else(|_| "10".to_string())
+                .parse()
+                .unwrap_or(10),
+            max_attempts: env::var("NOTIFICATION_MAX_ATTEMPTS")
+                .unwrap_or_else(|_| "6".to_string())
+                .parse()
+                .unwrap_or(6),
+            allow_private_webhooks: env::var("WEBHOOK_ALLOW_PRIVATE_HOSTS")
+                .map(|v| v == "true" || v == "1")
+                .unwrap_or(false),
+            smtp,
+        };
+
+        let scraper = ScraperConfig {
+            enabled: env::var("SCRAPER_ENABLED")
+                .map(|v| v != "false" && v != "0")
+                .unwrap_or(true),
+            schedule: env::var("SCRAPER_SCHEDULE").unwrap_or_else(|_| "0 * * * * *".to_string()),
+            request_timeout_secs: env::var("SCRAPER_TIMEOUT")
+                .unwrap_or_else(|_| "15".to_string())
+                .parse()
+                .unwrap_or(15),
+            user_agent: env::var("SCRAPER_USER_AGENT")
+                .unwrap_or_else(|_| "Mozilla/5.0 (compatible; ModelHub/0.1)".to_string()),
+            max_failures: env::var("SCRAPER_MAX_FAILURES")
+                .unwrap_or_else(|_| "10".to_string())
+                .parse()
+                .unwrap_or(10),
+        };
+
+        let status_transitions = StatusTransitionConfig {
+            enabled: env::var("STATUS_TRANSITION_ENABLED")
+                .map(|v| v != "false" && v != "0")
+                .unwrap_or(true),
+            schedule: env::var("STATUS_TRANSITION_SCHEDULE")
+                .unwrap_or_else(|_| "0 30 3 * * *".to_string()),
+            stale_after_months: env::var("STALE_PRICE_MONTHS")
+                .unwrap_or_else(|_| "6".to_string())
+                .parse()
+                .unwrap_or(6),
+        };
+
         Ok(Config {
             server,
             database,
             auth,
+            storage,
+            notifications,
+            scraper,
+            status_transitions,
         })
     }
 }
@@ -0,0 +1,44 @@
+use super::enums::BuildStage;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "build_log_entries")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    #[serde(skip_deserializing)]
+    pub id: i32,
+    pub purchase_id: i32,
+    pub entry_date: chrono::NaiveDate,
+    pub content: Option<String>,
+    pub hours_spent: Option<f64>,
+    pub stage: Option<BuildStage>,
+    #[serde(skip_deserializing)]
+    pub created_at: chrono::NaiveDateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user_model_purchase::Entity",
+        from = "Column::PurchaseId",
+        to = "super::user_model_purchase::Column::Id"
+    )]
+    Purchase,
+    #[sea_orm(has_many = "super::build_log_image::Entity")]
+    Image,
+}
+
+impl Related<super::user_model_purchase::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Purchase.def()
+    }
+}
+
+impl Related<super::build_log_image::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Image.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,180 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 模型销售状态，兼容历史数据中的中文标签
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(Some(20))")]
+pub enum ModelStatus {
+    #[sea_orm(string_value = "in_stock")]
+    #[serde(rename = "in_stock", alias = "现货", alias = "in stock")]
+    InStock,
+    #[sea_orm(string_value = "pre_order")]
+    #[serde(
+        rename = "pre_order",
+        alias = "预售",
+        alias = "pre-order",
+        alias = "preorder"
+    )]
+    PreOrder,
+    #[sea_orm(string_value = "discontinued")]
+    #[serde(rename = "discontinued", alias = "下架", alias = "停产")]
+    Discontinued,
+}
+
+impl ModelStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ModelStatus::InStock => "现货",
+            ModelStatus::PreOrder => "预售",
+            ModelStatus::Discontinued => "下架",
+        }
+    }
+}
+
+/// 制作阶段，制作日志中记录阶段变化
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(Some(20))")]
+pub enum BuildStage {
+    #[sea_orm(string_value = "straight_build")]
+    #[serde(rename = "straight_build", alias = "素组")]
+    StraightBuild,
+    #[sea_orm(string_value = "panel_lining")]
+    #[serde(rename = "panel_lining", alias = "渗线")]
+    PanelLining,
+    #[sea_orm(string_value = "decals")]
+    #[serde(rename = "decals", alias = "水贴", alias = "贴纸")]
+    Decals,
+    #[sea_orm(string_value = "topcoat")]
+    #[serde(rename = "topcoat", alias = "喷漆保护", alias = "消光")]
+    Topcoat,
+    #[sea_orm(string_value = "painting")]
+    #[serde(rename = "painting", alias = "涂装")]
+    Painting,
+}
+
+impl BuildStage {
+    pub fn label(&self) -> &'static str {
+        match self {
+            BuildStage::StraightBuild => "素组",
+            BuildStage::PanelLining => "渗线",
+            BuildStage::Decals => "水贴",
+            BuildStage::Topcoat => "喷漆保护",
+            BuildStage::Painting => "涂装",
+        }
+    }
+}
+
+/// 通知类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(Some(30))")]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    /// 心愿单模型的新价格换算到目标价币种后不高于目标价，由记录价格的服务生成
+    #[sea_orm(string_value = "price_drop")]
+    PriceDrop,
+}
+
+/// 通知投递渠道
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter, DeriveActiveEnum, Serialize, Deserialize,
+)]
+#[sea_orm(rs_type = "String", db_type = "String(Some(20))")]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryChannel {
+    #[sea_orm(string_value = "in_app")]
+    InApp,
+    #[sea_orm(string_value = "webhook")]
+    Webhook,
+    #[sea_orm(string_value = "email")]
+    Email,
+}
+
+/// 价格监控的抓取方式，新增方式时需同时实现对应的 `PriceSource`
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter, DeriveActiveEnum, Serialize, Deserialize,
+)]
+#[sea_orm(rs_type = "String", db_type = "String(Some(20))")]
+#[serde(rename_all = "snake_case")]
+pub enum PriceSourceKind {
+    /// 按 CSS 选择器读取 HTML 页面中的元素文本或属性
+    #[sea_orm(string_value = "html")]
+    Html,
+    /// 按 JSON 路径读取接口返回值
+    #[sea_orm(string_value = "json")]
+    Json,
+}
+
+/// 通知投递状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(Some(20))")]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryStatus {
+    #[sea_orm(string_value = "pending")]
+    Pending,
+    #[sea_orm(string_value = "sent")]
+    Sent,
+    /// 超过最大重试次数后不再投递
+    #[sea_orm(string_value = "failed")]
+    Failed,
+}
+
+/// 模型等级（对应 models.category 列）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(Some(20))")]
+pub enum Grade {
+    #[sea_orm(string_value = "hg")]
+    #[serde(rename = "hg", alias = "HG")]
+    Hg,
+    #[sea_orm(string_value = "rg")]
+    #[serde(rename = "rg", alias = "RG")]
+    Rg,
+    #[sea_orm(string_value = "mg")]
+    #[serde(rename = "mg", alias = "MG")]
+    Mg,
+    #[sea_orm(string_value = "pg")]
+    #[serde(rename = "pg", alias = "PG")]
+    Pg,
+    #[sea_orm(string_value = "sd")]
+    #[serde(rename = "sd", alias = "SD", alias = "BB战士")]
+    Sd,
+    #[sea_orm(string_value = "mgex")]
+    #[serde(rename = "mgex", alias = "MGEX")]
+    Mgex,
+    #[sea_orm(string_value = "mgsd")]
+    #[serde(rename = "mgsd", alias = "MGSD")]
+    Mgsd,
+    #[sea_orm(string_value = "re100")]
+    #[serde(rename = "re100", alias = "RE100", alias = "RE/100", alias = "re/100")]
+    Re100,
+    #[sea_orm(string_value = "fm")]
+    #[serde(rename = "fm", alias = "FM", alias = "Full Mechanics")]
+    Fm,
+    #[sea_orm(string_value = "eg")]
+    #[serde(rename = "eg", alias = "EG", alias = "Entry Grade")]
+    Eg,
+    #[sea_orm(string_value = "hirm")]
+    #[serde(rename = "hirm", alias = "HIRM", alias = "HiRM")]
+    Hirm,
+    #[sea_orm(string_value = "other")]
+    #[serde(rename = "other", alias = "其他")]
+    Other,
+}
+
+impl Grade {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Grade::Hg => "HG",
+            Grade::Rg => "RG",
+            Grade::Mg => "MG",
+            Grade::Pg => "PG",
+            Grade::Sd => "SD",
+            Grade::Mgex => "MGEX",
+            Grade::Mgsd => "MGSD",
+            Grade::Re100 => "RE/100",
+            Grade::Fm => "FM",
+            Grade::Eg => "EG",
+            Grade::Hirm => "HiRM",
+            Grade::Other => "其他",
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 1 单位 `base_currency` 自 `effective_date` 起折合 `rate` 单位 `quote_currency`
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "exchange_rates")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    #[serde(skip_deserializing)]
+    pub id: i32,
+    pub base_currency: String,
+    pub quote_currency: String,
+    pub rate: f64,
+    pub effective_date: chrono::NaiveDate,
+    pub source: Option<String>,
+    #[serde(skip_deserializing)]
+    pub created_at: chrono::NaiveDateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -8,9 +8,12 @@ pub struct Model {
     #[serde(skip_deserializing)]
     pub id: i32,
     pub name: String,
+    pub full_name: Option<String>,
     pub country: String,
     pub active_period_start: Option<chrono::NaiveDate>,
     pub active_period_end: Option<chrono::NaiveDate>,
+    #[serde(skip_deserializing)]
+    pub updated_at: chrono::NaiveDateTime,
     #[sea_orm(ignore)]
     pub models_count: Option<i64>,
 }
@@ -1,6 +1,22 @@
+pub mod build_log_entry;
+pub mod build_log_image;
+pub mod calendar_token;
+pub mod currency;
+pub mod enums;
+pub mod exchange_rate;
 pub mod manufacturer;
 pub mod model;
+pub mod model_image;
+pub mod model_revision;
+pub mod model_tag;
+pub mod notification;
+pub mod notification_delivery;
+pub mod notification_preference;
 pub mod price_history;
+pub mod price_watch;
+pub mod series;
+pub mod shop;
+pub mod tag;
 pub mod user;
 pub mod user_model_favorite;
 pub mod user_model_purchase;
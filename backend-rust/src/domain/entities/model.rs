@@ -1,7 +1,11 @@
+use super::enums::{Grade, ModelStatus};
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
+/// 变体层级上限：根模型深度为 0，变体（含变体的变体）最多嵌套到该深度
+pub const MAX_VARIANT_DEPTH: usize = 3;
+
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, Validate)]
 #[sea_orm(table_name = "models")]
 pub struct Model {
@@ -10,15 +14,21 @@ pub struct Model {
     pub id: i32,
     #[validate(length(min = 1, max = 255))]
     pub name: String,
-    #[validate(length(max = 100))]
-    pub series: Option<String>,
-    #[validate(length(max = 50))]
-    pub category: Option<String>,
+    pub series_id: Option<i32>,
+    pub category: Option<Grade>,
     pub release_date: Option<chrono::NaiveDate>,
-    pub status: String,
+    /// 预售开订日期
+    pub preorder_open_date: Option<chrono::NaiveDate>,
+    pub status: ModelStatus,
     pub manufacturer_id: i32,
     pub parent_id: Option<i32>,
     pub notes: Option<String>,
+    /// 定时任务标记为疑似停产的时间，由状态流转任务维护
+    #[serde(skip_deserializing)]
+    pub possibly_discontinued_at: Option<chrono::NaiveDateTime>,
+    /// 创建模型的用户，早期数据为空
+    #[serde(skip_deserializing)]
+    pub created_by: Option<i32>,
     #[serde(skip_deserializing)]
     pub created_at: chrono::NaiveDateTime,
     #[serde(skip_deserializing)]
@@ -33,6 +43,12 @@ pub enum Relation {
         to = "super::manufacturer::Column::Id"
     )]
     Manufacturer,
+    #[sea_orm(
+        belongs_to = "super::series::Entity",
+        from = "Column::SeriesId",
+        to = "super::series::Column::Id"
+    )]
+    Series,
     #[sea_orm(belongs_to = "Entity", from = "Column::ParentId", to = "Column::Id")]
     Parent,
     #[sea_orm(has_many = "Entity")]
@@ -43,6 +59,16 @@ pub enum Relation {
     Favorite,
     #[sea_orm(has_many = "super::user_model_purchase::Entity")]
     Purchase,
+    #[sea_orm(has_many = "super::model_tag::Entity")]
+    ModelTag,
+    #[sea_orm(has_many = "super::model_image::Entity")]
+    Image,
+}
+
+impl Related<super::model_image::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Image.def()
+    }
 }
 
 impl Related<super::manufacturer::Entity> for Entity {
@@ -51,6 +77,12 @@ impl Related<super::manufacturer::Entity> for Entity {
     }
 }
 
+impl Related<super::series::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Series.def()
+    }
+}
+
 impl Related<Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Parent.def()
@@ -75,4 +107,14 @@ impl Related<super::user_model_purchase::Entity> for Entity {
     }
 }
 
+impl Related<super::tag::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::model_tag::Relation::Tag.def()
+    }
+
+    fn via() -> Option<RelationDef> {
+        Some(super::model_tag::Relation::Model.def().rev())
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,50 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "notification_preferences")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub user_id: i32,
+    pub in_app_enabled: bool,
+    pub webhook_enabled: bool,
+    pub webhook_url: Option<String>,
+    pub webhook_secret: Option<String>,
+    pub email_enabled: bool,
+    pub email: Option<String>,
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id"
+    )]
+    User,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    /// 用户未设置偏好时的默认值：只使用站内通知
+    pub fn default_for(user_id: i32) -> Self {
+        Self {
+            user_id,
+            in_app_enabled: true,
+            webhook_enabled: false,
+            webhook_url: None,
+            webhook_secret: None,
+            email_enabled: false,
+            email: None,
+            updated_at: chrono::NaiveDateTime::default(),
+        }
+    }
+}
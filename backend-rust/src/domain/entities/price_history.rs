@@ -11,9 +11,15 @@ pub struct Model {
     #[validate(range(min = 0.0))]
     pub price: f64,
     pub currency: String,
+    /// 自由文本来源，关联店铺时与店铺名称一致
     pub source: Option<String>,
+    pub shop_id: Option<i32>,
+    /// 由价格监控抓取时为来源监控
+    pub watch_id: Option<i32>,
     pub notes: Option<String>,
     pub model_id: i32,
+    /// 记录价格的用户，早期数据与自动抓取的价格为空
+    pub recorded_by: Option<i32>,
     #[serde(skip_deserializing)]
     pub recorded_at: chrono::NaiveDateTime,
 }
@@ -26,6 +32,12 @@ pub enum Relation {
         to = "super::model::Column::Id"
     )]
     Model,
+    #[sea_orm(
+        belongs_to = "super::shop::Entity",
+        from = "Column::ShopId",
+        to = "super::shop::Column::Id"
+    )]
+    Shop,
 }
 
 impl Related<super::model::Entity> for Entity {
@@ -34,4 +46,10 @@ impl Related<super::model::Entity> for Entity {
     }
 }
 
+impl Related<super::shop::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Shop.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,61 @@
+use super::enums::PriceSourceKind;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "price_watches")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    #[serde(skip_deserializing)]
+    pub id: i32,
+    pub model_id: i32,
+    pub shop_id: Option<i32>,
+    pub url: String,
+    pub source_kind: PriceSourceKind,
+    /// HTML 来源为 CSS 选择器，JSON 来源为 JSON 路径
+    pub selector: String,
+    pub currency: String,
+    /// cron 表达式，如 `0 0 */6 * * *`
+    pub schedule: String,
+    pub enabled: bool,
+    pub next_run_at: chrono::NaiveDateTime,
+    pub last_run_at: Option<chrono::NaiveDateTime>,
+    pub last_price: Option<f64>,
+    pub last_error: Option<String>,
+    pub consecutive_failures: i32,
+    pub created_by: Option<i32>,
+    #[serde(skip_deserializing)]
+    pub created_at: chrono::NaiveDateTime,
+    #[serde(skip_deserializing)]
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::model::Entity",
+        from = "Column::ModelId",
+        to = "super::model::Column::Id"
+    )]
+    Model,
+    #[sea_orm(
+        belongs_to = "super::shop::Entity",
+        from = "Column::ShopId",
+        to = "super::shop::Column::Id"
+    )]
+    Shop,
+}
+
+impl Related<super::model::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Model.def()
+    }
+}
+
+impl Related<super::shop::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Shop.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
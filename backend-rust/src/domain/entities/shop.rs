@@ -0,0 +1,33 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "shops")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    #[serde(skip_deserializing)]
+    pub id: i32,
+    pub name: String,
+    pub platform: Option<String>,
+    pub url: Option<String>,
+    pub region: Option<String>,
+    pub trust_notes: Option<String>,
+    #[serde(skip_deserializing)]
+    pub created_at: chrono::NaiveDateTime,
+    #[serde(skip_deserializing)]
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(has_many = "super::price_history::Entity")]
+    PriceHistory,
+}
+
+impl Related<super::price_history::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PriceHistory.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
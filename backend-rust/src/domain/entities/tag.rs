@@ -0,0 +1,46 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "tags")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    #[serde(skip_deserializing)]
+    pub id: i32,
+    pub name: String,
+    pub slug: String,
+    #[serde(skip_deserializing)]
+    pub created_at: chrono::NaiveDateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(has_many = "super::model_tag::Entity")]
+    ModelTag,
+}
+
+impl Related<super::model_tag::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ModelTag.def()
+    }
+}
+
+impl Related<super::model::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::model_tag::Relation::Model.def()
+    }
+
+    fn via() -> Option<RelationDef> {
+        Some(super::model_tag::Relation::Tag.def().rev())
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+/// 标签的唯一标识：去除首尾空白、转小写并将空白替换为 `-`，如 "Premium Bandai" -> "premium-bandai"
+pub fn slugify(name: &str) -> String {
+    name.split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+        .to_lowercase()
+}
@@ -10,6 +10,8 @@ pub struct Model {
     pub username: String,
     #[serde(skip_deserializing)]
     pub password_hash: String,
+    #[serde(default)]
+    pub is_admin: bool,
     #[sea_orm(ignore)]
     pub password: Option<String>,
 }
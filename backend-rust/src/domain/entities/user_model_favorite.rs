@@ -1,7 +1,7 @@
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "user_model_favorite")]
 pub struct Model {
     #[sea_orm(primary_key)]
@@ -10,6 +10,11 @@ pub struct Model {
     pub user_id: i32,
     pub model_id: i32,
     pub notes: Option<String>,
+    /// 心愿单目标价，新价格不高于该值时生成降价提醒
+    pub target_price: Option<f64>,
+    pub target_currency: String,
+    /// 心愿优先级 1-5，越大越想要
+    pub priority: i16,
     #[serde(skip_deserializing)]
     pub created_at: chrono::NaiveDateTime,
 }
@@ -10,6 +10,10 @@ pub struct Model {
     pub user_id: i32,
     pub model_id: i32,
     pub notes: Option<String>,
+    /// 预售尾款截止日期
+    pub payment_due_date: Option<chrono::NaiveDate>,
+    /// 预计发货日期
+    pub ship_date: Option<chrono::NaiveDate>,
     #[serde(skip_deserializing)]
     pub created_at: chrono::NaiveDateTime,
 }
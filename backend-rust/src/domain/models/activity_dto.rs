@@ -0,0 +1,94 @@
+use crate::domain::models::currency_dto::ConvertedAmount;
+use serde::{Deserialize, Serialize};
+
+/// 动态类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityKind {
+    ModelCreated,
+    Favorited,
+    Purchased,
+    PriceAdded,
+}
+
+impl ActivityKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ActivityKind::ModelCreated => "model_created",
+            ActivityKind::Favorited => "favorited",
+            ActivityKind::Purchased => "purchased",
+            ActivityKind::PriceAdded => "price_added",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "model_created" => Some(ActivityKind::ModelCreated),
+            "favorited" => Some(ActivityKind::Favorited),
+            "purchased" => Some(ActivityKind::Purchased),
+            "price_added" => Some(ActivityKind::PriceAdded),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ActivityParams {
+    pub user_id: Option<i32>,
+    pub model_id: Option<i32>,
+    /// 逗号分隔的动态类型，如 `purchased,price_added`
+    #[serde(rename = "type")]
+    pub kinds: Option<String>,
+    #[serde(default = "default_page")]
+    pub page: u64,
+    #[serde(default = "default_per_page", alias = "page_size")]
+    pub per_page: u64,
+    /// 将 `price_added` 的价格换算到该币种
+    pub currency: Option<String>,
+}
+
+fn default_page() -> u64 {
+    1
+}
+
+fn default_per_page() -> u64 {
+    20
+}
+
+/// 仓储层查询动态的筛选条件
+#[derive(Debug, Clone, Default)]
+pub struct ActivityFilter {
+    pub user_id: Option<i32>,
+    pub model_id: Option<i32>,
+    /// 为空表示不限类型
+    pub kinds: Vec<ActivityKind>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ActivityEntry {
+    #[serde(rename = "type")]
+    pub kind: ActivityKind,
+    /// 早期数据未记录操作者时为空
+    pub user_id: Option<i32>,
+    pub username: Option<String>,
+    pub model_id: i32,
+    pub model_name: String,
+    /// 仅 `price_added` 有值
+    pub price: Option<f64>,
+    pub currency: Option<String>,
+    /// 仅指定 `currency` 时返回
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub converted: Option<ConvertedAmount>,
+    /// 可直接展示的描述，如“张三 购买了 MG 自由高达 2.0”
+    pub summary: String,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ActivityListResponse {
+    pub entries: Vec<ActivityEntry>,
+    pub total: u64,
+    pub page: u64,
+    pub per_page: u64,
+    pub total_pages: u64,
+}
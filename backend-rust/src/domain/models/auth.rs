@@ -19,4 +19,5 @@ pub struct LoginResponse {
 pub struct UserResponse {
     pub id: i32,
     pub username: String,
+    pub is_admin: bool,
 }
@@ -0,0 +1,47 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// 备份文件格式版本，文件结构发生不兼容变化时递增
+pub const BACKUP_FORMAT_VERSION: u32 = 1;
+
+/// 整库备份：每张表的列名与逐行数据（以列名为键的 JSON 对象）
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BackupArchive {
+    pub format_version: u32,
+    /// 生成备份时数据库已执行的最新迁移名，恢复时必须与目标库一致
+    pub schema_version: String,
+    pub created_at: NaiveDateTime,
+    pub includes_password_hashes: bool,
+    pub tables: Vec<BackupTable>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BackupTable {
+    pub name: String,
+    pub columns: Vec<String>,
+    pub rows: Vec<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BackupParams {
+    /// 为 false 时不导出用户密码哈希，恢复后这些用户需由管理员重新设置密码
+    #[serde(default = "default_include_password_hashes")]
+    pub include_password_hashes: bool,
+}
+
+fn default_include_password_hashes() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize)]
+pub struct RestoredTable {
+    pub name: String,
+    pub rows: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RestoreSummary {
+    pub schema_version: String,
+    pub tables: Vec<RestoredTable>,
+}
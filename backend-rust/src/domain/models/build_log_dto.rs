@@ -0,0 +1,45 @@
+use crate::domain::entities::enums::BuildStage;
+use serde::{Deserialize, Serialize};
+
+/// 新增制作日志的 multipart 表单，字段均为原始文本，由服务层校验
+#[derive(Debug, Default)]
+pub struct CreateBuildLogEntryForm {
+    pub entry_date: Option<String>,
+    pub content: Option<String>,
+    pub hours_spent: Option<String>,
+    pub stage: Option<String>,
+    pub images: Vec<Vec<u8>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BuildLogImageResponse {
+    pub id: i32,
+    pub url: String,
+    pub thumbnail_url: String,
+    pub width: i32,
+    pub height: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BuildLogEntryResponse {
+    pub id: i32,
+    pub entry_date: chrono::NaiveDate,
+    pub content: Option<String>,
+    pub hours_spent: Option<f64>,
+    /// 本条记录进入的阶段
+    pub stage: Option<BuildStage>,
+    /// 阶段变化前所处的阶段，仅在 `stage` 有值时给出
+    pub previous_stage: Option<BuildStage>,
+    pub images: Vec<BuildLogImageResponse>,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BuildLogResponse {
+    pub model_id: i32,
+    pub purchase_id: i32,
+    pub current_stage: Option<BuildStage>,
+    pub total_hours: f64,
+    /// 按日期先后排列
+    pub entries: Vec<BuildLogEntryResponse>,
+}
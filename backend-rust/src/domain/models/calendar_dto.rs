@@ -0,0 +1,82 @@
+use crate::domain::entities::enums::ModelStatus;
+use serde::{Deserialize, Serialize};
+
+/// 日历事件类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CalendarEventKind {
+    /// 预售开订
+    PreorderOpen,
+    /// 发售
+    Release,
+    /// 预售尾款截止，来自当前用户的购买记录
+    PaymentDue,
+    /// 预计发货，来自当前用户的购买记录
+    Ship,
+}
+
+impl CalendarEventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CalendarEventKind::PreorderOpen => "preorder_open",
+            CalendarEventKind::Release => "release",
+            CalendarEventKind::PaymentDue => "payment_due",
+            CalendarEventKind::Ship => "ship",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CalendarEventKind::PreorderOpen => "开订",
+            CalendarEventKind::Release => "发售",
+            CalendarEventKind::PaymentDue => "尾款截止",
+            CalendarEventKind::Ship => "发货",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CalendarParams {
+    /// 起始日期（含），缺省为 30 天前
+    pub from: Option<chrono::NaiveDate>,
+    /// 结束日期（含），缺省为 180 天后
+    pub to: Option<chrono::NaiveDate>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CalendarFeedParams {
+    pub token: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CalendarEvent {
+    pub date: chrono::NaiveDate,
+    pub kind: CalendarEventKind,
+    pub model_id: i32,
+    pub model_name: String,
+    pub status: ModelStatus,
+    /// 如「发售：RX-78-2」
+    pub summary: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CalendarMonth {
+    /// `YYYY-MM`
+    pub month: String,
+    pub events: Vec<CalendarEvent>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CalendarResponse {
+    pub from: chrono::NaiveDate,
+    pub to: chrono::NaiveDate,
+    pub months: Vec<CalendarMonth>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CalendarTokenResponse {
+    pub token: String,
+    /// 订阅地址，相对于服务根路径
+    pub feed_path: String,
+    pub created_at: chrono::NaiveDateTime,
+}
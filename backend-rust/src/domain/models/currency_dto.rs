@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// 价格类接口通用的 `?currency=` 参数，指定后额外返回换算到该币种的金额
+#[derive(Debug, Default, Deserialize)]
+pub struct CurrencyParams {
+    pub currency: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CurrencyResponse {
+    pub code: String,
+    pub name: String,
+    pub minor_units: i16,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExchangeRateListParams {
+    pub base: Option<String>,
+    pub quote: Option<String>,
+    /// 只返回该日期及之前生效的汇率
+    pub until: Option<chrono::NaiveDate>,
+    #[serde(default = "default_limit")]
+    pub limit: u64,
+}
+
+fn default_limit() -> u64 {
+    100
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExchangeRateResponse {
+    pub id: i32,
+    pub base_currency: String,
+    pub quote_currency: String,
+    pub rate: f64,
+    pub effective_date: chrono::NaiveDate,
+    pub source: Option<String>,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+/// 登记一条汇率，同一币种对同一天已有汇率时覆盖
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct CreateExchangeRateRequest {
+    #[validate(length(equal = 3))]
+    pub base_currency: String,
+    #[validate(length(equal = 3))]
+    pub quote_currency: String,
+    /// 必须大于 0
+    pub rate: f64,
+    pub effective_date: chrono::NaiveDate,
+    #[validate(length(max = 100))]
+    pub source: Option<String>,
+}
+
+/// 批量导入汇率：`rates` 为 JSON 数组，或 `csv` 为带表头
+/// `effective_date,base_currency,quote_currency,rate` 的 CSV 文本，二者可同时提供
+#[derive(Debug, Deserialize, Validate)]
+pub struct ImportExchangeRatesRequest {
+    #[serde(default)]
+    pub rates: Vec<CreateExchangeRateRequest>,
+    pub csv: Option<String>,
+    /// 未单独指定来源的汇率使用该来源，默认 import
+    #[validate(length(max = 100))]
+    pub source: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportExchangeRatesResponse {
+    pub imported: u64,
+    pub errors: Vec<ExchangeRateImportError>,
+}
+
+/// `line` 为 CSV 行号（表头为第 1 行）或 JSON 数组下标（从 1 开始）
+#[derive(Debug, Serialize)]
+pub struct ExchangeRateImportError {
+    pub line: usize,
+    pub message: String,
+}
+
+/// 换算结果，找不到可用汇率时 `amount` 为空
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConvertedAmount {
+    pub currency: String,
+    pub amount: Option<f64>,
+    pub rate: Option<f64>,
+    /// 所用汇率的生效日期，经第三币种交叉换算时取较早的一个
+    pub rate_date: Option<chrono::NaiveDate>,
+}
@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+
+/// 实时推送的数据变更类型，同时作为 SSE 的 `event` 字段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChangeEventKind {
+    #[serde(rename = "model.created")]
+    ModelCreated,
+    #[serde(rename = "model.updated")]
+    ModelUpdated,
+    #[serde(rename = "model.deleted")]
+    ModelDeleted,
+    #[serde(rename = "manufacturer.created")]
+    ManufacturerCreated,
+    #[serde(rename = "manufacturer.updated")]
+    ManufacturerUpdated,
+    #[serde(rename = "manufacturer.deleted")]
+    ManufacturerDeleted,
+    #[serde(rename = "price.added")]
+    PriceAdded,
+    #[serde(rename = "purchase.added")]
+    PurchaseAdded,
+    #[serde(rename = "purchase.removed")]
+    PurchaseRemoved,
+    /// 只推送给通知的接收者
+    #[serde(rename = "notification.created")]
+    NotificationCreated,
+}
+
+impl ChangeEventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeEventKind::ModelCreated => "model.created",
+            ChangeEventKind::ModelUpdated => "model.updated",
+            ChangeEventKind::ModelDeleted => "model.deleted",
+            ChangeEventKind::ManufacturerCreated => "manufacturer.created",
+            ChangeEventKind::ManufacturerUpdated => "manufacturer.updated",
+            ChangeEventKind::ManufacturerDeleted => "manufacturer.deleted",
+            ChangeEventKind::PriceAdded => "price.added",
+            ChangeEventKind::PurchaseAdded => "purchase.added",
+            ChangeEventKind::PurchaseRemoved => "purchase.removed",
+            ChangeEventKind::NotificationCreated => "notification.created",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChangeEvent {
+    /// 单调递增，客户端断线重连时通过 `Last-Event-ID` 回传
+    pub id: u64,
+    pub kind: ChangeEventKind,
+    /// 变更对象的 ID，价格与购买事件为对应的模型 ID
+    pub entity_id: i32,
+    /// 触发变更的用户
+    pub actor_id: Option<i32>,
+    pub data: serde_json::Value,
+    pub created_at: chrono::NaiveDateTime,
+    /// 非空时只推送给该用户
+    #[serde(skip)]
+    pub recipient_id: Option<i32>,
+}
+
+impl ChangeEvent {
+    pub fn visible_to(&self, user_id: i32) -> bool {
+        self.recipient_id
+            .is_none_or(|recipient| recipient == user_id)
+    }
+}
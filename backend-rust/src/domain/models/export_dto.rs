@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    /// 每行一个 JSON 对象（JSON Lines）
+    #[serde(alias = "json", alias = "ndjson")]
+    Jsonl,
+    Xlsx,
+}
+
+impl ExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+            ExportFormat::Jsonl => "application/x-ndjson",
+            ExportFormat::Xlsx => {
+                "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+            }
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Jsonl => "jsonl",
+            ExportFormat::Xlsx => "xlsx",
+        }
+    }
+}
+
+/// 导出范围：全部模型目录，或某个用户收藏/购买过的模型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportScope {
+    Catalog,
+    User(i32),
+}
+
+/// 导出的一行数据，列名与批量导入的目标字段保持一致，便于导出后修改再导入
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportRow {
+    pub id: i32,
+    pub name: String,
+    pub manufacturer: Option<String>,
+    pub series: Option<String>,
+    pub category: Option<String>,
+    pub status: String,
+    pub release_date: Option<chrono::NaiveDate>,
+    pub tags: Vec<String>,
+    pub rating: Option<f64>,
+    pub latest_price: Option<f64>,
+    pub latest_price_currency: Option<String>,
+    pub latest_price_at: Option<chrono::NaiveDateTime>,
+    pub notes: Option<String>,
+    /// 仅指定 `currency` 时返回，按价格记录当天的汇率换算，缺少汇率时为空
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub converted_price: Option<Option<f64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub converted_currency: Option<String>,
+    /// 仅用户导出时返回
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub favorited: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub purchased: Option<bool>,
+}
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ModelImageResponse {
+    pub id: i32,
+    pub model_id: i32,
+    pub url: String,
+    pub thumbnail_url: String,
+    pub content_type: String,
+    pub width: i32,
+    pub height: i32,
+    pub size_bytes: i64,
+    pub is_primary: bool,
+    pub created_at: chrono::NaiveDateTime,
+}
@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportFormat {
+    Csv,
+    Json,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImportModelsRequest {
+    pub format: ImportFormat,
+    /// CSV 时为包含表头的文本；JSON 时为对象数组
+    pub data: serde_json::Value,
+    /// 目标字段 → 源列名，如 `{"name": "名称", "manufacturer": "厂商"}`；
+    /// 未映射的字段按同名列读取
+    #[serde(default)]
+    pub mapping: HashMap<String, String>,
+    /// 厂商不存在时自动创建
+    #[serde(default)]
+    pub auto_create_manufacturers: bool,
+    /// 只校验并生成报告，不写入数据库
+    #[serde(default)]
+    pub dry_run: bool,
+    /// 忽略疑似重复提示，强制创建新模型
+    #[serde(default)]
+    pub force: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportRowStatus {
+    Created,
+    Updated,
+    Skipped,
+    Error,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportRowResult {
+    /// 数据行序号，从 1 开始（不含表头）
+    pub row: usize,
+    pub status: ImportRowStatus,
+    pub model_id: Option<i32>,
+    pub name: Option<String>,
+    pub messages: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub total: usize,
+    pub created: usize,
+    pub updated: usize,
+    pub skipped: usize,
+    pub errors: usize,
+    /// 本次导入自动创建（dry_run 时为将要创建）的厂商
+    pub created_manufacturers: Vec<String>,
+    pub rows: Vec<ImportRowResult>,
+}
@@ -1,3 +1,4 @@
+use super::nullable::deserialize_nullable;
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
@@ -5,6 +6,8 @@ use validator::Validate;
 pub struct CreateManufacturerRequest {
     #[validate(length(min = 1, max = 255))]
     pub name: String,
+    #[validate(length(max = 200))]
+    pub full_name: Option<String>,
     #[validate(length(max = 100))]
     pub country: String,
     pub active_period_start: Option<chrono::NaiveDate>,
@@ -15,18 +18,41 @@ pub struct CreateManufacturerRequest {
 pub struct UpdateManufacturerRequest {
     #[validate(length(min = 1, max = 255))]
     pub name: Option<String>,
+    #[validate(length(max = 200))]
+    pub full_name: Option<String>,
     #[validate(length(max = 100))]
     pub country: Option<String>,
     pub active_period_start: Option<chrono::NaiveDate>,
     pub active_period_end: Option<chrono::NaiveDate>,
+    /// 乐观锁版本号，与 If-Match 请求头二选一
+    pub version: Option<i64>,
+}
+
+/// PATCH 请求：字段缺失表示不修改，显式 `null` 表示清空可空字段
+#[derive(Debug, Deserialize, Validate)]
+pub struct PatchManufacturerRequest {
+    #[validate(length(min = 1, max = 255))]
+    pub name: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    #[validate(length(max = 200))]
+    pub full_name: Option<Option<String>>,
+    #[validate(length(max = 100))]
+    pub country: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    pub active_period_start: Option<Option<chrono::NaiveDate>>,
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    pub active_period_end: Option<Option<chrono::NaiveDate>>,
+    pub version: Option<i64>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ManufacturerResponse {
     pub id: i32,
     pub name: String,
+    pub full_name: Option<String>,
     pub country: String,
     pub active_period_start: Option<chrono::NaiveDate>,
     pub active_period_end: Option<chrono::NaiveDate>,
     pub models_count: Option<i64>,
+    pub version: i64,
 }
@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EnumOption {
+    pub value: String,
+    pub label: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EnumsResponse {
+    pub status: Vec<EnumOption>,
+    pub grade: Vec<EnumOption>,
+    pub build_stage: Vec<EnumOption>,
+}
@@ -1,3 +1,20 @@
+pub mod activity_dto;
 pub mod auth;
+pub mod backup_dto;
+pub mod build_log_dto;
+pub mod calendar_dto;
+pub mod currency_dto;
+pub mod event_dto;
+pub mod export_dto;
+pub mod image_dto;
+pub mod import_dto;
 pub mod manufacturer_dto;
+pub mod meta_dto;
 pub mod model_dto;
+pub mod notification_dto;
+pub mod nullable;
+pub mod price_watch_dto;
+pub mod search_dto;
+pub mod series_dto;
+pub mod shop_dto;
+pub mod tag_dto;
@@ -1,3 +1,6 @@
+use super::nullable::deserialize_nullable;
+use crate::domain::entities::enums::{Grade, ModelStatus};
+use crate::domain::models::currency_dto::ConvertedAmount;
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
@@ -5,59 +8,226 @@ use validator::Validate;
 pub struct CreateModelRequest {
     #[validate(length(min = 1, max = 255))]
     pub name: String,
-    #[validate(length(max = 100))]
-    pub series: Option<String>,
-    #[validate(length(max = 50))]
-    pub category: Option<String>,
+    pub series_id: Option<i32>,
+    pub category: Option<Grade>,
     pub rating: Option<f32>,
     pub release_date: Option<chrono::NaiveDate>,
-    pub status: String,
+    pub preorder_open_date: Option<chrono::NaiveDate>,
+    pub status: ModelStatus,
     pub manufacturer_id: i32,
     pub parent_id: Option<i32>,
     pub notes: Option<String>,
+    /// 标签名称，不存在的标签会自动创建
+    pub tags: Option<Vec<String>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Validate)]
 pub struct UpdateModelRequest {
     #[validate(length(min = 1, max = 255))]
     pub name: Option<String>,
-    #[validate(length(max = 100))]
-    pub series: Option<String>,
-    #[validate(length(max = 50))]
-    pub category: Option<String>,
+    pub series_id: Option<i32>,
+    pub category: Option<Grade>,
     pub rating: Option<f32>,
     pub release_date: Option<chrono::NaiveDate>,
-    pub status: Option<String>,
+    pub preorder_open_date: Option<chrono::NaiveDate>,
+    pub status: Option<ModelStatus>,
     pub manufacturer_id: Option<i32>,
     pub parent_id: Option<i32>,
     pub notes: Option<String>,
+    /// 传入时整体替换模型的标签
+    pub tags: Option<Vec<String>>,
+    /// 乐观锁版本号，与 If-Match 请求头二选一
+    pub version: Option<i64>,
+}
+
+/// PATCH 请求：字段缺失表示不修改，显式 `null` 表示清空可空字段
+#[derive(Debug, Deserialize, Validate)]
+pub struct PatchModelRequest {
+    #[validate(length(min = 1, max = 255))]
+    pub name: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    pub series_id: Option<Option<i32>>,
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    pub category: Option<Option<Grade>>,
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    pub release_date: Option<Option<chrono::NaiveDate>>,
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    pub preorder_open_date: Option<Option<chrono::NaiveDate>>,
+    pub status: Option<ModelStatus>,
+    pub manufacturer_id: Option<i32>,
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    pub parent_id: Option<Option<i32>>,
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    pub notes: Option<Option<String>>,
+    pub tags: Option<Vec<String>>,
+    pub version: Option<i64>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ModelResponse {
     pub id: i32,
     pub name: String,
+    pub series_id: Option<i32>,
+    /// 系列名称，由 series_id 关联得到
     pub series: Option<String>,
-    pub category: Option<String>,
+    pub category: Option<Grade>,
     pub release_date: Option<chrono::NaiveDate>,
-    pub status: String,
+    pub preorder_open_date: Option<chrono::NaiveDate>,
+    pub status: ModelStatus,
     pub manufacturer_id: i32,
     pub parent_id: Option<i32>,
     pub notes: Option<String>,
+    /// 长期没有价格更新而被标记为疑似停产的时间
+    pub possibly_discontinued_at: Option<chrono::NaiveDateTime>,
+    pub tags: Vec<String>,
+    /// 主图地址，未上传图片时为空
+    pub primary_image_url: Option<String>,
     pub created_at: chrono::NaiveDateTime,
     pub updated_at: chrono::NaiveDateTime,
+    pub version: i64,
 }
 
-#[allow(dead_code)]
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PriceHistoryResponse {
     pub id: i32,
     pub price: f64,
     pub currency: String,
     pub source: Option<String>,
+    pub shop_id: Option<i32>,
     pub notes: Option<String>,
     pub model_id: i32,
     pub recorded_at: chrono::NaiveDateTime,
+    /// 请求指定 `currency` 时返回
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub converted: Option<ConvertedAmount>,
+}
+
+/// 记录一条价格
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreatePriceRequest {
+    #[validate(range(min = 0.0))]
+    pub price: f64,
+    /// ISO 4217 币种代码，默认 CNY
+    #[validate(length(equal = 3))]
+    pub currency: Option<String>,
+    /// 价格来源，如店铺名称或网址；与已登记店铺同名时自动关联
+    #[validate(length(max = 255))]
+    pub source: Option<String>,
+    /// 关联的店铺，未填写 `source` 时以店铺名称作为来源
+    pub shop_id: Option<i32>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ModelRevisionResponse {
+    pub id: i32,
+    pub model_id: i32,
+    pub revision: i32,
+    pub snapshot: serde_json::Value,
+    pub edited_by: Option<i32>,
+    /// 自动状态流转的原因，见 `StatusChangeReason`
+    pub change_reason: Option<String>,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+/// 定时任务修改模型时记录在修订中的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusChangeReason {
+    /// 预售模型已过发售日期，转为现货
+    Released,
+    /// 现货模型长期没有价格更新，标记为疑似停产
+    Stale,
+    /// 疑似停产的模型有了新价格或已不是现货，清除标记
+    StaleCleared,
+}
+
+impl StatusChangeReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StatusChangeReason::Released => "auto_released",
+            StatusChangeReason::Stale => "auto_possibly_discontinued",
+            StatusChangeReason::StaleCleared => "auto_possibly_discontinued_cleared",
+        }
+    }
+}
+
+/// 一次状态流转任务的结果，均为模型 ID
+#[derive(Debug, Default, Serialize)]
+pub struct StatusTransitionReport {
+    /// 预售转为现货
+    pub released: Vec<i32>,
+    /// 新标记为疑似停产
+    pub flagged: Vec<i32>,
+    /// 清除疑似停产标记
+    pub cleared: Vec<i32>,
+    /// 执行期间被他人修改而跳过，下次执行时重新判断
+    pub skipped: Vec<i32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MergeModelRequest {
+    /// 被合并并删除的模型
+    pub source_id: i32,
+    /// 目标模型的乐观锁版本号，与 If-Match 请求头二选一
+    pub version: Option<i64>,
+}
+
+/// 合并时从来源模型转移到目标模型的记录数
+#[derive(Debug, Default, Serialize)]
+pub struct MergeSummary {
+    pub price_history: u64,
+    /// 目标模型已监控同一地址时，来源模型的监控随模型删除
+    pub price_watches: u64,
+    pub favorites: u64,
+    pub purchases: u64,
+    /// 同一用户在两个模型上都有购买记录时，改挂到保留记录下的制作日志数
+    pub build_log_entries: u64,
+    pub variants: u64,
+    pub tags: u64,
+    pub images: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MergeModelResponse {
+    pub model: ModelResponse,
+    pub moved: MergeSummary,
+}
+
+/// 创建模型时检测到的疑似重复记录
+#[derive(Debug, Serialize)]
+pub struct DuplicateCandidate {
+    pub id: i32,
+    pub name: String,
+    pub similarity: f64,
+}
+
+/// 重复检测报表中的一对疑似重复模型（同厂商、同等级）
+#[derive(Debug, Serialize)]
+pub struct DuplicatePair {
+    pub manufacturer_id: i32,
+    pub category: Option<Grade>,
+    pub first_id: i32,
+    pub first_name: String,
+    pub second_id: i32,
+    pub second_name: String,
+    pub similarity: f64,
+}
+
+/// 模型列表的筛选条件
+#[derive(Debug, Default, Clone)]
+pub struct ModelFilter {
+    /// 标签 slug
+    pub tags: Vec<String>,
+    pub tag_mode: TagMatchMode,
+}
+
+/// 多个标签的匹配方式：any 命中任一即可，all 需全部命中
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TagMatchMode {
+    #[default]
+    Any,
+    All,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -74,7 +244,122 @@ pub struct ToggleFavoriteRequest {
     pub notes: Option<String>,
 }
 
+/// 设置收藏的心愿单信息，未传的字段恢复默认值
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct UpdateWishlistRequest {
+    #[validate(range(min = 0.0))]
+    pub target_price: Option<f64>,
+    /// ISO 4217 币种代码，默认 CNY
+    #[validate(length(equal = 3))]
+    pub target_currency: Option<String>,
+    #[validate(range(min = 1, max = 5))]
+    pub priority: Option<i16>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WishlistItemResponse {
+    pub model: ModelResponse,
+    pub notes: Option<String>,
+    pub target_price: Option<f64>,
+    pub target_currency: String,
+    pub priority: i16,
+    pub latest_price: Option<f64>,
+    pub latest_price_currency: Option<String>,
+    /// 请求指定 `currency` 时返回
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latest_price_converted: Option<ConvertedAmount>,
+    /// 最新价格是否已达到目标价，币种不同时按价格记录当天的汇率换算后比较
+    pub target_reached: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CollectionValueResponse {
+    pub currency: String,
+    /// 可换算价格之和
+    pub total_value: f64,
+    pub purchased: u64,
+    /// 没有价格记录的模型
+    pub missing_price: Vec<i32>,
+    /// 缺少汇率、未计入总价值的模型
+    pub missing_rate: Vec<i32>,
+    pub items: Vec<CollectionValueItem>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CollectionValueItem {
+    pub model_id: i32,
+    pub name: String,
+    /// 最新价格及其原始币种
+    pub price: f64,
+    pub price_currency: String,
+    pub recorded_at: chrono::NaiveDateTime,
+    pub converted: ConvertedAmount,
+}
+
+/// 模型所在的整棵变体树
+#[derive(Debug, Serialize)]
+pub struct ModelFamilyResponse {
+    pub model_id: i32,
+    pub root_id: i32,
+    /// 从根模型到所请求模型的 ID 路径
+    pub path: Vec<i32>,
+    /// 价格换算的目标币种
+    pub currency: String,
+    pub tree: FamilyNode,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FamilyNode {
+    pub model: ModelResponse,
+    /// 距根模型的层数，根模型为 0
+    pub depth: i32,
+    /// 购买该模型的用户数
+    pub owner_count: i64,
+    pub favorite_count: i64,
+    /// 最新价格，`converted` 为换算到目标币种的结果
+    pub latest_price: Option<PriceHistoryResponse>,
+    /// 以该模型为根的分支（含自身）的汇总
+    pub branch: FamilyBranchSummary,
+    /// 直接变体，按名称排序
+    pub variants: Vec<FamilyNode>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct FamilyBranchSummary {
+    pub model_count: u64,
+    /// 至少有一位用户购买的模型数
+    pub owned_count: u64,
+    /// 各模型购买人数之和
+    pub owner_total: i64,
+    /// 最新价格可以换算的模型数，以下价格只统计这些模型
+    pub priced_count: u64,
+    pub min_price: Option<f64>,
+    pub max_price: Option<f64>,
+    /// 最新价格之和，即集齐整个分支的参考价
+    pub total_price: f64,
+}
+
 #[derive(Debug, Serialize, Deserialize, Validate)]
 pub struct TogglePurchaseRequest {
     pub notes: Option<String>,
 }
+
+/// 设置购买记录的备注与预售日期，未传的字段清空
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdatePurchaseRequest {
+    pub notes: Option<String>,
+    /// 预售尾款截止日期
+    pub payment_due_date: Option<chrono::NaiveDate>,
+    /// 预计发货日期
+    pub ship_date: Option<chrono::NaiveDate>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PurchaseResponse {
+    pub model_id: i32,
+    pub notes: Option<String>,
+    pub payment_due_date: Option<chrono::NaiveDate>,
+    pub ship_date: Option<chrono::NaiveDate>,
+    pub created_at: chrono::NaiveDateTime,
+}
@@ -0,0 +1,90 @@
+use crate::domain::entities::enums::NotificationKind;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+#[derive(Debug, Deserialize)]
+pub struct NotificationListParams {
+    /// 只返回未读通知
+    #[serde(default)]
+    pub unread: bool,
+    #[serde(default = "default_page")]
+    pub page: u64,
+    #[serde(default = "default_per_page", alias = "page_size")]
+    pub per_page: u64,
+}
+
+fn default_page() -> u64 {
+    1
+}
+
+fn default_per_page() -> u64 {
+    20
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NotificationResponse {
+    pub id: i32,
+    pub kind: NotificationKind,
+    pub model_id: Option<i32>,
+    pub title: String,
+    pub body: Option<String>,
+    pub payload: serde_json::Value,
+    pub read_at: Option<chrono::NaiveDateTime>,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NotificationListResponse {
+    pub notifications: Vec<NotificationResponse>,
+    pub total: u64,
+    pub unread: u64,
+    pub page: u64,
+    pub per_page: u64,
+    pub total_pages: u64,
+}
+
+/// 确认（标记已读）通知，未传 `ids` 时确认全部未读通知
+#[derive(Debug, Default, Deserialize)]
+pub struct AcknowledgeNotificationsRequest {
+    pub ids: Option<Vec<i32>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AcknowledgeNotificationsResponse {
+    pub acknowledged: u64,
+}
+
+/// 通知渠道偏好，未传的字段恢复默认值（仅站内通知）
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateNotificationPreferencesRequest {
+    #[serde(default = "default_true")]
+    pub in_app_enabled: bool,
+    #[serde(default)]
+    pub webhook_enabled: bool,
+    #[validate(url, length(max = 500))]
+    pub webhook_url: Option<String>,
+    /// 重新生成 webhook 签名密钥，首次启用 webhook 时会自动生成
+    #[serde(default)]
+    pub regenerate_webhook_secret: bool,
+    #[serde(default)]
+    pub email_enabled: bool,
+    #[validate(email, length(max = 255))]
+    pub email: Option<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NotificationPreferencesResponse {
+    pub in_app_enabled: bool,
+    pub webhook_enabled: bool,
+    pub webhook_url: Option<String>,
+    /// 用于校验 `X-ModelHub-Signature` 请求头
+    pub webhook_secret: Option<String>,
+    pub email_enabled: bool,
+    pub email: Option<String>,
+    /// 服务器是否配置了 SMTP，未配置时不能启用邮件通知
+    pub email_available: bool,
+}
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Deserializer};
+
+/// 配合 `#[serde(default)]` 使用：字段缺失时为 `None`，显式 `null` 时为 `Some(None)`，
+/// 用于 PATCH 请求中区分“不修改”和“清空”
+pub fn deserialize_nullable<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
@@ -0,0 +1,87 @@
+use super::model_dto::PriceHistoryResponse;
+use super::nullable::deserialize_nullable;
+use crate::domain::entities::enums::PriceSourceKind;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreatePriceWatchRequest {
+    #[validate(url, length(max = 1000))]
+    pub url: String,
+    pub source_kind: PriceSourceKind,
+    /// HTML 来源为 CSS 选择器（可加 `@属性名`），JSON 来源为 JSON 路径
+    #[validate(length(min = 1, max = 500))]
+    pub selector: String,
+    /// 页面价格的币种，默认 CNY
+    #[validate(length(equal = 3))]
+    pub currency: Option<String>,
+    /// cron 表达式，默认每 6 小时抓取一次
+    #[validate(length(min = 1, max = 100))]
+    pub schedule: Option<String>,
+    pub shop_id: Option<i32>,
+    pub enabled: Option<bool>,
+}
+
+/// PATCH 请求：字段缺失表示不修改，`shop_id` 显式传 `null` 表示解除店铺关联
+#[derive(Debug, Deserialize, Validate)]
+pub struct PatchPriceWatchRequest {
+    #[validate(url, length(max = 1000))]
+    pub url: Option<String>,
+    pub source_kind: Option<PriceSourceKind>,
+    #[validate(length(min = 1, max = 500))]
+    pub selector: Option<String>,
+    #[validate(length(equal = 3))]
+    pub currency: Option<String>,
+    #[validate(length(min = 1, max = 100))]
+    pub schedule: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    pub shop_id: Option<Option<i32>>,
+    pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PriceWatchResponse {
+    pub id: i32,
+    pub model_id: i32,
+    pub shop_id: Option<i32>,
+    pub url: String,
+    pub source_kind: PriceSourceKind,
+    pub selector: String,
+    pub currency: String,
+    pub schedule: String,
+    pub enabled: bool,
+    pub next_run_at: chrono::NaiveDateTime,
+    pub last_run_at: Option<chrono::NaiveDateTime>,
+    pub last_price: Option<f64>,
+    pub last_error: Option<String>,
+    pub consecutive_failures: i32,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+/// 试抓取：不保存监控，只返回解析结果，用于调试选择器
+#[derive(Debug, Deserialize, Validate)]
+pub struct PreviewPriceRequest {
+    #[validate(url, length(max = 1000))]
+    pub url: String,
+    pub source_kind: PriceSourceKind,
+    #[validate(length(min = 1, max = 500))]
+    pub selector: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PreviewPriceResponse {
+    pub price: f64,
+    /// 选择器匹配到的原始文本
+    pub raw: String,
+}
+
+/// 立即抓取一次的结果
+#[derive(Debug, Serialize)]
+pub struct PriceCheckResponse {
+    pub watch: PriceWatchResponse,
+    /// 抓取失败时为空，原因见 `error`
+    pub price: Option<f64>,
+    /// 新写入的价格记录；价格与上次抓取相同时不重复写入，此时为空
+    pub recorded: Option<PriceHistoryResponse>,
+    pub error: Option<String>,
+}
@@ -0,0 +1,31 @@
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchHitKind {
+    Model,
+    Manufacturer,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchHighlight {
+    pub field: &'static str,
+    /// 命中部分以 `<mark>` 包裹，其余内容已做 HTML 转义
+    pub snippet: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchHit {
+    pub kind: SearchHitKind,
+    pub id: i32,
+    pub title: String,
+    pub score: f32,
+    pub highlights: Vec<SearchHighlight>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchResponse {
+    pub query: String,
+    pub total: usize,
+    pub hits: Vec<SearchHit>,
+}
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct CreateSeriesRequest {
+    #[validate(length(min = 1, max = 200))]
+    pub name: String,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    #[validate(length(max = 50))]
+    pub universe: Option<String>,
+    pub air_start_year: Option<i32>,
+    pub air_end_year: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct UpdateSeriesRequest {
+    #[validate(length(min = 1, max = 200))]
+    pub name: Option<String>,
+    pub aliases: Option<Vec<String>>,
+    #[validate(length(max = 50))]
+    pub universe: Option<String>,
+    pub air_start_year: Option<i32>,
+    pub air_end_year: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SeriesResponse {
+    pub id: i32,
+    pub name: String,
+    pub aliases: Vec<String>,
+    pub universe: Option<String>,
+    pub air_start_year: Option<i32>,
+    pub air_end_year: Option<i32>,
+    pub models_count: Option<i64>,
+}
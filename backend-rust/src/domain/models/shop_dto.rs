@@ -0,0 +1,54 @@
+use super::model_dto::PriceHistoryResponse;
+use super::nullable::deserialize_nullable;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateShopRequest {
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+    /// 所在平台，如 pdd、taobao、jd、amazon_jp、offline
+    #[validate(length(max = 50))]
+    pub platform: Option<String>,
+    #[validate(url, length(max = 500))]
+    pub url: Option<String>,
+    #[validate(length(max = 50))]
+    pub region: Option<String>,
+    pub trust_notes: Option<String>,
+}
+
+/// PATCH 请求：字段缺失表示不修改，显式 `null` 表示清空可空字段
+#[derive(Debug, Deserialize, Validate)]
+pub struct PatchShopRequest {
+    #[validate(length(min = 1, max = 100))]
+    pub name: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    #[validate(length(max = 50))]
+    pub platform: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    #[validate(url, length(max = 500))]
+    pub url: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    #[validate(length(max = 50))]
+    pub region: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    pub trust_notes: Option<Option<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ShopResponse {
+    pub id: i32,
+    pub name: String,
+    pub platform: Option<String>,
+    pub url: Option<String>,
+    pub region: Option<String>,
+    pub trust_notes: Option<String>,
+}
+
+/// 某店铺对某模型的最新报价
+#[derive(Debug, Serialize)]
+pub struct ShopPriceResponse {
+    pub shop: ShopResponse,
+    /// `converted` 始终返回，用于跨币种比较
+    pub latest_price: PriceHistoryResponse,
+}
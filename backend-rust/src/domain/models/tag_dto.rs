@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TagResponse {
+    pub id: i32,
+    pub name: String,
+    pub slug: String,
+    pub models_count: i64,
+}
@@ -0,0 +1,40 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                -- 模型修订历史表 - 每次更新模型前保存旧数据快照
+                CREATE TABLE IF NOT EXISTS model_revisions (
+                    id SERIAL PRIMARY KEY,
+                    model_id INTEGER NOT NULL REFERENCES models(id) ON DELETE CASCADE,
+                    revision INTEGER NOT NULL,                  -- 模型内递增的修订号
+                    snapshot JSONB NOT NULL,                    -- 更新前的整行数据
+                    edited_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+                    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                    UNIQUE(model_id, revision)
+                );
+
+                CREATE INDEX IF NOT EXISTS idx_model_revisions_model ON model_revisions(model_id);
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS model_revisions;")
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,164 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::{ConnectionTrait, Statement};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                -- 无法识别的状态与等级会分别改写为 in_stock 与 other，改写前把原始取值记入
+                -- migration_unmapped_values 以便核对与人工修正，回滚迁移时据此还原
+                CREATE TABLE IF NOT EXISTS migration_unmapped_values (
+                    id SERIAL PRIMARY KEY,
+                    source_table VARCHAR(50) NOT NULL,      -- models / model_revisions
+                    row_id INTEGER NOT NULL,
+                    field VARCHAR(50) NOT NULL,             -- status / category
+                    original_value TEXT NOT NULL,
+                    stored_value TEXT NOT NULL,             -- 改写后的取值
+                    recorded_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+                );
+
+                INSERT INTO migration_unmapped_values (source_table, row_id, field, original_value, stored_value)
+                SELECT 'models', id, 'status', status, 'in_stock'
+                FROM models
+                WHERE trim(status) <> '' AND lower(trim(status)) NOT IN ('预售', 'pre_order', 'pre-order', 'preorder', 'pre order', '下架', '停产', 'discontinued', '现货', 'in_stock', 'in-stock', 'instock', 'in stock')
+                UNION ALL
+                SELECT 'model_revisions', id, 'status', snapshot->>'status', 'in_stock'
+                FROM model_revisions
+                WHERE trim(snapshot->>'status') <> ''
+                    AND lower(trim(snapshot->>'status')) NOT IN ('预售', 'pre_order', 'pre-order', 'preorder', 'pre order', '下架', '停产', 'discontinued', '现货', 'in_stock', 'in-stock', 'instock', 'in stock')
+                UNION ALL
+                SELECT 'models', id, 'category', category, 'other'
+                FROM models
+                WHERE trim(category) <> '' AND NOT (
+                        lower(replace(replace(trim(category), ' ', ''), '/', '')) IN ('hg', 'rg', 'mg', 'pg', 'sd', 'mgex', 'mgsd', 're100', 'fm', 'eg', 'hirm', 'other')
+                        OR trim(category) = 'BB战士'
+                        OR lower(replace(trim(category), ' ', '')) IN ('fullmechanics', 'entrygrade'))
+                UNION ALL
+                SELECT 'model_revisions', id, 'category', snapshot->>'category', 'other'
+                FROM model_revisions
+                WHERE trim(snapshot->>'category') <> '' AND NOT (
+                        lower(replace(replace(trim(snapshot->>'category'), ' ', ''), '/', '')) IN ('hg', 'rg', 'mg', 'pg', 'sd', 'mgex', 'mgsd', 're100', 'fm', 'eg', 'hirm', 'other')
+                        OR trim(snapshot->>'category') = 'BB战士'
+                        OR lower(replace(trim(snapshot->>'category'), ' ', '')) IN ('fullmechanics', 'entrygrade'))
+                ORDER BY 1, 2, 3;
+
+                -- 统一状态取值：中文标签与各种英文写法 -> in_stock / pre_order / discontinued
+                UPDATE models SET status = CASE
+                    WHEN lower(trim(status)) IN ('预售', 'pre_order', 'pre-order', 'preorder', 'pre order') THEN 'pre_order'
+                    WHEN lower(trim(status)) IN ('下架', '停产', 'discontinued') THEN 'discontinued'
+                    ELSE 'in_stock'
+                END;
+
+                -- 统一等级取值为小写代码，无法识别的归为 other
+                UPDATE models SET category = CASE
+                    WHEN category IS NULL OR trim(category) = '' THEN NULL
+                    WHEN lower(replace(replace(trim(category), ' ', ''), '/', '')) IN
+                        ('hg', 'rg', 'mg', 'pg', 'sd', 'mgex', 'mgsd', 're100', 'fm', 'eg', 'hirm')
+                        THEN lower(replace(replace(trim(category), ' ', ''), '/', ''))
+                    WHEN trim(category) = 'BB战士' THEN 'sd'
+                    WHEN lower(replace(trim(category), ' ', '')) = 'fullmechanics' THEN 'fm'
+                    WHEN lower(replace(trim(category), ' ', '')) = 'entrygrade' THEN 'eg'
+                    ELSE 'other'
+                END;
+
+                -- 修订快照中的取值按同样规则统一，保证旧版本可以恢复
+                UPDATE model_revisions SET snapshot = jsonb_set(snapshot, '{status}', to_jsonb(CASE
+                    WHEN lower(trim(snapshot->>'status')) IN ('预售', 'pre_order', 'pre-order', 'preorder', 'pre order') THEN 'pre_order'
+                    WHEN lower(trim(snapshot->>'status')) IN ('下架', '停产', 'discontinued') THEN 'discontinued'
+                    ELSE 'in_stock'
+                END))
+                WHERE snapshot ? 'status';
+
+                UPDATE model_revisions r SET snapshot = jsonb_set(r.snapshot, '{category}', CASE
+                    WHEN c.raw IS NULL OR trim(c.raw) = '' THEN 'null'::jsonb
+                    WHEN c.compact IN ('hg', 'rg', 'mg', 'pg', 'sd', 'mgex', 'mgsd', 're100', 'fm', 'eg', 'hirm')
+                        THEN to_jsonb(c.compact)
+                    WHEN trim(c.raw) = 'BB战士' THEN '"sd"'::jsonb
+                    WHEN c.compact = 'fullmechanics' THEN '"fm"'::jsonb
+                    WHEN c.compact = 'entrygrade' THEN '"eg"'::jsonb
+                    ELSE '"other"'::jsonb
+                END)
+                FROM (
+                    SELECT id,
+                           snapshot->>'category' AS raw,
+                           lower(replace(replace(trim(snapshot->>'category'), ' ', ''), '/', '')) AS compact
+                    FROM model_revisions
+                    WHERE snapshot ? 'category'
+                ) c
+                WHERE r.id = c.id;
+
+                ALTER TABLE models ALTER COLUMN status SET DEFAULT 'in_stock';
+                ALTER TABLE models ALTER COLUMN status SET NOT NULL;
+
+                ALTER TABLE models ADD CONSTRAINT chk_models_status
+                    CHECK (status IN ('in_stock', 'pre_order', 'discontinued'));
+                ALTER TABLE models ADD CONSTRAINT chk_models_category
+                    CHECK (category IN ('hg', 'rg', 'mg', 'pg', 'sd', 'mgex', 'mgsd', 're100', 'fm', 'eg', 'hirm', 'other'));
+                "#,
+            )
+            .await?;
+
+        let unmapped = manager
+            .get_connection()
+            .query_one(Statement::from_string(
+                manager.get_database_backend(),
+                "SELECT COUNT(*) AS count FROM migration_unmapped_values".to_string(),
+            ))
+            .await?
+            .map(|row| row.try_get::<i64>("", "count"))
+            .transpose()?
+            .unwrap_or_default();
+        if unmapped > 0 {
+            tracing::warn!(
+                "{} 个无法识别的模型状态/等级已改写为 in_stock / other，原始取值见 migration_unmapped_values 表",
+                unmapped
+            );
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                ALTER TABLE models DROP CONSTRAINT IF EXISTS chk_models_status;
+                ALTER TABLE models DROP CONSTRAINT IF EXISTS chk_models_category;
+                ALTER TABLE models ALTER COLUMN status DROP NOT NULL;
+                ALTER TABLE models ALTER COLUMN status SET DEFAULT '现货';
+
+                UPDATE models SET status = CASE status
+                    WHEN 'pre_order' THEN '预售'
+                    WHEN 'discontinued' THEN '下架'
+                    ELSE '现货'
+                END;
+
+                -- 还原迁移时无法识别而被改写的原始取值
+                UPDATE models m SET status = l.original_value
+                FROM migration_unmapped_values l
+                WHERE l.source_table = 'models' AND l.field = 'status' AND l.row_id = m.id;
+                UPDATE models m SET category = l.original_value
+                FROM migration_unmapped_values l
+                WHERE l.source_table = 'models' AND l.field = 'category' AND l.row_id = m.id;
+                UPDATE model_revisions r SET snapshot = jsonb_set(r.snapshot, ARRAY[l.field], to_jsonb(l.original_value))
+                FROM migration_unmapped_values l
+                WHERE l.source_table = 'model_revisions' AND l.row_id = r.id AND l.field = 'status';
+                UPDATE model_revisions r SET snapshot = jsonb_set(r.snapshot, ARRAY[l.field], to_jsonb(l.original_value))
+                FROM migration_unmapped_values l
+                WHERE l.source_table = 'model_revisions' AND l.row_id = r.id AND l.field = 'category';
+
+                DROP TABLE IF EXISTS migration_unmapped_values;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
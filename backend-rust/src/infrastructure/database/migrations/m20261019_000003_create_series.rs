@@ -0,0 +1,116 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                -- 系列名称来自 models.series（VARCHAR(200)）与修订快照（长度不限），
+                -- 超出 series.name 长度的值无法迁移，列出后中止，需先手工修正
+                DO $$
+                DECLARE
+                    too_long TEXT;
+                BEGIN
+                    SELECT string_agg(format('%s #%s（%s 个字符）: %s…', source, id, char_length(name), left(name, 50)), E'\n')
+                    INTO too_long
+                    FROM (
+                        SELECT 'models' AS source, id, trim(series) AS name FROM models
+                        UNION ALL
+                        SELECT 'model_revisions', id, trim(snapshot->>'series') FROM model_revisions
+                    ) names
+                    WHERE char_length(name) > 200;
+                    IF too_long IS NOT NULL THEN
+                        RAISE EXCEPTION '以下系列名称超过 200 个字符，请先缩短后再迁移:%', E'\n' || too_long;
+                    END IF;
+                END
+                $$;
+
+                -- 作品/系列表 - 替代 models.series 自由文本
+                CREATE TABLE IF NOT EXISTS series (
+                    id SERIAL PRIMARY KEY,
+                    name VARCHAR(200) UNIQUE NOT NULL,          -- 系列名称，如 UC / SEED / 00，与原 models.series 长度一致
+                    aliases TEXT[] NOT NULL DEFAULT '{}',       -- 别名，如 宇宙世纪 / Universal Century
+                    universe VARCHAR(50),                       -- 世界观/纪年，如 UC / CE / AD / PD
+                    air_start_year INTEGER,                     -- 首播年份
+                    air_end_year INTEGER,                       -- 完结年份
+                    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+                );
+
+                -- 以忽略大小写的方式合并现有系列文本（含修订快照中的历史值），
+                -- 优先取模型表中最先出现的写法作为名称
+                INSERT INTO series (name)
+                SELECT DISTINCT ON (lower(name)) name
+                FROM (
+                    SELECT trim(series) AS name, 0 AS priority, id
+                    FROM models
+                    WHERE series IS NOT NULL AND trim(series) <> ''
+                    UNION ALL
+                    SELECT trim(snapshot->>'series'), 1, id
+                    FROM model_revisions
+                    WHERE trim(snapshot->>'series') <> ''
+                ) names
+                ORDER BY lower(name), priority, id
+                ON CONFLICT (name) DO NOTHING;
+
+                ALTER TABLE models ADD COLUMN series_id INTEGER REFERENCES series(id) ON DELETE SET NULL;
+
+                UPDATE models m SET series_id = s.id
+                FROM series s
+                WHERE lower(trim(m.series)) = lower(s.name);
+
+                CREATE INDEX IF NOT EXISTS idx_models_series_id ON models(series_id);
+
+                -- 修订快照中的系列文本同样改写为 series_id，否则恢复旧版本会清空系列
+                UPDATE model_revisions r
+                SET snapshot = (r.snapshot - 'series') || jsonb_build_object('series_id', s.id)
+                FROM series s
+                WHERE lower(trim(r.snapshot->>'series')) = lower(s.name);
+                UPDATE model_revisions
+                SET snapshot = (snapshot - 'series') || '{"series_id": null}'::jsonb
+                WHERE snapshot ? 'series';
+
+                ALTER TABLE models DROP COLUMN series;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                ALTER TABLE models ADD COLUMN series VARCHAR(200);
+
+                UPDATE models m SET series = s.name
+                FROM series s
+                WHERE m.series_id = s.id;
+
+                CREATE INDEX IF NOT EXISTS idx_models_series ON models(series);
+
+                UPDATE model_revisions r
+                SET snapshot = (r.snapshot - 'series_id')
+                    || jsonb_build_object('series', s.name)
+                FROM series s
+                WHERE (r.snapshot->>'series_id')::INTEGER = s.id;
+                UPDATE model_revisions
+                SET snapshot = (snapshot - 'series_id') || '{"series": null}'::jsonb
+                WHERE snapshot ? 'series_id';
+
+                ALTER TABLE models DROP COLUMN series_id;
+                DROP TABLE IF EXISTS series;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                -- 标签表 - 如 Premium Bandai / 透明版 / 活动限定
+                CREATE TABLE IF NOT EXISTS tags (
+                    id SERIAL PRIMARY KEY,
+                    name VARCHAR(50) NOT NULL,                  -- 展示名称
+                    slug VARCHAR(50) UNIQUE NOT NULL,           -- 筛选用标识，小写并以 - 连接
+                    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+                );
+
+                -- 模型标签关联表
+                CREATE TABLE IF NOT EXISTS model_tags (
+                    model_id INTEGER NOT NULL REFERENCES models(id) ON DELETE CASCADE,
+                    tag_id INTEGER NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+                    PRIMARY KEY (model_id, tag_id)
+                );
+
+                CREATE INDEX IF NOT EXISTS idx_model_tags_tag ON model_tags(tag_id);
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                DROP TABLE IF EXISTS model_tags;
+                DROP TABLE IF EXISTS tags;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,87 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                -- 厂商全称，初始化脚本中已存在，此处兼容旧库
+                ALTER TABLE manufacturers ADD COLUMN IF NOT EXISTS full_name VARCHAR(200);
+
+                -- 检索文本预处理：转小写，非字母数字视为分隔符，
+                -- 中日文字符拆成单字 + 相邻二元组，使 simple 分词器也能按片段命中
+                -- 需与 services/search_text.rs 中的查询词切分规则保持一致
+                CREATE OR REPLACE FUNCTION modelhub_search_text(input TEXT) RETURNS TEXT
+                LANGUAGE plpgsql IMMUTABLE PARALLEL SAFE AS $$
+                DECLARE
+                    src TEXT := lower(coalesce(input, ''));
+                    result TEXT := '';
+                    ch TEXT;
+                    prev_cjk BOOLEAN := FALSE;
+                BEGIN
+                    FOR i IN 1..char_length(src) LOOP
+                        ch := substr(src, i, 1);
+                        IF ch ~ '[\u3040-\u30ff\u3400-\u9fff\uf900-\ufaff]' THEN
+                            result := result || ' ' || ch;
+                            IF substr(src, i + 1, 1) ~ '[\u3040-\u30ff\u3400-\u9fff\uf900-\ufaff]' THEN
+                                result := result || ' ' || ch || substr(src, i + 1, 1);
+                            END IF;
+                            prev_cjk := TRUE;
+                        ELSIF ch ~ '[[:alnum:]]' THEN
+                            IF prev_cjk THEN
+                                result := result || ' ';
+                            END IF;
+                            result := result || ch;
+                            prev_cjk := FALSE;
+                        ELSE
+                            result := result || ' ';
+                            prev_cjk := FALSE;
+                        END IF;
+                    END LOOP;
+                    RETURN result;
+                END;
+                $$;
+
+                -- 模型检索向量：名称权重 A，备注权重 C（系列名称在查询时按权重 B 拼接）
+                ALTER TABLE models ADD COLUMN IF NOT EXISTS search_vector tsvector
+                    GENERATED ALWAYS AS (
+                        setweight(to_tsvector('simple', modelhub_search_text(name)), 'A') ||
+                        setweight(to_tsvector('simple', modelhub_search_text(notes)), 'C')
+                    ) STORED;
+
+                -- 厂商检索向量：简称权重 A，全称权重 B
+                ALTER TABLE manufacturers ADD COLUMN IF NOT EXISTS search_vector tsvector
+                    GENERATED ALWAYS AS (
+                        setweight(to_tsvector('simple', modelhub_search_text(name)), 'A') ||
+                        setweight(to_tsvector('simple', modelhub_search_text(full_name)), 'B')
+                    ) STORED;
+
+                CREATE INDEX IF NOT EXISTS idx_models_search_vector ON models USING GIN (search_vector);
+                CREATE INDEX IF NOT EXISTS idx_manufacturers_search_vector ON manufacturers USING GIN (search_vector);
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                ALTER TABLE models DROP COLUMN IF EXISTS search_vector;
+                ALTER TABLE manufacturers DROP COLUMN IF EXISTS search_vector;
+                DROP FUNCTION IF EXISTS modelhub_search_text(TEXT);
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                -- 价格记录表与 price_history 实体对齐：补充币种与记录时间，价格改为浮点
+                ALTER TABLE price_history ADD COLUMN IF NOT EXISTS currency VARCHAR(3) NOT NULL DEFAULT 'CNY';
+                ALTER TABLE price_history ADD COLUMN IF NOT EXISTS recorded_at TIMESTAMP;
+
+                -- 初始化脚本中的 price_date 为记录日期，迁移到 recorded_at 并允许新记录省略
+                DO $$
+                BEGIN
+                    IF EXISTS (
+                        SELECT 1 FROM information_schema.columns
+                        WHERE table_name = 'price_history' AND column_name = 'price_date'
+                    ) THEN
+                        UPDATE price_history SET recorded_at = price_date::timestamp WHERE recorded_at IS NULL;
+                        ALTER TABLE price_history ALTER COLUMN price_date SET DEFAULT CURRENT_DATE;
+                    END IF;
+                END $$;
+
+                UPDATE price_history SET recorded_at = CURRENT_TIMESTAMP WHERE recorded_at IS NULL;
+                ALTER TABLE price_history ALTER COLUMN recorded_at SET DEFAULT CURRENT_TIMESTAMP;
+                ALTER TABLE price_history ALTER COLUMN recorded_at SET NOT NULL;
+                ALTER TABLE price_history ALTER COLUMN price TYPE DOUBLE PRECISION;
+
+                CREATE INDEX IF NOT EXISTS idx_price_history_model_recorded
+                    ON price_history(model_id, recorded_at DESC);
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                DROP INDEX IF EXISTS idx_price_history_model_recorded;
+                ALTER TABLE price_history ALTER COLUMN price TYPE DECIMAL(10,2);
+                ALTER TABLE price_history DROP COLUMN IF EXISTS recorded_at;
+                ALTER TABLE price_history DROP COLUMN IF EXISTS currency;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                -- 模型图片表 - 文件本身保存在存储后端，这里只记录对象键
+                CREATE TABLE IF NOT EXISTS model_images (
+                    id SERIAL PRIMARY KEY,
+                    model_id INTEGER NOT NULL REFERENCES models(id) ON DELETE CASCADE,
+                    storage_key VARCHAR(255) NOT NULL,          -- 原图对象键
+                    thumbnail_key VARCHAR(255) NOT NULL,        -- 缩略图对象键
+                    content_type VARCHAR(50) NOT NULL,
+                    width INTEGER NOT NULL,
+                    height INTEGER NOT NULL,
+                    size_bytes BIGINT NOT NULL,
+                    is_primary BOOLEAN NOT NULL DEFAULT FALSE,  -- 主图，在列表与详情中展示
+                    uploaded_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+                    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+                );
+
+                CREATE INDEX IF NOT EXISTS idx_model_images_model ON model_images(model_id);
+                -- 每个模型最多一张主图
+                CREATE UNIQUE INDEX IF NOT EXISTS idx_model_images_primary
+                    ON model_images(model_id) WHERE is_primary;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS model_images;")
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,61 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                -- 制作日志表 - 挂在购买记录下，每条记录一次制作进度
+                CREATE TABLE IF NOT EXISTS build_log_entries (
+                    id SERIAL PRIMARY KEY,
+                    purchase_id INTEGER NOT NULL REFERENCES user_model_purchase(id) ON DELETE CASCADE,
+                    entry_date DATE NOT NULL DEFAULT CURRENT_DATE,
+                    content TEXT,
+                    hours_spent DOUBLE PRECISION CHECK (hours_spent >= 0), -- 本次投入的工时
+                    stage VARCHAR(20),                          -- 本条记录进入的制作阶段，为空表示阶段不变
+                    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+                );
+
+                CREATE INDEX IF NOT EXISTS idx_build_log_entries_purchase
+                    ON build_log_entries(purchase_id, entry_date);
+
+                -- 制作日志图片表 - 文件保存在存储后端
+                CREATE TABLE IF NOT EXISTS build_log_images (
+                    id SERIAL PRIMARY KEY,
+                    entry_id INTEGER NOT NULL REFERENCES build_log_entries(id) ON DELETE CASCADE,
+                    storage_key VARCHAR(255) NOT NULL,
+                    thumbnail_key VARCHAR(255) NOT NULL,
+                    content_type VARCHAR(50) NOT NULL,
+                    width INTEGER NOT NULL,
+                    height INTEGER NOT NULL,
+                    size_bytes BIGINT NOT NULL,
+                    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+                );
+
+                CREATE INDEX IF NOT EXISTS idx_build_log_images_entry ON build_log_images(entry_id);
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                DROP TABLE IF EXISTS build_log_images;
+                DROP TABLE IF EXISTS build_log_entries;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,107 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                -- 收藏扩展为心愿单：目标价、币种与优先级（1-5，越大越想要）
+                ALTER TABLE user_model_favorite ADD COLUMN IF NOT EXISTS target_price DOUBLE PRECISION
+                    CHECK (target_price >= 0);
+                ALTER TABLE user_model_favorite ADD COLUMN IF NOT EXISTS target_currency VARCHAR(3) NOT NULL DEFAULT 'CNY';
+                ALTER TABLE user_model_favorite ADD COLUMN IF NOT EXISTS priority SMALLINT NOT NULL DEFAULT 3
+                    CHECK (priority BETWEEN 1 AND 5);
+
+                -- 用户通知表
+                CREATE TABLE IF NOT EXISTS notifications (
+                    id SERIAL PRIMARY KEY,
+                    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+                    kind VARCHAR(30) NOT NULL,                  -- 通知类型，如 price_drop
+                    model_id INTEGER REFERENCES models(id) ON DELETE CASCADE,
+                    title VARCHAR(255) NOT NULL,
+                    body TEXT,
+                    payload JSONB NOT NULL DEFAULT '{}',        -- 结构化数据，如价格与目标价
+                    read_at TIMESTAMP,                          -- 用户确认时间，为空表示未读
+                    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+                );
+
+                CREATE INDEX IF NOT EXISTS idx_notifications_user ON notifications(user_id, created_at DESC);
+                CREATE INDEX IF NOT EXISTS idx_notifications_unread ON notifications(user_id) WHERE read_at IS NULL;
+
+                -- 新价格不高于心愿单目标价（且币种一致）时为收藏者生成降价提醒；
+                -- 同一模型已有未读且价格不高于本次的提醒时不重复生成。
+                -- 整库恢复时会设置 modelhub.restoring，避免为历史价格补发提醒
+                CREATE OR REPLACE FUNCTION modelhub_notify_price_drop() RETURNS trigger AS $$
+                BEGIN
+                    IF current_setting('modelhub.restoring', true) = 'on' THEN
+                        RETURN NEW;
+                    END IF;
+
+                    INSERT INTO notifications (user_id, kind, model_id, title, body, payload)
+                    SELECT f.user_id,
+                           'price_drop',
+                           NEW.model_id,
+                           m.name || ' 降价至 ' || NEW.price || ' ' || NEW.currency,
+                           '已达到心愿单目标价 ' || f.target_price || ' ' || f.target_currency
+                               || COALESCE('（来源：' || NEW.source || '）', ''),
+                           jsonb_build_object(
+                               'price_history_id', NEW.id,
+                               'price', NEW.price,
+                               'currency', NEW.currency,
+                               'target_price', f.target_price,
+                               'source', NEW.source
+                           )
+                    FROM user_model_favorite f
+                    JOIN models m ON m.id = f.model_id
+                    WHERE f.model_id = NEW.model_id
+                      AND f.target_price IS NOT NULL
+                      AND f.target_currency = NEW.currency
+                      AND NEW.price <= f.target_price
+                      AND NOT EXISTS (
+                          SELECT 1 FROM notifications n
+                          WHERE n.user_id = f.user_id
+                            AND n.model_id = NEW.model_id
+                            AND n.kind = 'price_drop'
+                            AND n.read_at IS NULL
+                            AND (n.payload->>'currency') = NEW.currency
+                            AND (n.payload->>'price')::DOUBLE PRECISION <= NEW.price
+                      );
+
+                    RETURN NEW;
+                END;
+                $$ LANGUAGE plpgsql;
+
+                DROP TRIGGER IF EXISTS trg_price_history_price_drop ON price_history;
+                CREATE TRIGGER trg_price_history_price_drop
+                    AFTER INSERT ON price_history
+                    FOR EACH ROW EXECUTE FUNCTION modelhub_notify_price_drop();
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                DROP TRIGGER IF EXISTS trg_price_history_price_drop ON price_history;
+                DROP FUNCTION IF EXISTS modelhub_notify_price_drop();
+                DROP TABLE IF EXISTS notifications;
+                ALTER TABLE user_model_favorite DROP COLUMN IF EXISTS priority;
+                ALTER TABLE user_model_favorite DROP COLUMN IF EXISTS target_currency;
+                ALTER TABLE user_model_favorite DROP COLUMN IF EXISTS target_price;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
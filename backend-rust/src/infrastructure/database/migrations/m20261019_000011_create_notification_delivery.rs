@@ -0,0 +1,67 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                -- 通知被分发任务拆分为各渠道投递记录的时间，为空表示尚未分发
+                ALTER TABLE notifications ADD COLUMN IF NOT EXISTS dispatched_at TIMESTAMP;
+                CREATE INDEX IF NOT EXISTS idx_notifications_undispatched
+                    ON notifications(id) WHERE dispatched_at IS NULL;
+
+                -- 用户通知渠道偏好，没有记录时只使用站内通知
+                CREATE TABLE IF NOT EXISTS notification_preferences (
+                    user_id INTEGER PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+                    in_app_enabled BOOLEAN NOT NULL DEFAULT TRUE,
+                    webhook_enabled BOOLEAN NOT NULL DEFAULT FALSE,
+                    webhook_url VARCHAR(500),
+                    webhook_secret VARCHAR(64),                 -- 用于签名 webhook 请求体
+                    email_enabled BOOLEAN NOT NULL DEFAULT FALSE,
+                    email VARCHAR(255),
+                    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+                );
+
+                -- 通知在各渠道的投递记录，失败后按退避时间重试
+                CREATE TABLE IF NOT EXISTS notification_deliveries (
+                    id SERIAL PRIMARY KEY,
+                    notification_id INTEGER NOT NULL REFERENCES notifications(id) ON DELETE CASCADE,
+                    channel VARCHAR(20) NOT NULL,               -- in_app / webhook / email
+                    status VARCHAR(20) NOT NULL DEFAULT 'pending', -- pending / sent / failed
+                    attempts INTEGER NOT NULL DEFAULT 0,
+                    next_attempt_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                    last_error TEXT,
+                    delivered_at TIMESTAMP,
+                    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                    UNIQUE(notification_id, channel)
+                );
+
+                CREATE INDEX IF NOT EXISTS idx_notification_deliveries_due
+                    ON notification_deliveries(next_attempt_at) WHERE status = 'pending';
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                DROP TABLE IF EXISTS notification_deliveries;
+                DROP TABLE IF EXISTS notification_preferences;
+                ALTER TABLE notifications DROP COLUMN IF EXISTS dispatched_at;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
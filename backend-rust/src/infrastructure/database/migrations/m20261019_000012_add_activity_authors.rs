@@ -0,0 +1,48 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                -- 记录价格与模型的创建者，用于动态流；历史数据为空
+                ALTER TABLE price_history
+                    ADD COLUMN IF NOT EXISTS recorded_by INTEGER REFERENCES users(id) ON DELETE SET NULL;
+                ALTER TABLE models
+                    ADD COLUMN IF NOT EXISTS created_by INTEGER REFERENCES users(id) ON DELETE SET NULL;
+
+                -- 动态流按时间倒序合并各表，按创建时间建索引
+                CREATE INDEX IF NOT EXISTS idx_price_history_recorded_at ON price_history(recorded_at DESC);
+                CREATE INDEX IF NOT EXISTS idx_models_created_at ON models(created_at DESC);
+                CREATE INDEX IF NOT EXISTS idx_user_model_favorite_created_at ON user_model_favorite(created_at DESC);
+                CREATE INDEX IF NOT EXISTS idx_user_model_purchase_created_at ON user_model_purchase(created_at DESC);
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                DROP INDEX IF EXISTS idx_user_model_purchase_created_at;
+                DROP INDEX IF EXISTS idx_user_model_favorite_created_at;
+                DROP INDEX IF EXISTS idx_models_created_at;
+                DROP INDEX IF EXISTS idx_price_history_recorded_at;
+                ALTER TABLE models DROP COLUMN IF EXISTS created_by;
+                ALTER TABLE price_history DROP COLUMN IF EXISTS recorded_by;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
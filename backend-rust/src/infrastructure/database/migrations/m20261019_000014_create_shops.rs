@@ -0,0 +1,70 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                -- 店铺（价格来源），名称忽略大小写唯一
+                CREATE TABLE IF NOT EXISTS shops (
+                    id SERIAL PRIMARY KEY,
+                    name VARCHAR(100) NOT NULL,
+                    platform VARCHAR(50),                       -- 所在平台，如 pdd、taobao、jd、amazon_jp、offline
+                    url VARCHAR(500),
+                    region VARCHAR(50),                         -- 发货地或所在地区
+                    trust_notes TEXT,                           -- 信誉备注，如是否正品、发货速度
+                    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+                );
+                CREATE UNIQUE INDEX IF NOT EXISTS idx_shops_name_lower ON shops(lower(name));
+
+                ALTER TABLE price_history
+                    ADD COLUMN IF NOT EXISTS shop_id INTEGER REFERENCES shops(id) ON DELETE SET NULL;
+                CREATE INDEX IF NOT EXISTS idx_price_history_model_shop
+                    ON price_history(model_id, shop_id, recorded_at DESC);
+
+                -- 将已有的自由文本来源整理为店铺并关联
+                INSERT INTO shops (name, platform)
+                SELECT DISTINCT ON (lower(trim(source)))
+                       trim(source),
+                       CASE WHEN lower(trim(source)) = 'pdd' THEN 'pdd' END
+                FROM price_history
+                WHERE source IS NOT NULL AND trim(source) <> '' AND length(trim(source)) <= 100
+                ORDER BY lower(trim(source)), trim(source)
+                ON CONFLICT DO NOTHING;
+
+                UPDATE price_history p
+                SET shop_id = s.id
+                FROM shops s
+                WHERE p.shop_id IS NULL AND lower(trim(p.source)) = lower(s.name);
+
+                -- 来源改由店铺表示，不再默认为 pdd
+                ALTER TABLE price_history ALTER COLUMN source DROP DEFAULT;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                ALTER TABLE price_history ALTER COLUMN source SET DEFAULT 'pdd';
+                DROP INDEX IF EXISTS idx_price_history_model_shop;
+                ALTER TABLE price_history DROP COLUMN IF EXISTS shop_id;
+                DROP TABLE IF EXISTS shops;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                -- 价格监控：按 cron 表达式定时抓取商品页面并写入价格记录
+                CREATE TABLE IF NOT EXISTS price_watches (
+                    id SERIAL PRIMARY KEY,
+                    model_id INTEGER NOT NULL REFERENCES models(id) ON DELETE CASCADE,
+                    shop_id INTEGER REFERENCES shops(id) ON DELETE SET NULL,
+                    url VARCHAR(1000) NOT NULL,
+                    source_kind VARCHAR(20) NOT NULL,           -- html 或 json
+                    selector VARCHAR(500) NOT NULL,             -- CSS 选择器或 JSON 路径
+                    currency VARCHAR(3) NOT NULL REFERENCES currencies(code),
+                    schedule VARCHAR(100) NOT NULL,             -- cron 表达式
+                    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+                    next_run_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                    last_run_at TIMESTAMP,
+                    last_price DOUBLE PRECISION,
+                    last_error TEXT,
+                    consecutive_failures INTEGER NOT NULL DEFAULT 0,
+                    created_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+                    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                    UNIQUE (model_id, url)
+                );
+                CREATE INDEX IF NOT EXISTS idx_price_watches_due
+                    ON price_watches(next_run_at) WHERE enabled;
+
+                -- 抓取写入的价格记录关联来源监控，用于去重
+                ALTER TABLE price_history
+                    ADD COLUMN IF NOT EXISTS watch_id INTEGER REFERENCES price_watches(id) ON DELETE SET NULL;
+                CREATE INDEX IF NOT EXISTS idx_price_history_watch
+                    ON price_history(watch_id, recorded_at DESC) WHERE watch_id IS NOT NULL;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                DROP INDEX IF EXISTS idx_price_history_watch;
+                ALTER TABLE price_history DROP COLUMN IF EXISTS watch_id;
+                DROP TABLE IF EXISTS price_watches;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                -- 预售时间节点：模型的开订日期，购买记录的尾款截止与发货日期
+                ALTER TABLE models
+                    ADD COLUMN IF NOT EXISTS preorder_open_date DATE;
+                ALTER TABLE user_model_purchase
+                    ADD COLUMN IF NOT EXISTS payment_due_date DATE,
+                    ADD COLUMN IF NOT EXISTS ship_date DATE;
+
+                -- 日历按日期范围查询
+                CREATE INDEX IF NOT EXISTS idx_models_release_date
+                    ON models(release_date) WHERE release_date IS NOT NULL;
+                CREATE INDEX IF NOT EXISTS idx_models_preorder_open_date
+                    ON models(preorder_open_date) WHERE preorder_open_date IS NOT NULL;
+
+                -- 日历订阅令牌：手机日历无法携带 JWT，订阅地址通过令牌鉴权
+                CREATE TABLE IF NOT EXISTS calendar_tokens (
+                    user_id INTEGER PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+                    token VARCHAR(64) NOT NULL UNIQUE,
+                    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+                );
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                DROP TABLE IF EXISTS calendar_tokens;
+                DROP INDEX IF EXISTS idx_models_preorder_open_date;
+                DROP INDEX IF EXISTS idx_models_release_date;
+                ALTER TABLE user_model_purchase
+                    DROP COLUMN IF EXISTS ship_date,
+                    DROP COLUMN IF EXISTS payment_due_date;
+                ALTER TABLE models DROP COLUMN IF EXISTS preorder_open_date;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                -- 长期没有价格更新的现货模型由定时任务标记为疑似停产，有新价格或状态改变后清除
+                ALTER TABLE models
+                    ADD COLUMN IF NOT EXISTS possibly_discontinued_at TIMESTAMP;
+
+                -- 定时任务产生的修订没有操作者，记录变更原因以便区分
+                ALTER TABLE model_revisions
+                    ADD COLUMN IF NOT EXISTS change_reason VARCHAR(50);
+
+                CREATE INDEX IF NOT EXISTS idx_models_pre_order_release
+                    ON models(release_date) WHERE status = 'pre_order';
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                DROP INDEX IF EXISTS idx_models_pre_order_release;
+                ALTER TABLE model_revisions DROP COLUMN IF EXISTS change_reason;
+                ALTER TABLE models DROP COLUMN IF EXISTS possibly_discontinued_at;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
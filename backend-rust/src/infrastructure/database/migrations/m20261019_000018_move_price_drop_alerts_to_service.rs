@@ -0,0 +1,82 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                -- 降价提醒改由记录价格的服务生成：触发器只能比较同币种的价格，
+                -- 服务按记录当天的汇率把价格换算到心愿单目标价的币种后再比较
+                DROP TRIGGER IF EXISTS trg_price_history_price_drop ON price_history;
+                DROP FUNCTION IF EXISTS modelhub_notify_price_drop();
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                -- 新价格不高于心愿单目标价（且币种一致）时为收藏者生成降价提醒；
+                -- 同一模型已有未读且价格不高于本次的提醒时不重复生成。
+                -- 整库恢复时会设置 modelhub.restoring，避免为历史价格补发提醒
+                CREATE OR REPLACE FUNCTION modelhub_notify_price_drop() RETURNS trigger AS $$
+                BEGIN
+                    IF current_setting('modelhub.restoring', true) = 'on' THEN
+                        RETURN NEW;
+                    END IF;
+
+                    INSERT INTO notifications (user_id, kind, model_id, title, body, payload)
+                    SELECT f.user_id,
+                           'price_drop',
+                           NEW.model_id,
+                           m.name || ' 降价至 ' || NEW.price || ' ' || NEW.currency,
+                           '已达到心愿单目标价 ' || f.target_price || ' ' || f.target_currency
+                               || COALESCE('（来源：' || NEW.source || '）', ''),
+                           jsonb_build_object(
+                               'price_history_id', NEW.id,
+                               'price', NEW.price,
+                               'currency', NEW.currency,
+                               'target_price', f.target_price,
+                               'source', NEW.source
+                           )
+                    FROM user_model_favorite f
+                    JOIN models m ON m.id = f.model_id
+                    WHERE f.model_id = NEW.model_id
+                      AND f.target_price IS NOT NULL
+                      AND f.target_currency = NEW.currency
+                      AND NEW.price <= f.target_price
+                      AND NOT EXISTS (
+                          SELECT 1 FROM notifications n
+                          WHERE n.user_id = f.user_id
+                            AND n.model_id = NEW.model_id
+                            AND n.kind = 'price_drop'
+                            AND n.read_at IS NULL
+                            AND (n.payload->>'currency') = NEW.currency
+                            AND (n.payload->>'price')::DOUBLE PRECISION <= NEW.price
+                      );
+
+                    RETURN NEW;
+                END;
+                $$ LANGUAGE plpgsql;
+
+                DROP TRIGGER IF EXISTS trg_price_history_price_drop ON price_history;
+                CREATE TRIGGER trg_price_history_price_drop
+                    AFTER INSERT ON price_history
+                    FOR EACH ROW EXECUTE FUNCTION modelhub_notify_price_drop();
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+use sea_orm_migration::prelude::*;
+
+mod m20261019_000001_create_model_revisions;
+mod m20261019_000002_normalize_model_status_and_grade;
+mod m20261019_000003_create_series;
+mod m20261019_000004_create_tags;
+mod m20261019_000005_create_search_index;
+mod m20261019_000006_add_user_admin_flag;
+mod m20261019_000007_align_price_history;
+mod m20261019_000008_create_model_images;
+mod m20261019_000009_create_build_logs;
+mod m20261019_000010_create_wishlist_alerts;
+mod m20261019_000011_create_notification_delivery;
+mod m20261019_000012_add_activity_authors;
+mod m20261019_000013_create_currencies;
+mod m20261019_000014_create_shops;
+mod m20261019_000015_create_price_watches;
+mod m20261019_000016_create_calendar;
+mod m20261019_000017_add_status_transitions;
+mod m20261019_000018_move_price_drop_alerts_to_service;
+
+pub struct Migrator;
+
+#[async_trait::async_trait]
+impl MigratorTrait for Migrator {
+    fn migrations() -> Vec<Box<dyn MigrationTrait>> {
+        vec![
+            Box::new(m20261019_000001_create_model_revisions::Migration),
+            Box::new(m20261019_000002_normalize_model_status_and_grade::Migration),
+            Box::new(m20261019_000003_create_series::Migration),
+            Box::new(m20261019_000004_create_tags::Migration),
+            Box::new(m20261019_000005_create_search_index::Migration),
+            Box::new(m20261019_000006_add_user_admin_flag::Migration),
+            Box::new(m20261019_000007_align_price_history::Migration),
+            Box::new(m20261019_000008_create_model_images::Migration),
+            Box::new(m20261019_000009_create_build_logs::Migration),
+            Box::new(m20261019_000010_create_wishlist_alerts::Migration),
+            Box::new(m20261019_000011_create_notification_delivery::Migration),
+            Box::new(m20261019_000012_add_activity_authors::Migration),
+            Box::new(m20261019_000013_create_currencies::Migration),
+            Box::new(m20261019_000014_create_shops::Migration),
+            Box::new(m20261019_000015_create_price_watches::Migration),
+            Box::new(m20261019_000016_create_calendar::Migration),
+            Box::new(m20261019_000017_add_status_transitions::Migration),
+            Box::new(m20261019_000018_move_price_drop_alerts_to_service::Migration),
+        ]
+    }
+}
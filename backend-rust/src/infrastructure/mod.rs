@@ -1,2 +1,5 @@
 pub mod database;
+pub mod notifications;
+pub mod price_sources;
 pub mod repositories;
+pub mod storage;
@@ -0,0 +1,71 @@
+use super::NotificationChannel;
+use crate::config::{SmtpConfig, SmtpTls};
+use crate::domain::entities::enums::DeliveryChannel;
+use crate::domain::entities::{notification, notification_preference};
+use async_trait::async_trait;
+use lettre::message::{header::ContentType, Mailbox};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+
+/// 通过 SMTP 发送纯文本邮件
+pub struct EmailChannel {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl EmailChannel {
+    pub fn new(config: &SmtpConfig) -> anyhow::Result<Self> {
+        let mut builder = match config.tls {
+            SmtpTls::None => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.host),
+            SmtpTls::StartTls => {
+                AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host)?
+            }
+            SmtpTls::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(&config.host)?,
+        }
+        .port(config.port);
+        if let (Some(username), Some(password)) = (&config.username, &config.password) {
+            builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+        }
+
+        Ok(Self {
+            transport: builder.build(),
+            from: config.from.parse()?,
+        })
+    }
+}
+
+#[async_trait]
+impl NotificationChannel for EmailChannel {
+    fn kind(&self) -> DeliveryChannel {
+        DeliveryChannel::Email
+    }
+
+    fn enabled_for(&self, preferences: &notification_preference::Model) -> bool {
+        preferences.email_enabled && preferences.email.is_some()
+    }
+
+    async fn deliver(
+        &self,
+        notification: &notification::Model,
+        preferences: &notification_preference::Model,
+    ) -> anyhow::Result<()> {
+        let to = preferences
+            .email
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("email is not set"))?;
+        let message = Message::builder()
+            .from(self.from.clone())
+            .to(to.parse()?)
+            .subject(&notification.title)
+            .header(ContentType::TEXT_PLAIN)
+            .body(
+                notification
+                    .body
+                    .clone()
+                    .unwrap_or_else(|| notification.title.clone()),
+            )?;
+
+        self.transport.send(message).await?;
+        Ok(())
+    }
+}
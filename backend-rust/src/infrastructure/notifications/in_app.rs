@@ -0,0 +1,44 @@
+use super::NotificationChannel;
+use crate::domain::entities::enums::DeliveryChannel;
+use crate::domain::entities::{notification, notification_preference};
+use crate::domain::models::event_dto::ChangeEventKind;
+use crate::services::event_hub::EventHub;
+use async_trait::async_trait;
+
+/// 站内通知：通知写入 notifications 表后即可通过 `/api/user/alerts` 查看，
+/// 投递时再通过事件流推送给在线的接收者
+pub struct InAppChannel {
+    events: EventHub,
+}
+
+impl InAppChannel {
+    pub fn new(events: EventHub) -> Self {
+        Self { events }
+    }
+}
+
+#[async_trait]
+impl NotificationChannel for InAppChannel {
+    fn kind(&self) -> DeliveryChannel {
+        DeliveryChannel::InApp
+    }
+
+    fn enabled_for(&self, preferences: &notification_preference::Model) -> bool {
+        preferences.in_app_enabled
+    }
+
+    async fn deliver(
+        &self,
+        notification: &notification::Model,
+        _preferences: &notification_preference::Model,
+    ) -> anyhow::Result<()> {
+        self.events.publish_to(
+            ChangeEventKind::NotificationCreated,
+            notification.id,
+            None,
+            Some(notification.user_id),
+            notification,
+        );
+        Ok(())
+    }
+}
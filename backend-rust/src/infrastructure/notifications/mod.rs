@@ -0,0 +1,48 @@
+use crate::config::NotificationConfig;
+use crate::domain::entities::enums::DeliveryChannel;
+use crate::domain::entities::{notification, notification_preference};
+use crate::services::event_hub::EventHub;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub mod email;
+pub mod in_app;
+pub mod webhook;
+
+/// 通知的一种投递方式，新增渠道时实现此 trait 并在 `create_channels` 中注册
+#[async_trait]
+pub trait NotificationChannel: Send + Sync {
+    fn kind(&self) -> DeliveryChannel;
+    /// 用户偏好中是否启用了此渠道且配置完整
+    fn enabled_for(&self, preferences: &notification_preference::Model) -> bool;
+    /// 投递失败返回错误，由分发任务按退避时间重试
+    async fn deliver(
+        &self,
+        notification: &notification::Model,
+        preferences: &notification_preference::Model,
+    ) -> anyhow::Result<()>;
+}
+
+pub type SharedChannel = Arc<dyn NotificationChannel>;
+
+pub fn create_channels(
+    config: &NotificationConfig,
+    events: EventHub,
+) -> HashMap<DeliveryChannel, SharedChannel> {
+    let mut channels: Vec<SharedChannel> = vec![
+        Arc::new(in_app::InAppChannel::new(events)),
+        Arc::new(webhook::WebhookChannel::new(config.allow_private_webhooks)),
+    ];
+    match &config.smtp {
+        Some(smtp) => match email::EmailChannel::new(smtp) {
+            Ok(channel) => channels.push(Arc::new(channel)),
+            Err(e) => tracing::error!("SMTP 配置无效，邮件渠道不可用: {:?}", e),
+        },
+        None => tracing::info!("未配置 SMTP，邮件渠道不可用"),
+    }
+    channels
+        .into_iter()
+        .map(|channel| (channel.kind(), channel))
+        .collect()
+}
@@ -0,0 +1,375 @@
+use super::NotificationChannel;
+use crate::domain::entities::enums::DeliveryChannel;
+use crate::domain::entities::{notification, notification_preference};
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::time::Duration;
+
+type HmacSha256 = Hmac<Sha256>;
+
+pub const TIMESTAMP_HEADER: &str = "X-ModelHub-Timestamp";
+pub const SIGNATURE_HEADER: &str = "X-ModelHub-Signature";
+
+/// 以 JSON POST 到用户配置的地址，签名为 `sha256=hex(HMAC-SHA256(secret, "{timestamp}.{body}"))`。
+/// 默认拒绝内网地址：投递前解析域名，只连接检查过的地址，且不跟随重定向
+pub struct WebhookChannel {
+    client: reqwest::Client,
+    allow_private_hosts: bool,
+}
+
+#[derive(Serialize)]
+struct WebhookPayload<'a> {
+    id: i32,
+    kind: &'a crate::domain::entities::enums::NotificationKind,
+    model_id: Option<i32>,
+    title: &'a str,
+    body: Option<&'a str>,
+    payload: &'a serde_json::Value,
+    created_at: chrono::NaiveDateTime,
+}
+
+impl WebhookChannel {
+    pub fn new(allow_private_hosts: bool) -> Self {
+        Self {
+            client: client_builder().build().unwrap_or_default(),
+            allow_private_hosts,
+        }
+    }
+
+    /// 域名地址先解析并检查全部结果，再固定连接到这些地址，避免检查后被重新解析到内网（DNS rebinding）
+    async fn client_for(&self, url: &reqwest::Url) -> anyhow::Result<reqwest::Client> {
+        let domain = match url.host_str() {
+            Some(host) if !self.allow_private_hosts && literal_ip(host).is_none() => host,
+            _ => return Ok(self.client.clone()),
+        };
+        let port = url.port_or_known_default().unwrap_or(80);
+        let addresses: Vec<SocketAddr> = tokio::net::lookup_host((domain, port)).await?.collect();
+        if addresses.is_empty() {
+            anyhow::bail!("无法解析 webhook 域名 {}", domain);
+        }
+        if let Some(address) = addresses.iter().find(|a| !is_public_ip(a.ip())) {
+            anyhow::bail!("webhook 域名 {} 解析到非公网地址 {}", domain, address.ip());
+        }
+        Ok(client_builder()
+            .resolve_to_addrs(domain, &addresses)
+            .build()?)
+    }
+}
+
+fn client_builder() -> reqwest::ClientBuilder {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .redirect(reqwest::redirect::Policy::none())
+}
+
+/// 检查 webhook 地址：只允许 http/https，`allow_private_hosts` 为 false 时拒绝
+/// localhost 以及字面量为内网、回环、链路本地等非公网 IP 的主机。域名的解析结果在投递时检查
+pub fn validate_webhook_url(url: &str, allow_private_hosts: bool) -> Result<reqwest::Url, String> {
+    let parsed = reqwest::Url::parse(url).map_err(|_| "无效的地址".to_string())?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err("仅支持 http 或 https 地址".to_string());
+    }
+    let host = parsed
+        .host_str()
+        .filter(|host| !host.is_empty())
+        .ok_or_else(|| "地址缺少主机名".to_string())?;
+    if allow_private_hosts {
+        return Ok(parsed);
+    }
+    let public = match literal_ip(host) {
+        Some(ip) => is_public_ip(ip),
+        None => {
+            let domain = host.trim_end_matches('.').to_ascii_lowercase();
+            domain != "localhost" && !domain.ends_with(".localhost")
+        }
+    };
+    if !public {
+        return Err("不能指向内网、回环或链路本地地址".to_string());
+    }
+    Ok(parsed)
+}
+
+/// 主机名为 IP 字面量时返回该地址，IPv6 带方括号
+fn literal_ip(host: &str) -> Option<IpAddr> {
+    host.trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse()
+        .ok()
+}
+
+/// 是否为公网可路由的地址
+fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => {
+            // IPv4 映射地址与 NAT64 地址按其中的 IPv4 地址判断
+            if let Some(v4) = ip.to_ipv4_mapped() {
+                return is_public_ipv4(v4);
+            }
+            let segments = ip.segments();
+            if segments[..6] == [0x64, 0xff9b, 0, 0, 0, 0] {
+                let [a, b] = segments[6].to_be_bytes();
+                let [c, d] = segments[7].to_be_bytes();
+                return is_public_ipv4(Ipv4Addr::new(a, b, c, d));
+            }
+            !(ip.is_loopback()
+                || ip.is_unspecified()
+                || ip.is_multicast()
+                // 唯一本地地址 fc00::/7
+                || (segments[0] & 0xfe00) == 0xfc00
+                // 链路本地地址 fe80::/10
+                || (segments[0] & 0xffc0) == 0xfe80
+                // 文档地址 2001:db8::/32
+                || segments[..2] == [0x2001, 0xdb8])
+        }
+    }
+}
+
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    !(ip.is_private()
+        || ip.is_loopback()
+        || ip.is_link_local()
+        || ip.is_unspecified()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        || ip.is_documentation()
+        // 0.0.0.0/8、运营商级 NAT 100.64.0.0/10、IETF 保留 192.0.0.0/24、
+        // 基准测试 198.18.0.0/15 与保留段 240.0.0.0/4
+        || a == 0
+        || (a == 100 && (64..128).contains(&b))
+        || (a == 192 && b == 0 && ip.octets()[2] == 0)
+        || (a == 198 && (b == 18 || b == 19))
+        || a >= 240)
+}
+
+#[async_trait]
+impl NotificationChannel for WebhookChannel {
+    fn kind(&self) -> DeliveryChannel {
+        DeliveryChannel::Webhook
+    }
+
+    fn enabled_for(&self, preferences: &notification_preference::Model) -> bool {
+        preferences.webhook_enabled && preferences.webhook_url.is_some()
+    }
+
+    async fn deliver(
+        &self,
+        notification: &notification::Model,
+        preferences: &notification_preference::Model,
+    ) -> anyhow::Result<()> {
+        let url = preferences
+            .webhook_url
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("webhook_url is not set"))?;
+        let url = validate_webhook_url(url, self.allow_private_hosts)
+            .map_err(|message| anyhow::anyhow!("webhook 地址 {} 不可用: {}", url, message))?;
+        let client = self.client_for(&url).await?;
+        let body = serde_json::to_vec(&WebhookPayload {
+            id: notification.id,
+            kind: &notification.kind,
+            model_id: notification.model_id,
+            title: &notification.title,
+            body: notification.body.as_deref(),
+            payload: &notification.payload,
+            created_at: notification.created_at,
+        })?;
+        let timestamp = chrono::Utc::now().timestamp().to_string();
+
+        let mut request = client
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(TIMESTAMP_HEADER, &timestamp);
+        if let Some(secret) = &preferences.webhook_secret {
+            request = request.header(SIGNATURE_HEADER, sign(secret, &timestamp, &body)?);
+        }
+
+        let response = request.body(body).send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            anyhow::bail!("webhook responded with {}", status);
+        }
+        Ok(())
+    }
+}
+
+fn sign(secret: &str, timestamp: &str, body: &[u8]) -> anyhow::Result<String> {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes())?;
+    mac.update(timestamp.as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    Ok(format!(
+        "sha256={}",
+        hex::encode(mac.finalize().into_bytes())
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{sample_notification, MockResponse, MockServer};
+
+    fn preferences(url: &str) -> notification_preference::Model {
+        notification_preference::Model {
+            webhook_enabled: true,
+            webhook_url: Some(url.to_string()),
+            webhook_secret: Some("whsec_test".to_string()),
+            ..notification_preference::Model::default_for(1)
+        }
+    }
+
+    /// 接收端的校验方式：重新计算签名并以常量时间比较
+    fn verify(secret: &str, timestamp: &str, body: &[u8], signature: &str) -> bool {
+        let Some(hex_digest) = signature.strip_prefix("sha256=") else {
+            return false;
+        };
+        let Ok(expected) = hex::decode(hex_digest) else {
+            return false;
+        };
+        let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(format!("{}.", timestamp).as_bytes());
+        mac.update(body);
+        mac.verify_slice(&expected).is_ok()
+    }
+
+    #[test]
+    fn sign_matches_known_hmac() {
+        // python3 -c 'import hmac,hashlib;print(hmac.new(b"whsec_test",b"1700000000.{\"id\":1}",hashlib.sha256).hexdigest())'
+        assert_eq!(
+            sign("whsec_test", "1700000000", br#"{"id":1}"#).unwrap(),
+            "sha256=2f441ba4b3b2d50d28a9ab9d9fd8880376ecd1eb5d0435401553f5d8d0a5dcf8"
+        );
+    }
+
+    #[test]
+    fn signature_fails_verification_when_anything_changes() {
+        let body = br#"{"id":1}"#;
+        let signature = sign("whsec_test", "1700000000", body).unwrap();
+        assert!(verify("whsec_test", "1700000000", body, &signature));
+        assert!(!verify("whsec_other", "1700000000", body, &signature));
+        assert!(!verify("whsec_test", "1700000001", body, &signature));
+        assert!(!verify(
+            "whsec_test",
+            "1700000000",
+            br#"{"id":2}"#,
+            &signature
+        ));
+    }
+
+    #[test]
+    fn validate_webhook_url_rejects_non_public_hosts() {
+        for url in [
+            "http://127.0.0.1:8080/hook",
+            "http://localhost/hook",
+            "http://api.localhost/hook",
+            "http://10.0.0.5/hook",
+            "http://172.16.3.4/hook",
+            "http://192.168.1.10/hook",
+            "http://169.254.169.254/latest/meta-data",
+            "http://100.64.0.1/hook",
+            "http://0.0.0.0/hook",
+            "http://2130706433/hook",
+            "http://[::1]/hook",
+            "http://[fe80::1]/hook",
+            "http://[fd00::1]/hook",
+            "http://[::ffff:127.0.0.1]/hook",
+        ] {
+            assert!(
+                validate_webhook_url(url, false).is_err(),
+                "{} 应被拒绝",
+                url
+            );
+            assert!(validate_webhook_url(url, true).is_ok(), "{} 应被允许", url);
+        }
+    }
+
+    #[test]
+    fn validate_webhook_url_accepts_public_hosts_and_checks_scheme() {
+        assert!(validate_webhook_url("https://hooks.example.com/modelhub", false).is_ok());
+        assert!(validate_webhook_url("http://93.184.216.34/hook", false).is_ok());
+        assert!(validate_webhook_url("http://[2606:4700::1111]/hook", false).is_ok());
+        assert!(validate_webhook_url("ftp://hooks.example.com/", false).is_err());
+        assert!(validate_webhook_url("hooks.example.com/hook", false).is_err());
+    }
+
+    #[test]
+    fn nat64_and_mapped_addresses_use_embedded_ipv4() {
+        assert!(!is_public_ip("64:ff9b::a00:1".parse().unwrap()));
+        assert!(is_public_ip("64:ff9b::5db8:d822".parse().unwrap()));
+        assert!(!is_public_ip("::ffff:192.168.0.1".parse().unwrap()));
+    }
+
+    #[tokio::test]
+    async fn deliver_posts_signed_json() {
+        let server = MockServer::start(vec![MockResponse::new(204, "text/plain", "")]).await;
+        let channel = WebhookChannel::new(true);
+        let notification = sample_notification(1);
+
+        channel
+            .deliver(&notification, &preferences(&format!("{}/hook", server.url)))
+            .await
+            .unwrap();
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        let request = &requests[0];
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/hook");
+        assert_eq!(request.header("content-type"), Some("application/json"));
+        let timestamp = request.header(TIMESTAMP_HEADER).unwrap();
+        let signature = request.header(SIGNATURE_HEADER).unwrap();
+        assert!(verify("whsec_test", timestamp, &request.body, signature));
+
+        let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+        assert_eq!(body["id"], 42);
+        assert_eq!(body["kind"], "price_drop");
+        assert_eq!(body["title"], notification.title);
+    }
+
+    #[tokio::test]
+    async fn deliver_without_secret_sends_no_signature() {
+        let server = MockServer::start(vec![MockResponse::new(200, "text/plain", "ok")]).await;
+        let channel = WebhookChannel::new(true);
+        let mut preferences = preferences(&server.url);
+        preferences.webhook_secret = None;
+
+        channel
+            .deliver(&sample_notification(1), &preferences)
+            .await
+            .unwrap();
+        let request = &server.requests()[0];
+        assert!(request.header(TIMESTAMP_HEADER).is_some());
+        assert!(request.header(SIGNATURE_HEADER).is_none());
+    }
+
+    #[tokio::test]
+    async fn deliver_fails_on_error_status() {
+        let server = MockServer::start(vec![MockResponse::new(500, "text/plain", "boom")]).await;
+        let channel = WebhookChannel::new(true);
+
+        let error = channel
+            .deliver(&sample_notification(1), &preferences(&server.url))
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("500"));
+    }
+
+    #[tokio::test]
+    async fn deliver_refuses_private_hosts_unless_allowed() {
+        let server = MockServer::start(vec![MockResponse::new(200, "text/plain", "ok")]).await;
+        let channel = WebhookChannel::new(false);
+
+        // 回环 IP 字面量与 localhost 都不会发出请求
+        let localhost_url = server.url.replace("127.0.0.1", "localhost");
+        for url in [server.url.clone(), localhost_url] {
+            assert!(channel
+                .deliver(&sample_notification(1), &preferences(&url))
+                .await
+                .is_err());
+        }
+        assert!(server.requests().is_empty());
+    }
+}
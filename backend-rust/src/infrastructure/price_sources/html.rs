@@ -0,0 +1,148 @@
+use super::{fetch_text, parse_price, FetchedPrice, PriceSource};
+use crate::domain::entities::enums::PriceSourceKind;
+use crate::domain::entities::model;
+use async_trait::async_trait;
+use scraper::{Html, Selector};
+
+/// 通用 HTML 来源：取第一个匹配 CSS 选择器的元素文本；
+/// 选择器以 `@属性名` 结尾时读取属性值，如 `meta[itemprop=price]@content`
+pub struct HtmlSource {
+    client: reqwest::Client,
+}
+
+impl HtmlSource {
+    pub fn new(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl PriceSource for HtmlSource {
+    fn kind(&self) -> PriceSourceKind {
+        PriceSourceKind::Html
+    }
+
+    fn validate_selector(&self, selector: &str) -> anyhow::Result<()> {
+        parse_selector(selector).map(|_| ())
+    }
+
+    async fn fetch(
+        &self,
+        _model: &model::Model,
+        url: &str,
+        selector: &str,
+    ) -> anyhow::Result<FetchedPrice> {
+        let body = fetch_text(&self.client, url).await?;
+        let raw = extract(&body, selector)?;
+        let price = parse_price(&raw)
+            .ok_or_else(|| anyhow::anyhow!("无法从「{}」中解析出价格", raw.trim()))?;
+        Ok(FetchedPrice { price, raw })
+    }
+}
+
+/// 拆分出 CSS 选择器与可选的属性名
+fn parse_selector(selector: &str) -> anyhow::Result<(Selector, Option<&str>)> {
+    let (css, attribute) = match selector.rsplit_once('@') {
+        Some((css, attribute)) if is_attribute_name(attribute) => (css, Some(attribute)),
+        _ => (selector, None),
+    };
+    let parsed = Selector::parse(css.trim())
+        .map_err(|e| anyhow::anyhow!("无效的 CSS 选择器 {}: {}", css, e))?;
+    Ok((parsed, attribute))
+}
+
+fn is_attribute_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':' | '.'))
+}
+
+fn extract(body: &str, selector: &str) -> anyhow::Result<String> {
+    let (selector, attribute) = parse_selector(selector)?;
+    let document = Html::parse_document(body);
+    let element = document
+        .select(&selector)
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("页面中没有匹配选择器的元素"))?;
+    match attribute {
+        Some(name) => element
+            .value()
+            .attr(name)
+            .map(str::to_string)
+            .ok_or_else(|| anyhow::anyhow!("匹配的元素没有属性 {}", name)),
+        None => Ok(element.text().collect::<String>().trim().to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{sample_model, MockResponse, MockServer};
+
+    const PAGE: &str = r#"<html><head><meta itemprop="price" content="1299.00"></head>
+        <body><div class="promo">满2件 减10</div><span class="price"> ¥1,099.00 </span></body></html>"#;
+
+    #[test]
+    fn extract_reads_element_text() {
+        assert_eq!(extract(PAGE, "span.price").unwrap(), "¥1,099.00");
+    }
+
+    #[test]
+    fn extract_reads_attribute() {
+        assert_eq!(
+            extract(PAGE, "meta[itemprop=price]@content").unwrap(),
+            "1299.00"
+        );
+        assert!(extract(PAGE, "span.price@content").is_err());
+    }
+
+    #[test]
+    fn extract_fails_without_match() {
+        assert!(extract(PAGE, "#missing").is_err());
+    }
+
+    #[test]
+    fn parse_selector_keeps_at_sign_that_is_not_an_attribute() {
+        // 属性值中的 @ 后面不是合法属性名，整体按 CSS 选择器解析
+        let (_, attribute) = parse_selector(r#"a[title="a@b c"]"#).unwrap();
+        assert_eq!(attribute, None);
+        let (_, attribute) = parse_selector("meta@data-price").unwrap();
+        assert_eq!(attribute, Some("data-price"));
+        assert!(parse_selector("div[").is_err());
+    }
+
+    #[tokio::test]
+    async fn fetch_parses_price_from_page() {
+        let server = MockServer::start(vec![MockResponse::new(200, "text/html", PAGE)]).await;
+        let source = HtmlSource::new(reqwest::Client::new());
+
+        let fetched = source
+            .fetch(
+                &sample_model(),
+                &format!("{}/item/1", server.url),
+                "span.price",
+            )
+            .await
+            .unwrap();
+        assert_eq!(fetched.price, 1099.0);
+        assert_eq!(fetched.raw, "¥1,099.00");
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].path, "/item/1");
+    }
+
+    #[tokio::test]
+    async fn fetch_fails_on_error_status() {
+        let server = MockServer::start(vec![MockResponse::new(503, "text/html", PAGE)]).await;
+        let source = HtmlSource::new(reqwest::Client::new());
+
+        let error = source
+            .fetch(&sample_model(), &server.url, "span.price")
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("503"));
+    }
+}
@@ -0,0 +1,181 @@
+use super::{fetch_text, parse_price, FetchedPrice, PriceSource};
+use crate::domain::entities::enums::PriceSourceKind;
+use crate::domain::entities::model;
+use async_trait::async_trait;
+use serde_json::Value;
+
+/// 通用 JSON 来源：按 JSON 路径读取接口返回值，路径形如 `$.data.skus[0].price`
+/// 或 `$["data"]["price"]`，值可以是数字或带货币符号的字符串
+pub struct JsonSource {
+    client: reqwest::Client,
+}
+
+impl JsonSource {
+    pub fn new(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl PriceSource for JsonSource {
+    fn kind(&self) -> PriceSourceKind {
+        PriceSourceKind::Json
+    }
+
+    fn validate_selector(&self, selector: &str) -> anyhow::Result<()> {
+        parse_path(selector).map(|_| ())
+    }
+
+    async fn fetch(
+        &self,
+        _model: &model::Model,
+        url: &str,
+        selector: &str,
+    ) -> anyhow::Result<FetchedPrice> {
+        let path = parse_path(selector)?;
+        let body = fetch_text(&self.client, url).await?;
+        let document: Value = serde_json::from_str(&body)
+            .map_err(|e| anyhow::anyhow!("响应不是有效的 JSON: {}", e))?;
+
+        let value = lookup(&document, &path)
+            .ok_or_else(|| anyhow::anyhow!("响应中没有路径 {}", selector))?;
+        let (price, raw) = match value {
+            Value::Number(number) => (number.as_f64(), number.to_string()),
+            Value::String(text) => (parse_price(text), text.clone()),
+            other => (None, other.to_string()),
+        };
+        let price = price.ok_or_else(|| anyhow::anyhow!("无法从「{}」中解析出价格", raw))?;
+        Ok(FetchedPrice { price, raw })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+fn parse_path(path: &str) -> anyhow::Result<Vec<Segment>> {
+    let invalid = || anyhow::anyhow!("无效的 JSON 路径: {}", path);
+    let path = path.trim();
+    let mut rest = path.strip_prefix('$').unwrap_or(path);
+    let mut segments = Vec::new();
+
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('[') {
+            let (inner, tail) = after.split_once(']').ok_or_else(invalid)?;
+            let inner = inner.trim();
+            let segment = match inner.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+                Some(key) => Segment::Key(key.to_string()),
+                None => Segment::Index(inner.parse().map_err(|_| invalid())?),
+            };
+            segments.push(segment);
+            rest = tail;
+        } else {
+            let after = rest.strip_prefix('.').unwrap_or(rest);
+            let end = after.find(['.', '[']).unwrap_or(after.len());
+            if end == 0 {
+                return Err(invalid());
+            }
+            segments.push(Segment::Key(after[..end].to_string()));
+            rest = &after[end..];
+        }
+    }
+
+    if segments.is_empty() {
+        return Err(invalid());
+    }
+    Ok(segments)
+}
+
+fn lookup<'a>(value: &'a Value, path: &[Segment]) -> Option<&'a Value> {
+    path.iter()
+        .try_fold(value, |current, segment| match segment {
+            Segment::Key(key) => current.get(key.as_str()),
+            Segment::Index(index) => current.get(*index),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{sample_model, MockResponse, MockServer};
+    use serde_json::json;
+
+    fn key(name: &str) -> Segment {
+        Segment::Key(name.to_string())
+    }
+
+    #[test]
+    fn parse_path_supports_dot_and_bracket_notation() {
+        assert_eq!(
+            parse_path("$.data.skus[0].price").unwrap(),
+            vec![key("data"), key("skus"), Segment::Index(0), key("price")]
+        );
+        assert_eq!(
+            parse_path(r#"$["data"]["sale price"]"#).unwrap(),
+            vec![key("data"), key("sale price")]
+        );
+        assert_eq!(parse_path("price").unwrap(), vec![key("price")]);
+    }
+
+    #[test]
+    fn parse_path_rejects_invalid_paths() {
+        for path in ["$", "", "$.data..price", "$.items[x]", "$.items[0"] {
+            assert!(parse_path(path).is_err(), "{} 应解析失败", path);
+        }
+    }
+
+    #[test]
+    fn lookup_follows_segments() {
+        let document = json!({ "data": { "skus": [{ "price": 88 }, { "price": 99 }] } });
+        let path = parse_path("$.data.skus[1].price").unwrap();
+        assert_eq!(lookup(&document, &path), Some(&json!(99)));
+        let missing = parse_path("$.data.skus[2].price").unwrap();
+        assert_eq!(lookup(&document, &missing), None);
+    }
+
+    #[tokio::test]
+    async fn fetch_reads_number_and_string_values() {
+        let body = r#"{"data":{"price":1299.5,"display":"1.299,00 €"}}"#;
+        let server =
+            MockServer::start(vec![MockResponse::new(200, "application/json", body)]).await;
+        let source = JsonSource::new(reqwest::Client::new());
+        let url = format!("{}/api/item?id=1", server.url);
+
+        let fetched = source
+            .fetch(&sample_model(), &url, "$.data.price")
+            .await
+            .unwrap();
+        assert_eq!(fetched.price, 1299.5);
+
+        let fetched = source
+            .fetch(&sample_model(), &url, "$.data.display")
+            .await
+            .unwrap();
+        assert_eq!(fetched.price, 1299.0);
+        assert_eq!(fetched.raw, "1.299,00 €");
+        assert_eq!(server.requests()[0].path, "/api/item?id=1");
+    }
+
+    #[tokio::test]
+    async fn fetch_reports_missing_path_and_invalid_json() {
+        let server = MockServer::start(vec![
+            MockResponse::new(200, "application/json", r#"{"data":{}}"#),
+            MockResponse::new(200, "text/html", "<html></html>"),
+        ])
+        .await;
+        let source = JsonSource::new(reqwest::Client::new());
+
+        let error = source
+            .fetch(&sample_model(), &server.url, "$.data.price")
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("$.data.price"));
+        let error = source
+            .fetch(&sample_model(), &server.url, "$.data.price")
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("JSON"));
+    }
+}
@@ -0,0 +1,181 @@
+use crate::config::ScraperConfig;
+use crate::domain::entities::enums::PriceSourceKind;
+use crate::domain::entities::model;
+use async_trait::async_trait;
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+pub mod html;
+pub mod json;
+
+/// 从商品页面抓取到的价格
+#[derive(Debug, Clone)]
+pub struct FetchedPrice {
+    pub price: f64,
+    /// 选择器匹配到的原始文本，便于排查解析问题
+    pub raw: String,
+}
+
+/// 一种价格抓取方式，新增方式时实现此 trait 并在 `create_sources` 中注册
+#[async_trait]
+pub trait PriceSource: Send + Sync {
+    fn kind(&self) -> PriceSourceKind;
+    /// 检查选择器语法，登记监控时调用
+    fn validate_selector(&self, selector: &str) -> anyhow::Result<()>;
+    /// 抓取 `url` 并按 `selector` 解析出价格。通用来源只使用地址，
+    /// 按模型名称搜索的来源可使用 `model`
+    async fn fetch(
+        &self,
+        model: &model::Model,
+        url: &str,
+        selector: &str,
+    ) -> anyhow::Result<FetchedPrice>;
+}
+
+pub type SharedSource = Arc<dyn PriceSource>;
+
+pub fn create_sources(config: &ScraperConfig) -> HashMap<PriceSourceKind, SharedSource> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(config.request_timeout_secs.max(1)))
+        .user_agent(config.user_agent.clone())
+        .build()
+        .unwrap_or_default();
+    let sources: Vec<SharedSource> = vec![
+        Arc::new(html::HtmlSource::new(client.clone())),
+        Arc::new(json::JsonSource::new(client)),
+    ];
+    sources
+        .into_iter()
+        .map(|source| (source.kind(), source))
+        .collect()
+}
+
+/// 请求页面并返回响应正文，非 2xx 状态码视为失败
+pub(crate) async fn fetch_text(client: &reqwest::Client, url: &str) -> anyhow::Result<String> {
+    let response = client.get(url).send().await?;
+    let status = response.status();
+    if !status.is_success() {
+        anyhow::bail!("请求 {} 返回状态码 {}", url, status);
+    }
+    Ok(response.text().await?)
+}
+
+/// 币种符号与代码，紧挨着它们的数值优先视为价格
+const CURRENCY_MARKERS: &[&str] = &[
+    "¥", "￥", "$", "€", "£", "元", "円", "圆", "RMB", "CNY", "JPY", "USD", "EUR", "HKD", "GBP",
+    "TWD",
+];
+
+/// 从 `¥1,299.00`、`1 299 円`、`价格：88.5元`、`1.299,00 €` 之类的文本中取出价格。
+/// 优先取紧挨币种标记的数值（`满2件 ¥88` 取 88），没有时取第一个数值
+pub(crate) fn parse_price(text: &str) -> Option<f64> {
+    static NUMBER: OnceLock<Regex> = OnceLock::new();
+    let number = NUMBER.get_or_init(|| {
+        Regex::new(r"\d+(?:[.,，]\d+|[ \u{a0}]\d{3}(?-u:\b))*").expect("price pattern is valid")
+    });
+    let candidates: Vec<_> = number.find_iter(text).collect();
+    let chosen = candidates
+        .iter()
+        .find(|m| {
+            let before = text[..m.start()].trim_end();
+            let after = text[m.end()..].trim_start();
+            CURRENCY_MARKERS.iter().any(|marker| {
+                ends_with_ignore_case(before, marker) || starts_with_ignore_case(after, marker)
+            })
+        })
+        .or_else(|| candidates.first())?;
+    normalize_number(chosen.as_str())
+        .parse()
+        .ok()
+        .filter(|p: &f64| p.is_finite())
+}
+
+/// 判断小数点与千分位：`.` 与 `,` 同时出现时靠后的是小数点；只出现一次且后面不是恰好三位数字时是小数点；
+/// 其余分隔符（包括空格）都是千分位
+fn normalize_number(matched: &str) -> String {
+    let matched = matched.replace('，', ",");
+    let last_dot = matched.rfind('.');
+    let last_comma = matched.rfind(',');
+    let decimal = match (last_dot, last_comma) {
+        (Some(dot), Some(comma)) => Some(dot.max(comma)),
+        (Some(position), None) | (None, Some(position)) => {
+            let separator = &matched[position..position + 1];
+            let single = matched.matches(separator).count() == 1;
+            let fraction_len = matched[position + 1..]
+                .chars()
+                .take_while(|c| c.is_ascii_digit())
+                .count();
+            (single && fraction_len != 3).then_some(position)
+        }
+        (None, None) => None,
+    };
+    matched
+        .char_indices()
+        .filter_map(|(index, c)| {
+            if c.is_ascii_digit() {
+                Some(c)
+            } else if Some(index) == decimal {
+                Some('.')
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+fn starts_with_ignore_case(text: &str, prefix: &str) -> bool {
+    text.get(..prefix.len())
+        .is_some_and(|head| head.eq_ignore_ascii_case(prefix))
+}
+
+fn ends_with_ignore_case(text: &str, suffix: &str) -> bool {
+    text.len() >= suffix.len()
+        && text
+            .get(text.len() - suffix.len()..)
+            .is_some_and(|tail| tail.eq_ignore_ascii_case(suffix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_price_handles_thousands_separators() {
+        assert_eq!(parse_price("¥1,299.00"), Some(1299.0));
+        assert_eq!(parse_price("1 299 円"), Some(1299.0));
+        assert_eq!(parse_price("1\u{a0}299 円"), Some(1299.0));
+        assert_eq!(parse_price("¥1，299"), Some(1299.0));
+        assert_eq!(parse_price("$12,345,678"), Some(12345678.0));
+    }
+
+    #[test]
+    fn parse_price_handles_decimal_comma() {
+        assert_eq!(parse_price("1.299,00 €"), Some(1299.0));
+        assert_eq!(parse_price("12,50 €"), Some(12.5));
+        assert_eq!(parse_price("EUR 1.299.000"), Some(1299000.0));
+    }
+
+    #[test]
+    fn parse_price_prefers_number_next_to_currency() {
+        assert_eq!(parse_price("满2件 ¥88"), Some(88.0));
+        assert_eq!(parse_price("价格：88.5元"), Some(88.5));
+        assert_eq!(parse_price("3 件起 售价 120 rmb"), Some(120.0));
+        assert_eq!(parse_price("到手价 ¥ 1,099 原价 ¥1,299"), Some(1099.0));
+    }
+
+    #[test]
+    fn parse_price_falls_back_to_first_number() {
+        assert_eq!(parse_price("88.5"), Some(88.5));
+        assert_eq!(parse_price("共 2 种 499"), Some(2.0));
+        assert_eq!(parse_price("缺货"), None);
+    }
+
+    #[test]
+    fn whitespace_grouping_requires_three_digit_groups() {
+        // 数量后面跟价格时不应拼成一个数
+        assert_eq!(parse_price("2 1999"), Some(2.0));
+        assert_eq!(parse_price("1 299円"), Some(1299.0));
+    }
+}
@@ -0,0 +1,125 @@
+#![allow(dead_code)]
+
+use crate::domain::models::activity_dto::ActivityFilter;
+use async_trait::async_trait;
+use sea_orm::*;
+
+/// 各表中的操作记录合并为统一的动态行，筛选条件由外层查询使用
+const ACTIVITY_SOURCE: &str = r#"
+    SELECT 'model_created' AS kind, m.created_by AS user_id, m.id AS model_id,
+           NULL::DOUBLE PRECISION AS price, NULL::VARCHAR AS currency, m.created_at
+    FROM models m
+    UNION ALL
+    SELECT 'favorited', f.user_id, f.model_id, NULL, NULL, f.created_at
+    FROM user_model_favorite f
+    UNION ALL
+    SELECT 'purchased', p.user_id, p.model_id, NULL, NULL, p.created_at
+    FROM user_model_purchase p
+    UNION ALL
+    SELECT 'price_added', ph.recorded_by, ph.model_id, ph.price, ph.currency, ph.recorded_at
+    FROM price_history ph
+"#;
+
+const ACTIVITY_WHERE: &str = r#"
+    WHERE ($1::INTEGER IS NULL OR a.user_id = $1)
+      AND ($2::INTEGER IS NULL OR a.model_id = $2)
+      AND (CARDINALITY($3::TEXT[]) = 0 OR a.kind = ANY($3))
+"#;
+
+#[derive(Debug, FromQueryResult)]
+pub struct ActivityRow {
+    pub kind: String,
+    pub user_id: Option<i32>,
+    pub username: Option<String>,
+    pub model_id: i32,
+    pub model_name: String,
+    pub price: Option<f64>,
+    pub currency: Option<String>,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct CountRow {
+    total: i64,
+}
+
+#[async_trait]
+pub trait ActivityRepositoryTrait: Send + Sync {
+    /// 按时间倒序分页
+    async fn find_activity(
+        &self,
+        filter: &ActivityFilter,
+        page: u64,
+        per_page: u64,
+    ) -> Result<(Vec<ActivityRow>, u64), DbErr>;
+}
+
+#[derive(Clone)]
+pub struct ActivityRepository {
+    db: DatabaseConnection,
+}
+
+impl ActivityRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+}
+
+fn filter_values(filter: &ActivityFilter) -> Vec<Value> {
+    let kinds: Vec<String> = filter
+        .kinds
+        .iter()
+        .map(|kind| kind.as_str().to_string())
+        .collect();
+    vec![filter.user_id.into(), filter.model_id.into(), kinds.into()]
+}
+
+#[async_trait]
+impl ActivityRepositoryTrait for ActivityRepository {
+    async fn find_activity(
+        &self,
+        filter: &ActivityFilter,
+        page: u64,
+        per_page: u64,
+    ) -> Result<(Vec<ActivityRow>, u64), DbErr> {
+        let page = page.max(1);
+        let per_page = per_page.max(1);
+
+        let total = CountRow::find_by_statement(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            format!(
+                "SELECT COUNT(*) AS total FROM ({}) a JOIN models m ON m.id = a.model_id {}",
+                ACTIVITY_SOURCE, ACTIVITY_WHERE
+            ),
+            filter_values(filter),
+        ))
+        .one(&self.db)
+        .await?
+        .map_or(0, |row| row.total as u64);
+
+        let mut values = filter_values(filter);
+        values.push((per_page as i64).into());
+        values.push((((page - 1) * per_page) as i64).into());
+        let rows = ActivityRow::find_by_statement(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            format!(
+                r#"
+                SELECT a.kind, a.user_id, u.username, a.model_id, m.name AS model_name,
+                       a.price, a.currency, a.created_at
+                FROM ({}) a
+                JOIN models m ON m.id = a.model_id
+                LEFT JOIN users u ON u.id = a.user_id
+                {}
+                ORDER BY a.created_at DESC, a.model_id DESC, a.kind
+                LIMIT $4 OFFSET $5
+                "#,
+                ACTIVITY_SOURCE, ACTIVITY_WHERE
+            ),
+            values,
+        ))
+        .all(&self.db)
+        .await?;
+
+        Ok((rows, total))
+    }
+}
@@ -0,0 +1,227 @@
+#![allow(dead_code)]
+
+use crate::domain::models::backup_dto::BackupTable;
+use async_trait::async_trait;
+use sea_orm::*;
+use serde_json::Value;
+
+/// 参与备份的表，按外键依赖排列，恢复时依次写入
+pub const BACKUP_TABLES: [&str; 21] = [
+    "users",
+    "currencies",
+    "exchange_rates",
+    "manufacturers",
+    "series",
+    "shops",
+    "models",
+    "tags",
+    "model_tags",
+    "model_revisions",
+    "model_images",
+    "price_watches",
+    "price_history",
+    "user_model_purchase",
+    "user_model_favorite",
+    "build_log_entries",
+    "build_log_images",
+    "notifications",
+    "notification_preferences",
+    "notification_deliveries",
+    "calendar_tokens",
+];
+
+/// 恢复前允许已有数据、恢复时整表替换的表：用户表包含发起恢复的管理员，币种表由迁移预置
+pub const REPLACED_TABLES: [&str; 2] = ["users", "currencies"];
+
+#[derive(Debug, FromQueryResult)]
+struct JsonRow {
+    data: String,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct ColumnName {
+    column_name: String,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct RowCount {
+    count: i64,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct MigrationVersion {
+    version: String,
+}
+
+#[async_trait]
+pub trait BackupRepositoryTrait: Send + Sync {
+    /// 已执行的最新迁移名
+    async fn schema_version(&self) -> Result<Option<String>, DbErr>;
+    /// 表中可写入的列（不含生成列），按定义顺序
+    async fn table_columns(&self, table: &str) -> Result<Vec<String>, DbErr>;
+    async fn dump_table(&self, table: &str, columns: &[String]) -> Result<Vec<Value>, DbErr>;
+    async fn count_rows(&self, table: &str) -> Result<i64, DbErr>;
+    /// 在一个事务中清空用户表并按顺序写入各表数据，最后重置自增序列。
+    /// `placeholder_hash` 非空表示备份不含密码哈希：同名的现有用户保留原密码，其余用户使用占位哈希
+    async fn restore_tables(
+        &self,
+        tables: &[BackupTable],
+        placeholder_hash: Option<&str>,
+    ) -> Result<(), DbErr>;
+}
+
+#[derive(Clone)]
+pub struct BackupRepository {
+    db: DatabaseConnection,
+}
+
+impl BackupRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+}
+
+/// 表名与列名均已对照 `BACKUP_TABLES` 和 information_schema 校验，这里只做标识符转义
+fn quote_ident(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
+fn column_list(columns: &[String]) -> String {
+    columns
+        .iter()
+        .map(|c| quote_ident(c))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[async_trait]
+impl BackupRepositoryTrait for BackupRepository {
+    async fn schema_version(&self) -> Result<Option<String>, DbErr> {
+        let version = MigrationVersion::find_by_statement(Statement::from_string(
+            DbBackend::Postgres,
+            "SELECT version FROM seaql_migrations ORDER BY version DESC LIMIT 1",
+        ))
+        .one(&self.db)
+        .await?;
+        Ok(version.map(|v| v.version))
+    }
+
+    async fn table_columns(&self, table: &str) -> Result<Vec<String>, DbErr> {
+        let columns = ColumnName::find_by_statement(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            r#"
+            SELECT column_name::text AS column_name
+            FROM information_schema.columns
+            WHERE table_schema = current_schema()
+              AND table_name = $1
+              AND is_generated = 'NEVER'
+            ORDER BY ordinal_position
+            "#,
+            [table.into()],
+        ))
+        .all(&self.db)
+        .await?;
+        Ok(columns.into_iter().map(|c| c.column_name).collect())
+    }
+
+    async fn dump_table(&self, table: &str, columns: &[String]) -> Result<Vec<Value>, DbErr> {
+        let sql = format!(
+            "SELECT row_to_json(t)::text AS data FROM (SELECT {} FROM {} ORDER BY 1) t",
+            column_list(columns),
+            quote_ident(table)
+        );
+        let rows = JsonRow::find_by_statement(Statement::from_string(DbBackend::Postgres, sql))
+            .all(&self.db)
+            .await?;
+        rows.into_iter()
+            .map(|row| serde_json::from_str(&row.data).map_err(|e| DbErr::Json(e.to_string())))
+            .collect()
+    }
+
+    async fn count_rows(&self, table: &str) -> Result<i64, DbErr> {
+        let sql = format!("SELECT COUNT(*) AS count FROM {}", quote_ident(table));
+        let count = RowCount::find_by_statement(Statement::from_string(DbBackend::Postgres, sql))
+            .one(&self.db)
+            .await?;
+        Ok(count.map(|c| c.count).unwrap_or_default())
+    }
+
+    async fn restore_tables(
+        &self,
+        tables: &[BackupTable],
+        placeholder_hash: Option<&str>,
+    ) -> Result<(), DbErr> {
+        let txn = self.db.begin().await?;
+        if placeholder_hash.is_some() {
+            txn.execute_unprepared(
+                "CREATE TEMP TABLE restore_existing_users ON COMMIT DROP AS \
+                 SELECT username, password_hash FROM users",
+            )
+            .await?;
+        }
+        // 发起恢复的管理员账号存在于用户表中，其余表在调用前已确认为空；
+        // 只在备份带有用户数据时替换现有账号（服务层会拒绝没有用户数据的备份）
+        if tables
+            .iter()
+            .any(|t| t.name == "users" && !t.rows.is_empty())
+        {
+            txn.execute_unprepared("DELETE FROM users").await?;
+        }
+        if tables
+            .iter()
+            .any(|t| t.name == "currencies" && !t.rows.is_empty())
+        {
+            txn.execute_unprepared("DELETE FROM currencies").await?;
+        }
+
+        for table in tables {
+            if table.rows.is_empty() {
+                continue;
+            }
+
+            let mut insert_columns = column_list(&table.columns);
+            let mut select_columns = insert_columns.clone();
+            let mut values: Vec<sea_orm::Value> = vec![serde_json::to_string(&table.rows)
+                .map_err(|e| DbErr::Json(e.to_string()))?
+                .into()];
+            if let (Some(hash), "users") = (placeholder_hash, table.name.as_str()) {
+                insert_columns.push_str(", password_hash");
+                select_columns.push_str(", $2");
+                values.push(hash.into());
+            }
+
+            let sql = format!(
+                "INSERT INTO {table} ({insert_columns}) \
+                 SELECT {select_columns} FROM json_populate_recordset(NULL::{table}, $1::json)",
+                table = quote_ident(&table.name),
+            );
+            txn.execute(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                sql,
+                values,
+            ))
+            .await?;
+
+            if table.columns.iter().any(|c| c == "id") {
+                let sql = format!(
+                    "SELECT setval(pg_get_serial_sequence('{name}', 'id'), \
+                     COALESCE((SELECT MAX(id) FROM {table}), 0) + 1, false)",
+                    name = table.name,
+                    table = quote_ident(&table.name),
+                );
+                txn.execute_unprepared(&sql).await?;
+            }
+        }
+
+        if placeholder_hash.is_some() {
+            txn.execute_unprepared(
+                "UPDATE users u SET password_hash = e.password_hash \
+                 FROM restore_existing_users e WHERE u.username = e.username",
+            )
+            .await?;
+        }
+
+        txn.commit().await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+#![allow(dead_code)]
+
+use crate::domain::entities::{build_log_entry, build_log_image, user_model_purchase};
+use async_trait::async_trait;
+use sea_orm::ActiveValue::Set;
+use sea_orm::*;
+use std::collections::HashMap;
+
+#[async_trait]
+pub trait BuildLogRepositoryTrait: Send + Sync {
+    async fn find_purchase(
+        &self,
+        user_id: i32,
+        model_id: i32,
+    ) -> Result<Option<user_model_purchase::Model>, DbErr>;
+    /// 按日期先后排列，同一天按录入顺序
+    async fn find_entries(&self, purchase_id: i32) -> Result<Vec<build_log_entry::Model>, DbErr>;
+    /// 日志条目 ID -> 图片
+    async fn find_images(
+        &self,
+        entry_ids: Vec<i32>,
+    ) -> Result<HashMap<i32, Vec<build_log_image::Model>>, DbErr>;
+    /// 在同一事务中写入日志条目及其图片
+    async fn create_entry(
+        &self,
+        entry: build_log_entry::ActiveModel,
+        images: Vec<build_log_image::ActiveModel>,
+    ) -> Result<build_log_entry::Model, DbErr>;
+}
+
+#[derive(Clone)]
+pub struct BuildLogRepository {
+    db: DatabaseConnection,
+}
+
+impl BuildLogRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl BuildLogRepositoryTrait for BuildLogRepository {
+    async fn find_purchase(
+        &self,
+        user_id: i32,
+        model_id: i32,
+    ) -> Result<Option<user_model_purchase::Model>, DbErr> {
+        user_model_purchase::Entity::find()
+            .filter(user_model_purchase::Column::UserId.eq(user_id))
+            .filter(user_model_purchase::Column::ModelId.eq(model_id))
+            .one(&self.db)
+            .await
+    }
+
+    async fn find_entries(&self, purchase_id: i32) -> Result<Vec<build_log_entry::Model>, DbErr> {
+        build_log_entry::Entity::find()
+            .filter(build_log_entry::Column::PurchaseId.eq(purchase_id))
+            .order_by_asc(build_log_entry::Column::EntryDate)
+            .order_by_asc(build_log_entry::Column::Id)
+            .all(&self.db)
+            .await
+    }
+
+    async fn find_images(
+        &self,
+        entry_ids: Vec<i32>,
+    ) -> Result<HashMap<i32, Vec<build_log_image::Model>>, DbErr> {
+        if entry_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let rows = build_log_image::Entity::find()
+            .filter(build_log_image::Column::EntryId.is_in(entry_ids))
+            .order_by_asc(build_log_image::Column::Id)
+            .all(&self.db)
+            .await?;
+
+        let mut images: HashMap<i32, Vec<build_log_image::Model>> = HashMap::new();
+        for image in rows {
+            images.entry(image.entry_id).or_default().push(image);
+        }
+        Ok(images)
+    }
+
+    async fn create_entry(
+        &self,
+        mut entry: build_log_entry::ActiveModel,
+        images: Vec<build_log_image::ActiveModel>,
+    ) -> Result<build_log_entry::Model, DbErr> {
+        let txn = self.db.begin().await?;
+        let now = chrono::Utc::now().naive_utc();
+
+        entry.created_at = Set(now);
+        let created = entry.insert(&txn).await?;
+
+        let images: Vec<build_log_image::ActiveModel> = images
+            .into_iter()
+            .map(|mut image| {
+                image.entry_id = Set(created.id);
+                image.created_at = Set(now);
+                image
+            })
+            .collect();
+        if !images.is_empty() {
+            build_log_image::Entity::insert_many(images)
+                .exec(&txn)
+                .await?;
+        }
+
+        txn.commit().await?;
+        Ok(created)
+    }
+}
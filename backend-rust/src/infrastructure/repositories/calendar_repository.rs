@@ -0,0 +1,127 @@
+#![allow(dead_code)]
+
+use crate::domain::entities::{calendar_token, model, user_model_purchase};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::*;
+
+#[async_trait]
+pub trait CalendarRepositoryTrait: Send + Sync {
+    /// 发售日期或开订日期落在区间内的模型
+    async fn find_scheduled_models(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<model::Model>, DbErr>;
+    /// 用户尾款截止或发货日期落在区间内的购买记录
+    async fn find_scheduled_purchases(
+        &self,
+        user_id: i32,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<(user_model_purchase::Model, model::Model)>, DbErr>;
+    async fn find_token_by_user(
+        &self,
+        user_id: i32,
+    ) -> Result<Option<calendar_token::Model>, DbErr>;
+    async fn find_token(&self, token: &str) -> Result<Option<calendar_token::Model>, DbErr>;
+    /// 写入用户的订阅令牌，已有令牌时覆盖
+    async fn upsert_token(
+        &self,
+        user_id: i32,
+        token: String,
+    ) -> Result<calendar_token::Model, DbErr>;
+}
+
+#[derive(Clone)]
+pub struct CalendarRepository {
+    db: DatabaseConnection,
+}
+
+impl CalendarRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl CalendarRepositoryTrait for CalendarRepository {
+    async fn find_scheduled_models(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<model::Model>, DbErr> {
+        model::Entity::find()
+            .filter(
+                Condition::any()
+                    .add(model::Column::ReleaseDate.between(from, to))
+                    .add(model::Column::PreorderOpenDate.between(from, to)),
+            )
+            .order_by_asc(model::Column::Name)
+            .all(&self.db)
+            .await
+    }
+
+    async fn find_scheduled_purchases(
+        &self,
+        user_id: i32,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<(user_model_purchase::Model, model::Model)>, DbErr> {
+        let rows = user_model_purchase::Entity::find()
+            .find_also_related(model::Entity)
+            .filter(user_model_purchase::Column::UserId.eq(user_id))
+            .filter(
+                Condition::any()
+                    .add(user_model_purchase::Column::PaymentDueDate.between(from, to))
+                    .add(user_model_purchase::Column::ShipDate.between(from, to)),
+            )
+            .all(&self.db)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(purchase, model)| model.map(|model| (purchase, model)))
+            .collect())
+    }
+
+    async fn find_token_by_user(
+        &self,
+        user_id: i32,
+    ) -> Result<Option<calendar_token::Model>, DbErr> {
+        calendar_token::Entity::find_by_id(user_id)
+            .one(&self.db)
+            .await
+    }
+
+    async fn find_token(&self, token: &str) -> Result<Option<calendar_token::Model>, DbErr> {
+        calendar_token::Entity::find()
+            .filter(calendar_token::Column::Token.eq(token))
+            .one(&self.db)
+            .await
+    }
+
+    async fn upsert_token(
+        &self,
+        user_id: i32,
+        token: String,
+    ) -> Result<calendar_token::Model, DbErr> {
+        let active = calendar_token::ActiveModel {
+            user_id: Set(user_id),
+            token: Set(token),
+            created_at: Set(chrono::Utc::now().naive_utc()),
+        };
+        calendar_token::Entity::insert(active)
+            .on_conflict(
+                OnConflict::column(calendar_token::Column::UserId)
+                    .update_columns([
+                        calendar_token::Column::Token,
+                        calendar_token::Column::CreatedAt,
+                    ])
+                    .to_owned(),
+            )
+            .exec_with_returning(&self.db)
+            .await
+    }
+}
@@ -0,0 +1,131 @@
+#![allow(dead_code)]
+
+use crate::domain::entities::{currency, exchange_rate};
+use async_trait::async_trait;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::*;
+
+#[async_trait]
+pub trait CurrencyRepositoryTrait: Send + Sync {
+    async fn find_currencies(&self) -> Result<Vec<currency::Model>, DbErr>;
+    async fn find_currency(&self, code: &str) -> Result<Option<currency::Model>, DbErr>;
+    /// 按生效日期倒序
+    async fn find_rates(
+        &self,
+        base: Option<String>,
+        quote: Option<String>,
+        until: Option<chrono::NaiveDate>,
+        limit: u64,
+    ) -> Result<Vec<exchange_rate::Model>, DbErr>;
+    /// 涉及任一指定币种、且在 `until` 当天及之前生效的全部汇率，用于换算
+    async fn find_rates_involving(
+        &self,
+        currencies: Vec<String>,
+        until: chrono::NaiveDate,
+    ) -> Result<Vec<exchange_rate::Model>, DbErr>;
+    /// 写入汇率，同一币种对同一天已有汇率时覆盖，返回写入条数
+    async fn upsert_rates(&self, rates: Vec<exchange_rate::ActiveModel>) -> Result<u64, DbErr>;
+    async fn delete_rate(&self, id: i32) -> Result<u64, DbErr>;
+}
+
+#[derive(Clone)]
+pub struct CurrencyRepository {
+    db: DatabaseConnection,
+}
+
+impl CurrencyRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl CurrencyRepositoryTrait for CurrencyRepository {
+    async fn find_currencies(&self) -> Result<Vec<currency::Model>, DbErr> {
+        currency::Entity::find()
+            .order_by_asc(currency::Column::Code)
+            .all(&self.db)
+            .await
+    }
+
+    async fn find_currency(&self, code: &str) -> Result<Option<currency::Model>, DbErr> {
+        currency::Entity::find_by_id(code.to_string())
+            .one(&self.db)
+            .await
+    }
+
+    async fn find_rates(
+        &self,
+        base: Option<String>,
+        quote: Option<String>,
+        until: Option<chrono::NaiveDate>,
+        limit: u64,
+    ) -> Result<Vec<exchange_rate::Model>, DbErr> {
+        let mut query = exchange_rate::Entity::find();
+        if let Some(base) = base {
+            query = query.filter(exchange_rate::Column::BaseCurrency.eq(base));
+        }
+        if let Some(quote) = quote {
+            query = query.filter(exchange_rate::Column::QuoteCurrency.eq(quote));
+        }
+        if let Some(until) = until {
+            query = query.filter(exchange_rate::Column::EffectiveDate.lte(until));
+        }
+        query
+            .order_by_desc(exchange_rate::Column::EffectiveDate)
+            .order_by_asc(exchange_rate::Column::BaseCurrency)
+            .order_by_asc(exchange_rate::Column::QuoteCurrency)
+            .limit(limit)
+            .all(&self.db)
+            .await
+    }
+
+    async fn find_rates_involving(
+        &self,
+        currencies: Vec<String>,
+        until: chrono::NaiveDate,
+    ) -> Result<Vec<exchange_rate::Model>, DbErr> {
+        if currencies.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        exchange_rate::Entity::find()
+            .filter(
+                Condition::any()
+                    .add(exchange_rate::Column::BaseCurrency.is_in(currencies.clone()))
+                    .add(exchange_rate::Column::QuoteCurrency.is_in(currencies)),
+            )
+            .filter(exchange_rate::Column::EffectiveDate.lte(until))
+            .order_by_asc(exchange_rate::Column::EffectiveDate)
+            .all(&self.db)
+            .await
+    }
+
+    async fn upsert_rates(&self, rates: Vec<exchange_rate::ActiveModel>) -> Result<u64, DbErr> {
+        if rates.is_empty() {
+            return Ok(0);
+        }
+
+        let count = rates.len() as u64;
+        exchange_rate::Entity::insert_many(rates)
+            .on_conflict(
+                OnConflict::columns([
+                    exchange_rate::Column::BaseCurrency,
+                    exchange_rate::Column::QuoteCurrency,
+                    exchange_rate::Column::EffectiveDate,
+                ])
+                .update_columns([exchange_rate::Column::Rate, exchange_rate::Column::Source])
+                .to_owned(),
+            )
+            .exec_without_returning(&self.db)
+            .await?;
+        Ok(count)
+    }
+
+    async fn delete_rate(&self, id: i32) -> Result<u64, DbErr> {
+        Ok(exchange_rate::Entity::delete_by_id(id)
+            .exec(&self.db)
+            .await?
+            .rows_affected)
+    }
+}
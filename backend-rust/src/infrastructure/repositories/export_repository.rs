@@ -0,0 +1,226 @@
+#![allow(dead_code)]
+
+use super::model_repository::apply_model_filter;
+use crate::domain::entities::{
+    manufacturer, model, model_tag, price_history, series, tag, user_model_favorite,
+    user_model_purchase,
+};
+use crate::domain::models::export_dto::ExportScope;
+use crate::domain::models::model_dto::ModelFilter;
+use async_trait::async_trait;
+use sea_orm::sea_query::{Expr, Query};
+use sea_orm::*;
+use std::collections::{HashMap, HashSet};
+
+#[async_trait]
+pub trait ExportRepositoryTrait: Send + Sync {
+    /// 按 ID 游标分批读取，避免一次性加载整个目录
+    async fn find_models_after(
+        &self,
+        scope: ExportScope,
+        filter: &ModelFilter,
+        after_id: i32,
+        limit: u64,
+    ) -> Result<Vec<model::Model>, DbErr>;
+    async fn find_manufacturer_names(&self, ids: Vec<i32>) -> Result<HashMap<i32, String>, DbErr>;
+    async fn find_series_names(&self, ids: Vec<i32>) -> Result<HashMap<i32, String>, DbErr>;
+    async fn find_tag_names(&self, model_ids: Vec<i32>)
+        -> Result<HashMap<i32, Vec<String>>, DbErr>;
+    /// 评分列仅存在于数据库中（0~5，步长 0.5），读取时转换为浮点
+    async fn find_ratings(&self, model_ids: Vec<i32>) -> Result<HashMap<i32, f64>, DbErr>;
+    /// 每个模型最近一条价格记录
+    async fn find_latest_prices(
+        &self,
+        model_ids: Vec<i32>,
+    ) -> Result<HashMap<i32, price_history::Model>, DbErr>;
+    /// 返回用户已收藏、已购买的模型 ID
+    async fn find_user_flags(
+        &self,
+        user_id: i32,
+        model_ids: Vec<i32>,
+    ) -> Result<(HashSet<i32>, HashSet<i32>), DbErr>;
+}
+
+#[derive(Clone)]
+pub struct ExportRepository {
+    db: DatabaseConnection,
+}
+
+impl ExportRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl ExportRepositoryTrait for ExportRepository {
+    async fn find_models_after(
+        &self,
+        scope: ExportScope,
+        filter: &ModelFilter,
+        after_id: i32,
+        limit: u64,
+    ) -> Result<Vec<model::Model>, DbErr> {
+        let mut query = apply_model_filter(model::Entity::find(), filter)
+            .filter(model::Column::Id.gt(after_id));
+
+        if let ExportScope::User(user_id) = scope {
+            let favorites = Query::select()
+                .column(user_model_favorite::Column::ModelId)
+                .from(user_model_favorite::Entity)
+                .and_where(Expr::col(user_model_favorite::Column::UserId).eq(user_id))
+                .to_owned();
+            let purchases = Query::select()
+                .column(user_model_purchase::Column::ModelId)
+                .from(user_model_purchase::Entity)
+                .and_where(Expr::col(user_model_purchase::Column::UserId).eq(user_id))
+                .to_owned();
+            query = query.filter(
+                Condition::any()
+                    .add(model::Column::Id.in_subquery(favorites))
+                    .add(model::Column::Id.in_subquery(purchases)),
+            );
+        }
+
+        query
+            .order_by_asc(model::Column::Id)
+            .limit(limit)
+            .all(&self.db)
+            .await
+    }
+
+    async fn find_manufacturer_names(&self, ids: Vec<i32>) -> Result<HashMap<i32, String>, DbErr> {
+        if ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let names: Vec<(i32, String)> = manufacturer::Entity::find()
+            .select_only()
+            .column(manufacturer::Column::Id)
+            .column(manufacturer::Column::Name)
+            .filter(manufacturer::Column::Id.is_in(ids))
+            .into_tuple()
+            .all(&self.db)
+            .await?;
+
+        Ok(names.into_iter().collect())
+    }
+
+    async fn find_series_names(&self, ids: Vec<i32>) -> Result<HashMap<i32, String>, DbErr> {
+        if ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let names: Vec<(i32, String)> = series::Entity::find()
+            .select_only()
+            .column(series::Column::Id)
+            .column(series::Column::Name)
+            .filter(series::Column::Id.is_in(ids))
+            .into_tuple()
+            .all(&self.db)
+            .await?;
+
+        Ok(names.into_iter().collect())
+    }
+
+    async fn find_tag_names(
+        &self,
+        model_ids: Vec<i32>,
+    ) -> Result<HashMap<i32, Vec<String>>, DbErr> {
+        if model_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let rows: Vec<(i32, String)> = model_tag::Entity::find()
+            .select_only()
+            .column(model_tag::Column::ModelId)
+            .column(tag::Column::Name)
+            .join(JoinType::InnerJoin, model_tag::Relation::Tag.def())
+            .filter(model_tag::Column::ModelId.is_in(model_ids))
+            .order_by_asc(tag::Column::Name)
+            .into_tuple()
+            .all(&self.db)
+            .await?;
+
+        let mut tags: HashMap<i32, Vec<String>> = HashMap::new();
+        for (model_id, name) in rows {
+            tags.entry(model_id).or_default().push(name);
+        }
+        Ok(tags)
+    }
+
+    async fn find_ratings(&self, model_ids: Vec<i32>) -> Result<HashMap<i32, f64>, DbErr> {
+        if model_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let ratings: Vec<(i32, Option<f64>)> = model::Entity::find()
+            .select_only()
+            .column(model::Column::Id)
+            .column_as(
+                Expr::cust("CAST(models.rating AS DOUBLE PRECISION)"),
+                "rating",
+            )
+            .filter(model::Column::Id.is_in(model_ids))
+            .into_tuple()
+            .all(&self.db)
+            .await?;
+
+        Ok(ratings
+            .into_iter()
+            .filter_map(|(id, rating)| rating.map(|r| (id, r)))
+            .collect())
+    }
+
+    async fn find_latest_prices(
+        &self,
+        model_ids: Vec<i32>,
+    ) -> Result<HashMap<i32, price_history::Model>, DbErr> {
+        if model_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let prices = price_history::Entity::find()
+            .distinct_on([price_history::Column::ModelId])
+            .filter(price_history::Column::ModelId.is_in(model_ids))
+            .order_by_asc(price_history::Column::ModelId)
+            .order_by_desc(price_history::Column::RecordedAt)
+            .order_by_desc(price_history::Column::Id)
+            .all(&self.db)
+            .await?;
+
+        Ok(prices.into_iter().map(|p| (p.model_id, p)).collect())
+    }
+
+    async fn find_user_flags(
+        &self,
+        user_id: i32,
+        model_ids: Vec<i32>,
+    ) -> Result<(HashSet<i32>, HashSet<i32>), DbErr> {
+        if model_ids.is_empty() {
+            return Ok((HashSet::new(), HashSet::new()));
+        }
+
+        let favorites: Vec<i32> = user_model_favorite::Entity::find()
+            .select_only()
+            .column(user_model_favorite::Column::ModelId)
+            .filter(user_model_favorite::Column::UserId.eq(user_id))
+            .filter(user_model_favorite::Column::ModelId.is_in(model_ids.clone()))
+            .into_tuple()
+            .all(&self.db)
+            .await?;
+        let purchases: Vec<i32> = user_model_purchase::Entity::find()
+            .select_only()
+            .column(user_model_purchase::Column::ModelId)
+            .filter(user_model_purchase::Column::UserId.eq(user_id))
+            .filter(user_model_purchase::Column::ModelId.is_in(model_ids))
+            .into_tuple()
+            .all(&self.db)
+            .await?;
+
+        Ok((
+            favorites.into_iter().collect(),
+            purchases.into_iter().collect(),
+        ))
+    }
+}
@@ -2,23 +2,31 @@
 
 use crate::domain::entities::manufacturer;
 use async_trait::async_trait;
+use sea_orm::sea_query::{Expr, Func};
 use sea_orm::*;
 
 #[async_trait]
 pub trait ManufacturerRepositoryTrait: Send + Sync {
     async fn find_all(&self) -> Result<Vec<manufacturer::Model>, DbErr>;
     async fn find_by_id(&self, id: i32) -> Result<Option<manufacturer::Model>, DbErr>;
+    /// 按简称或全称查找，忽略大小写与首尾空白
     async fn find_by_name(&self, name: &str) -> Result<Option<manufacturer::Model>, DbErr>;
     async fn create(
         &self,
         manufacturer_data: manufacturer::ActiveModel,
     ) -> Result<manufacturer::Model, DbErr>;
+    /// 传入 `expected_updated_at` 且与当前行不一致时返回 `DbErr::RecordNotUpdated`
     async fn update(
         &self,
         id: i32,
         manufacturer_data: manufacturer::ActiveModel,
+        expected_updated_at: Option<chrono::NaiveDateTime>,
     ) -> Result<manufacturer::Model, DbErr>;
-    async fn delete(&self, id: i32) -> Result<DeleteResult, DbErr>;
+    async fn delete(
+        &self,
+        id: i32,
+        expected_updated_at: Option<chrono::NaiveDateTime>,
+    ) -> Result<DeleteResult, DbErr>;
 }
 
 #[derive(Clone)]
@@ -46,8 +54,19 @@ impl ManufacturerRepositoryTrait for ManufacturerRepository {
     }
 
     async fn find_by_name(&self, name: &str) -> Result<Option<manufacturer::Model>, DbErr> {
+        let name = name.trim().to_lowercase();
         manufacturer::Entity::find()
-            .filter(manufacturer::Column::Name.eq(name))
+            .filter(
+                Condition::any()
+                    .add(
+                        Expr::expr(Func::lower(Expr::col(manufacturer::Column::Name)))
+                            .eq(name.clone()),
+                    )
+                    .add(
+                        Expr::expr(Func::lower(Expr::col(manufacturer::Column::FullName))).eq(name),
+                    ),
+            )
+            .order_by_asc(manufacturer::Column::Id)
             .one(&self.db)
             .await
     }
@@ -63,12 +82,36 @@ impl ManufacturerRepositoryTrait for ManufacturerRepository {
         &self,
         id: i32,
         mut manufacturer_data: manufacturer::ActiveModel,
+        expected_updated_at: Option<chrono::NaiveDateTime>,
     ) -> Result<manufacturer::Model, DbErr> {
+        let txn = self.db.begin().await?;
+
+        let existing = manufacturer::Entity::find_by_id(id)
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+            .ok_or_else(|| DbErr::RecordNotFound(format!("manufacturer {}", id)))?;
+
+        if expected_updated_at.is_some_and(|expected| expected != existing.updated_at) {
+            return Err(DbErr::RecordNotUpdated);
+        }
+
         manufacturer_data.id = Set(id);
-        manufacturer_data.update(&self.db).await
+        let updated = manufacturer_data.update(&txn).await?;
+
+        txn.commit().await?;
+        Ok(updated)
     }
 
-    async fn delete(&self, id: i32) -> Result<DeleteResult, DbErr> {
-        manufacturer::Entity::delete_by_id(id).exec(&self.db).await
+    async fn delete(
+        &self,
+        id: i32,
+        expected_updated_at: Option<chrono::NaiveDateTime>,
+    ) -> Result<DeleteResult, DbErr> {
+        let mut query = manufacturer::Entity::delete_many().filter(manufacturer::Column::Id.eq(id));
+        if let Some(expected) = expected_updated_at {
+            query = query.filter(manufacturer::Column::UpdatedAt.eq(expected));
+        }
+        query.exec(&self.db).await
     }
 }
@@ -1,3 +1,16 @@
+pub mod activity_repository;
+pub mod backup_repository;
+pub mod build_log_repository;
+pub mod calendar_repository;
+pub mod currency_repository;
+pub mod export_repository;
 pub mod manufacturer_repository;
+pub mod model_image_repository;
 pub mod model_repository;
+pub mod notification_repository;
+pub mod price_watch_repository;
+pub mod search_repository;
+pub mod series_repository;
+pub mod shop_repository;
+pub mod tag_repository;
 pub mod user_repository;
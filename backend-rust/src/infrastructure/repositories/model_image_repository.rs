@@ -0,0 +1,159 @@
+#![allow(dead_code)]
+
+use crate::domain::entities::{model, model_image};
+use async_trait::async_trait;
+use sea_orm::sea_query::Expr;
+use sea_orm::*;
+
+#[async_trait]
+pub trait ModelImageRepositoryTrait: Send + Sync {
+    async fn model_exists(&self, model_id: i32) -> Result<bool, DbErr>;
+    /// 主图在前，其余按上传顺序
+    async fn find_by_model(&self, model_id: i32) -> Result<Vec<model_image::Model>, DbErr>;
+    /// 写入图片记录；模型的第一张图片自动成为主图
+    async fn create(
+        &self,
+        image: model_image::ActiveModel,
+        make_primary: bool,
+    ) -> Result<model_image::Model, DbErr>;
+    async fn set_primary(
+        &self,
+        model_id: i32,
+        image_id: i32,
+    ) -> Result<Option<model_image::Model>, DbErr>;
+    /// 删除图片并返回被删除的记录；删除的是主图时由最早上传的剩余图片接替
+    async fn delete(
+        &self,
+        model_id: i32,
+        image_id: i32,
+    ) -> Result<Option<model_image::Model>, DbErr>;
+}
+
+#[derive(Clone)]
+pub struct ModelImageRepository {
+    db: DatabaseConnection,
+}
+
+impl ModelImageRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+}
+
+/// 取消模型现有主图，需在设置新主图前执行以满足每个模型一张主图的唯一索引
+async fn clear_primary<C: ConnectionTrait>(db: &C, model_id: i32) -> Result<(), DbErr> {
+    model_image::Entity::update_many()
+        .col_expr(model_image::Column::IsPrimary, Expr::value(false))
+        .filter(model_image::Column::ModelId.eq(model_id))
+        .filter(model_image::Column::IsPrimary.eq(true))
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+#[async_trait]
+impl ModelImageRepositoryTrait for ModelImageRepository {
+    async fn model_exists(&self, model_id: i32) -> Result<bool, DbErr> {
+        Ok(model::Entity::find_by_id(model_id).count(&self.db).await? > 0)
+    }
+
+    async fn find_by_model(&self, model_id: i32) -> Result<Vec<model_image::Model>, DbErr> {
+        model_image::Entity::find()
+            .filter(model_image::Column::ModelId.eq(model_id))
+            .order_by_desc(model_image::Column::IsPrimary)
+            .order_by_asc(model_image::Column::Id)
+            .all(&self.db)
+            .await
+    }
+
+    async fn create(
+        &self,
+        mut image: model_image::ActiveModel,
+        make_primary: bool,
+    ) -> Result<model_image::Model, DbErr> {
+        let txn = self.db.begin().await?;
+        let model_id = image
+            .model_id
+            .clone()
+            .take()
+            .ok_or_else(|| DbErr::Custom("图片记录缺少 model_id".to_string()))?;
+
+        let has_primary = model_image::Entity::find()
+            .filter(model_image::Column::ModelId.eq(model_id))
+            .filter(model_image::Column::IsPrimary.eq(true))
+            .count(&txn)
+            .await?
+            > 0;
+        if make_primary && has_primary {
+            clear_primary(&txn, model_id).await?;
+        }
+        image.is_primary = Set(make_primary || !has_primary);
+        image.created_at = Set(chrono::Utc::now().naive_utc());
+
+        let created = image.insert(&txn).await?;
+        txn.commit().await?;
+        Ok(created)
+    }
+
+    async fn set_primary(
+        &self,
+        model_id: i32,
+        image_id: i32,
+    ) -> Result<Option<model_image::Model>, DbErr> {
+        let txn = self.db.begin().await?;
+
+        let Some(image) = model_image::Entity::find_by_id(image_id)
+            .filter(model_image::Column::ModelId.eq(model_id))
+            .one(&txn)
+            .await?
+        else {
+            return Ok(None);
+        };
+        if image.is_primary {
+            return Ok(Some(image));
+        }
+
+        clear_primary(&txn, model_id).await?;
+        let mut image: model_image::ActiveModel = image.into();
+        image.is_primary = Set(true);
+        let updated = image.update(&txn).await?;
+
+        txn.commit().await?;
+        Ok(Some(updated))
+    }
+
+    async fn delete(
+        &self,
+        model_id: i32,
+        image_id: i32,
+    ) -> Result<Option<model_image::Model>, DbErr> {
+        let txn = self.db.begin().await?;
+
+        let Some(image) = model_image::Entity::find_by_id(image_id)
+            .filter(model_image::Column::ModelId.eq(model_id))
+            .one(&txn)
+            .await?
+        else {
+            return Ok(None);
+        };
+        model_image::Entity::delete_by_id(image.id)
+            .exec(&txn)
+            .await?;
+
+        if image.is_primary {
+            let successor = model_image::Entity::find()
+                .filter(model_image::Column::ModelId.eq(model_id))
+                .order_by_asc(model_image::Column::Id)
+                .one(&txn)
+                .await?;
+            if let Some(successor) = successor {
+                let mut successor: model_image::ActiveModel = successor.into();
+                successor.is_primary = Set(true);
+                successor.update(&txn).await?;
+            }
+        }
+
+        txn.commit().await?;
+        Ok(Some(image))
+    }
+}
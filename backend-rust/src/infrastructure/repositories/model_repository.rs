@@ -1,18 +1,81 @@
 #![allow(dead_code)]
 
-use crate::domain::entities::{model, user_model_favorite, user_model_purchase};
+use crate::domain::entities::enums::{Grade, ModelStatus};
+use crate::domain::entities::{
+    build_log_entry, build_log_image, manufacturer, model, model_image, model_revision, model_tag,
+    price_history, price_watch, series, shop, tag, user_model_favorite, user_model_purchase,
+};
+use crate::domain::models::model_dto::{MergeSummary, ModelFilter, TagMatchMode};
 use async_trait::async_trait;
+use sea_orm::sea_query::{Expr, Func, OnConflict, Query, SelectStatement};
 use sea_orm::ActiveValue::Set;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, DeleteResult, EntityTrait, JoinType,
-    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, RelationTrait,
+    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, DatabaseTransaction, DbBackend,
+    DbErr, DeleteResult, EntityTrait, FromQueryResult, JoinType, ModelTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect, RelationTrait, Select, Statement, TransactionTrait,
 };
+use std::collections::HashMap;
+
+/// 沿 parent_id 找到最上层的祖先，再向下展开整棵变体树；
+/// 路径数组用于防止历史数据中的环导致无限递归
+const FAMILY_SQL: &str = r#"
+WITH RECURSIVE ancestors AS (
+    SELECT id, parent_id, 0 AS up, ARRAY[id] AS path
+    FROM models
+    WHERE id = $1
+    UNION ALL
+    SELECT m.id, m.parent_id, a.up + 1, a.path || m.id
+    FROM models m
+    JOIN ancestors a ON m.id = a.parent_id
+    WHERE NOT m.id = ANY(a.path)
+),
+root AS (
+    SELECT id FROM ancestors ORDER BY up DESC LIMIT 1
+),
+family AS (
+    SELECT id, 0 AS depth, ARRAY[id] AS path
+    FROM root
+    UNION ALL
+    SELECT m.id, f.depth + 1, f.path || m.id
+    FROM models m
+    JOIN family f ON m.parent_id = f.id
+    WHERE NOT m.id = ANY(f.path)
+)
+SELECT f.id,
+       f.depth,
+       (SELECT COUNT(DISTINCT p.user_id) FROM user_model_purchase p WHERE p.model_id = f.id)
+           AS owner_count,
+       (SELECT COUNT(*) FROM user_model_favorite fv WHERE fv.model_id = f.id)
+           AS favorite_count
+FROM family f
+ORDER BY f.depth, f.id
+"#;
+
+/// 切换购买状态的结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PurchaseToggle {
+    Added,
+    Removed,
+    /// 购买记录下还有制作日志（条数），未取消购买
+    HasBuildLog(u64),
+}
+
+/// 变体树中的一个模型及其购买、收藏人数
+#[derive(Debug, FromQueryResult)]
+pub struct FamilyMember {
+    pub id: i32,
+    /// 距根模型的层数，根模型为 0
+    pub depth: i32,
+    pub owner_count: i64,
+    pub favorite_count: i64,
+}
 
 #[async_trait]
 pub trait ModelRepositoryTrait: Send + Sync {
     async fn find_all(
         &self,
         user_id: Option<i32>,
+        filter: &ModelFilter,
         page: u64,
         per_page: u64,
     ) -> Result<(Vec<model::Model>, u64), DbErr>;
@@ -26,21 +89,112 @@ pub trait ModelRepositoryTrait: Send + Sync {
         parent_id: i32,
         user_id: Option<i32>,
     ) -> Result<Vec<model::Model>, DbErr>;
-    async fn create(&self, model_data: model::ActiveModel) -> Result<model::Model, DbErr>;
+    /// 模型所在的整棵变体树（从根模型开始），模型不存在时为空
+    async fn find_family(&self, model_id: i32) -> Result<Vec<FamilyMember>, DbErr>;
+    async fn find_by_ids(&self, ids: Vec<i32>) -> Result<Vec<model::Model>, DbErr>;
+    async fn find_by_series(
+        &self,
+        series_id: i32,
+        page: u64,
+        per_page: u64,
+    ) -> Result<(Vec<model::Model>, u64), DbErr>;
+    /// 同厂商、同等级的模型，用于重复检测；等级为空时匹配等级同样为空的模型
+    async fn find_by_manufacturer_and_category(
+        &self,
+        manufacturer_id: i32,
+        category: Option<Grade>,
+    ) -> Result<Vec<model::Model>, DbErr>;
+    /// 同厂商下名称相同（忽略大小写）的模型，用于导入时判断新建还是更新
+    async fn find_by_manufacturer_and_name(
+        &self,
+        manufacturer_id: i32,
+        name: &str,
+    ) -> Result<Option<model::Model>, DbErr>;
+    /// 全部模型，按厂商、等级、ID 排序
+    async fn find_all_for_duplicate_check(&self) -> Result<Vec<model::Model>, DbErr>;
+    async fn manufacturer_exists(&self, manufacturer_id: i32) -> Result<bool, DbErr>;
+    async fn series_exists(&self, series_id: i32) -> Result<bool, DbErr>;
+    async fn find_series_names(&self, series_ids: Vec<i32>) -> Result<HashMap<i32, String>, DbErr>;
+    async fn find_tag_names(&self, model_ids: Vec<i32>)
+        -> Result<HashMap<i32, Vec<String>>, DbErr>;
+    /// 模型 ID -> 主图对象键
+    async fn find_primary_image_keys(
+        &self,
+        model_ids: Vec<i32>,
+    ) -> Result<HashMap<i32, String>, DbErr>;
+    /// 模型图片及其购买记录下制作日志图片（含缩略图）的对象键，用于删除模型后清理存储
+    async fn find_image_keys(&self, model_id: i32) -> Result<Vec<String>, DbErr>;
+    /// 在同一事务中插入模型及其标签（不存在的标签按 slug 自动创建）。
+    /// 设置了父模型时在写入事务中锁定祖先链并复核变体层级，并发修改导致成环或超出层级时返回 `DbErr::RecordNotUpdated`
+    async fn create(
+        &self,
+        model_data: model::ActiveModel,
+        tag_names: Option<Vec<String>>,
+    ) -> Result<model::Model, DbErr>;
     async fn update(&self, id: i32, model_data: model::ActiveModel) -> Result<model::Model, DbErr>;
-    async fn delete(&self, id: i32) -> Result<DeleteResult, DbErr>;
+    /// 在同一事务中先保存当前行的快照到 model_revisions，再执行更新；
+    /// 传入 `tag_names` 时在同一事务中替换模型标签。
+    /// 传入 `expected_updated_at` 且与当前行不一致，或修改父模型后成环、超出层级时返回 `DbErr::RecordNotUpdated`
+    async fn update_with_revision(
+        &self,
+        id: i32,
+        model_data: model::ActiveModel,
+        expected_updated_at: Option<chrono::NaiveDateTime>,
+        edited_by: Option<i32>,
+        change_reason: Option<&str>,
+        tag_names: Option<Vec<String>>,
+    ) -> Result<model::Model, DbErr>;
+    /// 发售日期不晚于 `today` 的预售模型
+    async fn find_released_pre_orders(
+        &self,
+        today: chrono::NaiveDate,
+    ) -> Result<Vec<model::Model>, DbErr>;
+    /// 创建与发售都早于 `cutoff`、此后没有新价格且尚未标记的现货模型
+    async fn find_stale_in_stock(
+        &self,
+        cutoff: chrono::NaiveDateTime,
+    ) -> Result<Vec<model::Model>, DbErr>;
+    /// 已标记疑似停产，但 `cutoff` 之后有新价格或已不是现货的模型
+    async fn find_refreshed_flagged(
+        &self,
+        cutoff: chrono::NaiveDateTime,
+    ) -> Result<Vec<model::Model>, DbErr>;
+    async fn find_revisions(&self, model_id: i32) -> Result<Vec<model_revision::Model>, DbErr>;
+    async fn find_revision(
+        &self,
+        model_id: i32,
+        revision: i32,
+    ) -> Result<Option<model_revision::Model>, DbErr>;
+    async fn delete(
+        &self,
+        id: i32,
+        expected_updated_at: Option<chrono::NaiveDateTime>,
+    ) -> Result<DeleteResult, DbErr>;
+    /// 在同一事务中将来源模型的价格记录、收藏、购买、变体、标签与图片转移到目标模型，然后删除来源模型；
+    /// 同一用户在两者上都有收藏/购买记录时逐列合并到目标模型的记录上（保留非空值、拼接备注），
+    /// 来源购买记录下的制作日志改挂到保留的记录。
+    /// 若目标模型是来源模型的直接变体，目标模型改挂到来源模型的父模型下。
+    /// 合并前目标模型的数据保存为一条修订，变更原因记录来源模型 ID
+    async fn merge_models(
+        &self,
+        target_id: i32,
+        source_id: i32,
+        expected_updated_at: Option<chrono::NaiveDateTime>,
+        edited_by: Option<i32>,
+    ) -> Result<(model::Model, MergeSummary), DbErr>;
     async fn toggle_favorite(
         &self,
         user_id: i32,
         model_id: i32,
         notes: Option<String>,
     ) -> Result<(), DbErr>;
+    /// 切换购买状态；购买记录下已有制作日志时不取消购买，避免日志随记录级联删除
     async fn toggle_purchase(
         &self,
         user_id: i32,
         model_id: i32,
         notes: Option<String>,
-    ) -> Result<(), DbErr>;
+    ) -> Result<PurchaseToggle, DbErr>;
     async fn get_favorites(
         &self,
         user_id: i32,
@@ -53,6 +207,46 @@ pub trait ModelRepositoryTrait: Send + Sync {
         page: u64,
         per_page: u64,
     ) -> Result<(Vec<model::Model>, u64), DbErr>;
+    /// 更新收藏的心愿单字段，未收藏时返回 `None`
+    async fn update_wishlist(
+        &self,
+        user_id: i32,
+        model_id: i32,
+        wishlist: user_model_favorite::ActiveModel,
+    ) -> Result<Option<user_model_favorite::Model>, DbErr>;
+    /// 更新购买记录的备注与预售日期，未购买时返回 `None`
+    async fn update_purchase(
+        &self,
+        user_id: i32,
+        model_id: i32,
+        purchase: user_model_purchase::ActiveModel,
+    ) -> Result<Option<user_model_purchase::Model>, DbErr>;
+    /// 用户的心愿单，按优先级从高到低
+    async fn find_wishlist(
+        &self,
+        user_id: i32,
+    ) -> Result<Vec<(user_model_favorite::Model, model::Model)>, DbErr>;
+    /// 模型 ID -> 最新一条价格记录
+    async fn find_latest_prices(
+        &self,
+        model_ids: Vec<i32>,
+    ) -> Result<HashMap<i32, price_history::Model>, DbErr>;
+    /// 用户已购买的全部模型
+    async fn find_purchased_models(&self, user_id: i32) -> Result<Vec<model::Model>, DbErr>;
+    /// 模型的价格记录，最新的在前
+    async fn find_prices(&self, model_id: i32) -> Result<Vec<price_history::Model>, DbErr>;
+    async fn add_price(
+        &self,
+        price: price_history::ActiveModel,
+    ) -> Result<price_history::Model, DbErr>;
+    async fn find_shop(&self, shop_id: i32) -> Result<Option<shop::Model>, DbErr>;
+    /// 按名称查找店铺，忽略大小写
+    async fn find_shop_by_name(&self, name: &str) -> Result<Option<shop::Model>, DbErr>;
+    /// 模型在各店铺的最新一条价格记录，未关联店铺的记录不计入
+    async fn find_latest_prices_by_shop(
+        &self,
+        model_id: i32,
+    ) -> Result<Vec<(price_history::Model, shop::Model)>, DbErr>;
 }
 
 #[derive(Clone)]
@@ -71,13 +265,14 @@ impl ModelRepositoryTrait for ModelRepository {
     async fn find_all(
         &self,
         _user_id: Option<i32>,
+        filter: &ModelFilter,
         page: u64,
         per_page: u64,
     ) -> Result<(Vec<model::Model>, u64), DbErr> {
         let page = page.max(1);
         let per_page = per_page.max(1);
 
-        let paginator = model::Entity::find()
+        let paginator = apply_model_filter(model::Entity::find(), filter)
             .order_by_asc(model::Column::Name)
             .paginate(&self.db, per_page);
 
@@ -112,8 +307,214 @@ impl ModelRepositoryTrait for ModelRepository {
         Ok(variants)
     }
 
-    async fn create(&self, model_data: model::ActiveModel) -> Result<model::Model, DbErr> {
-        model_data.insert(&self.db).await
+    async fn find_family(&self, model_id: i32) -> Result<Vec<FamilyMember>, DbErr> {
+        FamilyMember::find_by_statement(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            FAMILY_SQL,
+            [model_id.into()],
+        ))
+        .all(&self.db)
+        .await
+    }
+
+    async fn find_by_ids(&self, ids: Vec<i32>) -> Result<Vec<model::Model>, DbErr> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        model::Entity::find()
+            .filter(model::Column::Id.is_in(ids))
+            .order_by_asc(model::Column::Name)
+            .all(&self.db)
+            .await
+    }
+
+    async fn find_by_series(
+        &self,
+        series_id: i32,
+        page: u64,
+        per_page: u64,
+    ) -> Result<(Vec<model::Model>, u64), DbErr> {
+        let page = page.max(1);
+        let per_page = per_page.max(1);
+
+        let paginator = model::Entity::find()
+            .filter(model::Column::SeriesId.eq(series_id))
+            .order_by_asc(model::Column::ReleaseDate)
+            .order_by_asc(model::Column::Name)
+            .paginate(&self.db, per_page);
+
+        let total = paginator.num_items().await?;
+        let models = paginator.fetch_page(page - 1).await?;
+
+        Ok((models, total))
+    }
+
+    async fn series_exists(&self, series_id: i32) -> Result<bool, DbErr> {
+        let count = series::Entity::find_by_id(series_id)
+            .count(&self.db)
+            .await?;
+        Ok(count > 0)
+    }
+
+    async fn find_series_names(&self, series_ids: Vec<i32>) -> Result<HashMap<i32, String>, DbErr> {
+        if series_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let names: Vec<(i32, String)> = series::Entity::find()
+            .select_only()
+            .column(series::Column::Id)
+            .column(series::Column::Name)
+            .filter(series::Column::Id.is_in(series_ids))
+            .into_tuple()
+            .all(&self.db)
+            .await?;
+
+        Ok(names.into_iter().collect())
+    }
+
+    async fn find_tag_names(
+        &self,
+        model_ids: Vec<i32>,
+    ) -> Result<HashMap<i32, Vec<String>>, DbErr> {
+        if model_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let rows: Vec<(i32, String)> = model_tag::Entity::find()
+            .select_only()
+            .column(model_tag::Column::ModelId)
+            .column(tag::Column::Name)
+            .join(JoinType::InnerJoin, model_tag::Relation::Tag.def())
+            .filter(model_tag::Column::ModelId.is_in(model_ids))
+            .order_by_asc(tag::Column::Name)
+            .into_tuple()
+            .all(&self.db)
+            .await?;
+
+        let mut tags: HashMap<i32, Vec<String>> = HashMap::new();
+        for (model_id, name) in rows {
+            tags.entry(model_id).or_default().push(name);
+        }
+        Ok(tags)
+    }
+
+    async fn find_primary_image_keys(
+        &self,
+        model_ids: Vec<i32>,
+    ) -> Result<HashMap<i32, String>, DbErr> {
+        if model_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let rows: Vec<(i32, String)> = model_image::Entity::find()
+            .select_only()
+            .column(model_image::Column::ModelId)
+            .column(model_image::Column::StorageKey)
+            .filter(model_image::Column::ModelId.is_in(model_ids))
+            .filter(model_image::Column::IsPrimary.eq(true))
+            .into_tuple()
+            .all(&self.db)
+            .await?;
+        Ok(rows.into_iter().collect())
+    }
+
+    async fn find_image_keys(&self, model_id: i32) -> Result<Vec<String>, DbErr> {
+        let rows: Vec<(String, String)> = model_image::Entity::find()
+            .select_only()
+            .column(model_image::Column::StorageKey)
+            .column(model_image::Column::ThumbnailKey)
+            .filter(model_image::Column::ModelId.eq(model_id))
+            .into_tuple()
+            .all(&self.db)
+            .await?;
+        let build_log_rows: Vec<(String, String)> = build_log_image::Entity::find()
+            .select_only()
+            .column(build_log_image::Column::StorageKey)
+            .column(build_log_image::Column::ThumbnailKey)
+            .join(JoinType::InnerJoin, build_log_image::Relation::Entry.def())
+            .join(
+                JoinType::InnerJoin,
+                build_log_entry::Relation::Purchase.def(),
+            )
+            .filter(user_model_purchase::Column::ModelId.eq(model_id))
+            .into_tuple()
+            .all(&self.db)
+            .await?;
+        Ok(rows
+            .into_iter()
+            .chain(build_log_rows)
+            .flat_map(|(original, thumbnail)| [original, thumbnail])
+            .collect())
+    }
+
+    async fn find_by_manufacturer_and_category(
+        &self,
+        manufacturer_id: i32,
+        category: Option<Grade>,
+    ) -> Result<Vec<model::Model>, DbErr> {
+        let category_condition = match category {
+            Some(category) => model::Column::Category.eq(category),
+            None => model::Column::Category.is_null(),
+        };
+
+        model::Entity::find()
+            .filter(model::Column::ManufacturerId.eq(manufacturer_id))
+            .filter(category_condition)
+            .order_by_asc(model::Column::Id)
+            .all(&self.db)
+            .await
+    }
+
+    async fn find_by_manufacturer_and_name(
+        &self,
+        manufacturer_id: i32,
+        name: &str,
+    ) -> Result<Option<model::Model>, DbErr> {
+        model::Entity::find()
+            .filter(model::Column::ManufacturerId.eq(manufacturer_id))
+            .filter(
+                Expr::expr(Func::lower(Expr::col(model::Column::Name)))
+                    .eq(name.trim().to_lowercase()),
+            )
+            .order_by_asc(model::Column::Id)
+            .one(&self.db)
+            .await
+    }
+
+    async fn find_all_for_duplicate_check(&self) -> Result<Vec<model::Model>, DbErr> {
+        model::Entity::find()
+            .order_by_asc(model::Column::ManufacturerId)
+            .order_by_asc(model::Column::Category)
+            .order_by_asc(model::Column::Id)
+            .all(&self.db)
+            .await
+    }
+
+    async fn manufacturer_exists(&self, manufacturer_id: i32) -> Result<bool, DbErr> {
+        let count = manufacturer::Entity::find_by_id(manufacturer_id)
+            .count(&self.db)
+            .await?;
+        Ok(count > 0)
+    }
+
+    async fn create(
+        &self,
+        model_data: model::ActiveModel,
+        tag_names: Option<Vec<String>>,
+    ) -> Result<model::Model, DbErr> {
+        let txn = self.db.begin().await?;
+        if let Set(Some(parent_id)) = model_data.parent_id {
+            if !variant_parent_allowed(&txn, None, parent_id).await? {
+                return Err(DbErr::RecordNotUpdated);
+            }
+        }
+        let created = model_data.insert(&txn).await?;
+        if let Some(tag_names) = tag_names {
+            replace_model_tags(&txn, created.id, tag_names).await?;
+        }
+        txn.commit().await?;
+        Ok(created)
     }
 
     async fn update(
@@ -125,8 +526,329 @@ impl ModelRepositoryTrait for ModelRepository {
         model_data.update(&self.db).await
     }
 
-    async fn delete(&self, id: i32) -> Result<DeleteResult, DbErr> {
-        model::Entity::delete_by_id(id).exec(&self.db).await
+    async fn update_with_revision(
+        &self,
+        id: i32,
+        mut model_data: model::ActiveModel,
+        expected_updated_at: Option<chrono::NaiveDateTime>,
+        edited_by: Option<i32>,
+        change_reason: Option<&str>,
+        tag_names: Option<Vec<String>>,
+    ) -> Result<model::Model, DbErr> {
+        let txn = self.db.begin().await?;
+
+        // 锁定当前行，避免并发更新时修订号重复
+        let existing = model::Entity::find_by_id(id)
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+            .ok_or_else(|| DbErr::RecordNotFound(format!("model {}", id)))?;
+
+        if expected_updated_at.is_some_and(|expected| expected != existing.updated_at) {
+            return Err(DbErr::RecordNotUpdated);
+        }
+
+        if let Set(Some(parent_id)) = model_data.parent_id {
+            if existing.parent_id != Some(parent_id)
+                && !variant_parent_allowed(&txn, Some(id), parent_id).await?
+            {
+                return Err(DbErr::RecordNotUpdated);
+            }
+        }
+
+        insert_revision(&txn, &existing, edited_by, change_reason).await?;
+
+        model_data.id = Set(id);
+        let updated = model_data.update(&txn).await?;
+        if let Some(tag_names) = tag_names {
+            replace_model_tags(&txn, id, tag_names).await?;
+        }
+
+        txn.commit().await?;
+        Ok(updated)
+    }
+
+    async fn find_released_pre_orders(
+        &self,
+        today: chrono::NaiveDate,
+    ) -> Result<Vec<model::Model>, DbErr> {
+        model::Entity::find()
+            .filter(model::Column::Status.eq(ModelStatus::PreOrder))
+            .filter(model::Column::ReleaseDate.lte(today))
+            .order_by_asc(model::Column::Id)
+            .all(&self.db)
+            .await
+    }
+
+    async fn find_stale_in_stock(
+        &self,
+        cutoff: chrono::NaiveDateTime,
+    ) -> Result<Vec<model::Model>, DbErr> {
+        model::Entity::find()
+            .filter(model::Column::Status.eq(ModelStatus::InStock))
+            .filter(model::Column::PossiblyDiscontinuedAt.is_null())
+            .filter(model::Column::CreatedAt.lt(cutoff))
+            // 刚发售的模型从发售日起计算
+            .filter(
+                Condition::any()
+                    .add(model::Column::ReleaseDate.is_null())
+                    .add(model::Column::ReleaseDate.lt(cutoff.date())),
+            )
+            .filter(model::Column::Id.not_in_subquery(priced_since(cutoff)))
+            .order_by_asc(model::Column::Id)
+            .all(&self.db)
+            .await
+    }
+
+    async fn find_refreshed_flagged(
+        &self,
+        cutoff: chrono::NaiveDateTime,
+    ) -> Result<Vec<model::Model>, DbErr> {
+        model::Entity::find()
+            .filter(model::Column::PossiblyDiscontinuedAt.is_not_null())
+            .filter(
+                Condition::any()
+                    .add(model::Column::Status.ne(ModelStatus::InStock))
+                    .add(model::Column::Id.in_subquery(priced_since(cutoff))),
+            )
+            .order_by_asc(model::Column::Id)
+            .all(&self.db)
+            .await
+    }
+
+    async fn find_revisions(&self, model_id: i32) -> Result<Vec<model_revision::Model>, DbErr> {
+        model_revision::Entity::find()
+            .filter(model_revision::Column::ModelId.eq(model_id))
+            .order_by_desc(model_revision::Column::Revision)
+            .all(&self.db)
+            .await
+    }
+
+    async fn find_revision(
+        &self,
+        model_id: i32,
+        revision: i32,
+    ) -> Result<Option<model_revision::Model>, DbErr> {
+        model_revision::Entity::find()
+            .filter(model_revision::Column::ModelId.eq(model_id))
+            .filter(model_revision::Column::Revision.eq(revision))
+            .one(&self.db)
+            .await
+    }
+
+    async fn delete(
+        &self,
+        id: i32,
+        expected_updated_at: Option<chrono::NaiveDateTime>,
+    ) -> Result<DeleteResult, DbErr> {
+        let mut query = model::Entity::delete_many().filter(model::Column::Id.eq(id));
+        if let Some(expected) = expected_updated_at {
+            query = query.filter(model::Column::UpdatedAt.eq(expected));
+        }
+        query.exec(&self.db).await
+    }
+
+    async fn merge_models(
+        &self,
+        target_id: i32,
+        source_id: i32,
+        expected_updated_at: Option<chrono::NaiveDateTime>,
+        edited_by: Option<i32>,
+    ) -> Result<(model::Model, MergeSummary), DbErr> {
+        let txn = self.db.begin().await?;
+        let mut summary = MergeSummary::default();
+
+        let target = model::Entity::find_by_id(target_id)
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+            .ok_or_else(|| DbErr::RecordNotFound(format!("model {}", target_id)))?;
+        if expected_updated_at.is_some_and(|expected| expected != target.updated_at) {
+            return Err(DbErr::RecordNotUpdated);
+        }
+        let source = model::Entity::find_by_id(source_id)
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+            .ok_or_else(|| DbErr::RecordNotFound(format!("model {}", source_id)))?;
+        // 来源的变体改挂到目标下；目标是来源的直接变体时目标接替来源的位置，层级不会加深
+        if target.parent_id != Some(source_id)
+            && !merge_hierarchy_allowed(&txn, &target, source_id).await?
+        {
+            return Err(DbErr::RecordNotUpdated);
+        }
+
+        summary.price_history = price_history::Entity::update_many()
+            .col_expr(price_history::Column::ModelId, Expr::value(target_id))
+            .filter(price_history::Column::ModelId.eq(source_id))
+            .exec(&txn)
+            .await?
+            .rows_affected;
+
+        let target_urls: Vec<String> = price_watch::Entity::find()
+            .select_only()
+            .column(price_watch::Column::Url)
+            .filter(price_watch::Column::ModelId.eq(target_id))
+            .into_tuple()
+            .all(&txn)
+            .await?;
+        summary.price_watches = price_watch::Entity::update_many()
+            .col_expr(price_watch::Column::ModelId, Expr::value(target_id))
+            .filter(price_watch::Column::ModelId.eq(source_id))
+            .filter(price_watch::Column::Url.is_not_in(target_urls))
+            .exec(&txn)
+            .await?
+            .rows_affected;
+
+        let favorites = user_model_favorite::Entity::find()
+            .filter(user_model_favorite::Column::ModelId.eq(source_id))
+            .all(&txn)
+            .await?;
+        for favorite in favorites {
+            let existing = user_model_favorite::Entity::find()
+                .filter(user_model_favorite::Column::UserId.eq(favorite.user_id))
+                .filter(user_model_favorite::Column::ModelId.eq(target_id))
+                .one(&txn)
+                .await?;
+            match existing {
+                Some(existing) => {
+                    // 目标价与币种成对取值，目标记录没有目标价时才使用来源记录的
+                    let (target_price, target_currency) = if existing.target_price.is_some() {
+                        (existing.target_price, existing.target_currency.clone())
+                    } else {
+                        (favorite.target_price, favorite.target_currency.clone())
+                    };
+                    let merged = user_model_favorite::ActiveModel {
+                        id: Set(existing.id),
+                        notes: Set(merge_notes(existing.notes.clone(), favorite.notes.clone())),
+                        target_price: Set(target_price),
+                        target_currency: Set(target_currency),
+                        priority: Set(existing.priority.max(favorite.priority)),
+                        created_at: Set(existing.created_at.min(favorite.created_at)),
+                        ..Default::default()
+                    };
+                    merged.update(&txn).await?;
+                    favorite.delete(&txn).await?;
+                }
+                None => {
+                    let mut active: user_model_favorite::ActiveModel = favorite.into();
+                    active.model_id = Set(target_id);
+                    active.update(&txn).await?;
+                }
+            }
+            summary.favorites += 1;
+        }
+
+        let purchases = user_model_purchase::Entity::find()
+            .filter(user_model_purchase::Column::ModelId.eq(source_id))
+            .all(&txn)
+            .await?;
+        for purchase in purchases {
+            let existing = user_model_purchase::Entity::find()
+                .filter(user_model_purchase::Column::UserId.eq(purchase.user_id))
+                .filter(user_model_purchase::Column::ModelId.eq(target_id))
+                .one(&txn)
+                .await?;
+            match existing {
+                Some(existing) => {
+                    // 制作日志随购买记录级联删除，删除来源记录前先改挂到保留的记录
+                    summary.build_log_entries += build_log_entry::Entity::update_many()
+                        .col_expr(
+                            build_log_entry::Column::PurchaseId,
+                            Expr::value(existing.id),
+                        )
+                        .filter(build_log_entry::Column::PurchaseId.eq(purchase.id))
+                        .exec(&txn)
+                        .await?
+                        .rows_affected;
+                    let merged = user_model_purchase::ActiveModel {
+                        id: Set(existing.id),
+                        notes: Set(merge_notes(existing.notes.clone(), purchase.notes.clone())),
+                        payment_due_date: Set(existing
+                            .payment_due_date
+                            .or(purchase.payment_due_date)),
+                        ship_date: Set(existing.ship_date.or(purchase.ship_date)),
+                        created_at: Set(existing.created_at.min(purchase.created_at)),
+                        ..Default::default()
+                    };
+                    merged.update(&txn).await?;
+                    purchase.delete(&txn).await?;
+                }
+                None => {
+                    let mut active: user_model_purchase::ActiveModel = purchase.into();
+                    active.model_id = Set(target_id);
+                    active.update(&txn).await?;
+                }
+            }
+            summary.purchases += 1;
+        }
+
+        summary.variants = model::Entity::update_many()
+            .col_expr(model::Column::ParentId, Expr::value(target_id))
+            .filter(model::Column::ParentId.eq(source_id))
+            .filter(model::Column::Id.ne(target_id))
+            .exec(&txn)
+            .await?
+            .rows_affected;
+
+        // 目标模型已有主图时，转移过来的图片都作为普通图片
+        let target_has_primary = model_image::Entity::find()
+            .filter(model_image::Column::ModelId.eq(target_id))
+            .filter(model_image::Column::IsPrimary.eq(true))
+            .count(&txn)
+            .await?
+            > 0;
+        let mut move_images = model_image::Entity::update_many()
+            .col_expr(model_image::Column::ModelId, Expr::value(target_id))
+            .filter(model_image::Column::ModelId.eq(source_id));
+        if target_has_primary {
+            move_images = move_images.col_expr(model_image::Column::IsPrimary, Expr::value(false));
+        }
+        summary.images = move_images.exec(&txn).await?.rows_affected;
+
+        let target_tag_ids: Vec<i32> = model_tag::Entity::find()
+            .select_only()
+            .column(model_tag::Column::TagId)
+            .filter(model_tag::Column::ModelId.eq(target_id))
+            .into_tuple()
+            .all(&txn)
+            .await?;
+        let links: Vec<model_tag::ActiveModel> = model_tag::Entity::find()
+            .filter(model_tag::Column::ModelId.eq(source_id))
+            .filter(model_tag::Column::TagId.is_not_in(target_tag_ids))
+            .all(&txn)
+            .await?
+            .into_iter()
+            .map(|link| model_tag::ActiveModel {
+                model_id: Set(target_id),
+                tag_id: Set(link.tag_id),
+            })
+            .collect();
+        summary.tags = links.len() as u64;
+        if !links.is_empty() {
+            model_tag::Entity::insert_many(links).exec(&txn).await?;
+        }
+
+        let reparent = target.parent_id == Some(source_id);
+        insert_revision(
+            &txn,
+            &target,
+            edited_by,
+            Some(&format!("merged_from:{}", source_id)),
+        )
+        .await?;
+        let mut target_data: model::ActiveModel = target.into();
+        if reparent {
+            target_data.parent_id = Set(source.parent_id);
+        }
+        target_data.updated_at = Set(chrono::Utc::now().naive_utc());
+        let merged = target_data.update(&txn).await?;
+
+        // 价格记录等已转移，剩余的修订历史与标签关联随外键级联删除
+        source.delete(&txn).await?;
+
+        txn.commit().await?;
+        Ok((merged, summary))
     }
 
     async fn toggle_favorite(
@@ -141,8 +863,8 @@ impl ModelRepositoryTrait for ModelRepository {
             .one(&self.db)
             .await?;
 
-        if existing.is_some() {
-            user_model_favorite::Entity::delete_by_id(existing.unwrap().id)
+        if let Some(existing) = existing {
+            user_model_favorite::Entity::delete_by_id(existing.id)
                 .exec(&self.db)
                 .await?;
         } else {
@@ -164,17 +886,27 @@ impl ModelRepositoryTrait for ModelRepository {
         user_id: i32,
         model_id: i32,
         notes: Option<String>,
-    ) -> Result<(), DbErr> {
+    ) -> Result<PurchaseToggle, DbErr> {
+        let txn = self.db.begin().await?;
+        // 锁定购买记录：新增制作日志需对其加外键锁，检查与删除之间不会插入新日志
         let existing = user_model_purchase::Entity::find()
             .filter(user_model_purchase::Column::UserId.eq(user_id))
             .filter(user_model_purchase::Column::ModelId.eq(model_id))
-            .one(&self.db)
+            .lock_exclusive()
+            .one(&txn)
             .await?;
 
-        if existing.is_some() {
-            user_model_purchase::Entity::delete_by_id(existing.unwrap().id)
-                .exec(&self.db)
+        let toggled = if let Some(existing) = existing {
+            let entries = build_log_entry::Entity::find()
+                .filter(build_log_entry::Column::PurchaseId.eq(existing.id))
+                .count(&txn)
                 .await?;
+            if entries > 0 {
+                PurchaseToggle::HasBuildLog(entries)
+            } else {
+                existing.delete(&txn).await?;
+                PurchaseToggle::Removed
+            }
         } else {
             let purchase = user_model_purchase::ActiveModel {
                 user_id: Set(user_id),
@@ -183,10 +915,12 @@ impl ModelRepositoryTrait for ModelRepository {
                 created_at: Set(chrono::Utc::now().naive_utc()),
                 ..Default::default()
             };
-            purchase.insert(&self.db).await?;
-        }
+            purchase.insert(&txn).await?;
+            PurchaseToggle::Added
+        };
 
-        Ok(())
+        txn.commit().await?;
+        Ok(toggled)
     }
 
     async fn get_favorites(
@@ -210,6 +944,154 @@ impl ModelRepositoryTrait for ModelRepository {
         Ok((models, total))
     }
 
+    async fn update_wishlist(
+        &self,
+        user_id: i32,
+        model_id: i32,
+        mut wishlist: user_model_favorite::ActiveModel,
+    ) -> Result<Option<user_model_favorite::Model>, DbErr> {
+        let Some(existing) = user_model_favorite::Entity::find()
+            .filter(user_model_favorite::Column::UserId.eq(user_id))
+            .filter(user_model_favorite::Column::ModelId.eq(model_id))
+            .one(&self.db)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        wishlist.id = Set(existing.id);
+        Ok(Some(wishlist.update(&self.db).await?))
+    }
+
+    async fn update_purchase(
+        &self,
+        user_id: i32,
+        model_id: i32,
+        mut purchase: user_model_purchase::ActiveModel,
+    ) -> Result<Option<user_model_purchase::Model>, DbErr> {
+        let Some(existing) = user_model_purchase::Entity::find()
+            .filter(user_model_purchase::Column::UserId.eq(user_id))
+            .filter(user_model_purchase::Column::ModelId.eq(model_id))
+            .one(&self.db)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        purchase.id = Set(existing.id);
+        Ok(Some(purchase.update(&self.db).await?))
+    }
+
+    async fn find_wishlist(
+        &self,
+        user_id: i32,
+    ) -> Result<Vec<(user_model_favorite::Model, model::Model)>, DbErr> {
+        let rows = user_model_favorite::Entity::find()
+            .find_also_related(model::Entity)
+            .filter(user_model_favorite::Column::UserId.eq(user_id))
+            .order_by_desc(user_model_favorite::Column::Priority)
+            .order_by_asc(model::Column::Name)
+            .all(&self.db)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(favorite, model)| model.map(|m| (favorite, m)))
+            .collect())
+    }
+
+    async fn find_latest_prices(
+        &self,
+        model_ids: Vec<i32>,
+    ) -> Result<HashMap<i32, price_history::Model>, DbErr> {
+        if model_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let prices = price_history::Entity::find()
+            .distinct_on([price_history::Column::ModelId])
+            .filter(price_history::Column::ModelId.is_in(model_ids))
+            .order_by_asc(price_history::Column::ModelId)
+            .order_by_desc(price_history::Column::RecordedAt)
+            .order_by_desc(price_history::Column::Id)
+            .all(&self.db)
+            .await?;
+        Ok(prices.into_iter().map(|p| (p.model_id, p)).collect())
+    }
+
+    async fn find_purchased_models(&self, user_id: i32) -> Result<Vec<model::Model>, DbErr> {
+        model::Entity::find()
+            .join(JoinType::InnerJoin, model::Relation::Purchase.def())
+            .filter(user_model_purchase::Column::UserId.eq(user_id))
+            .order_by_asc(model::Column::Name)
+            .all(&self.db)
+            .await
+    }
+
+    async fn find_prices(&self, model_id: i32) -> Result<Vec<price_history::Model>, DbErr> {
+        price_history::Entity::find()
+            .filter(price_history::Column::ModelId.eq(model_id))
+            .order_by_desc(price_history::Column::RecordedAt)
+            .order_by_desc(price_history::Column::Id)
+            .all(&self.db)
+            .await
+    }
+
+    async fn add_price(
+        &self,
+        price: price_history::ActiveModel,
+    ) -> Result<price_history::Model, DbErr> {
+        price.insert(&self.db).await
+    }
+
+    async fn find_shop(&self, shop_id: i32) -> Result<Option<shop::Model>, DbErr> {
+        shop::Entity::find_by_id(shop_id).one(&self.db).await
+    }
+
+    async fn find_shop_by_name(&self, name: &str) -> Result<Option<shop::Model>, DbErr> {
+        shop::Entity::find()
+            .filter(
+                Expr::expr(Func::lower(Expr::col(shop::Column::Name)))
+                    .eq(name.trim().to_lowercase()),
+            )
+            .one(&self.db)
+            .await
+    }
+
+    async fn find_latest_prices_by_shop(
+        &self,
+        model_id: i32,
+    ) -> Result<Vec<(price_history::Model, shop::Model)>, DbErr> {
+        let prices = price_history::Entity::find()
+            .distinct_on([price_history::Column::ShopId])
+            .filter(price_history::Column::ModelId.eq(model_id))
+            .filter(price_history::Column::ShopId.is_not_null())
+            .order_by_asc(price_history::Column::ShopId)
+            .order_by_desc(price_history::Column::RecordedAt)
+            .order_by_desc(price_history::Column::Id)
+            .all(&self.db)
+            .await?;
+        let shop_ids: Vec<i32> = prices.iter().filter_map(|p| p.shop_id).collect();
+        if shop_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut shops: HashMap<i32, shop::Model> = shop::Entity::find()
+            .filter(shop::Column::Id.is_in(shop_ids))
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(|s| (s.id, s))
+            .collect();
+        Ok(prices
+            .into_iter()
+            .filter_map(|p| {
+                let shop = shops.remove(&p.shop_id?)?;
+                Some((p, shop))
+            })
+            .collect())
+    }
+
     async fn get_purchases(
         &self,
         user_id: i32,
@@ -231,3 +1113,223 @@ impl ModelRepositoryTrait for ModelRepository {
         Ok((models, total))
     }
 }
+
+/// 将列表筛选条件应用到查询上，模型列表与导出共用
+pub(crate) fn apply_model_filter(
+    mut query: Select<model::Entity>,
+    filter: &ModelFilter,
+) -> Select<model::Entity> {
+    if !filter.tags.is_empty() {
+        query = query.filter(model::Column::Id.in_subquery(tag_filter_subquery(filter)));
+    }
+    query
+}
+
+/// 按标签筛选的子查询：any 模式命中任一标签即可，all 模式需命中全部标签
+fn tag_filter_subquery(filter: &ModelFilter) -> SelectStatement {
+    let mut query = Query::select();
+    query
+        .column((model_tag::Entity, model_tag::Column::ModelId))
+        .from(model_tag::Entity)
+        .inner_join(
+            tag::Entity,
+            Expr::col((tag::Entity, tag::Column::Id))
+                .equals((model_tag::Entity, model_tag::Column::TagId)),
+        )
+        .and_where(Expr::col((tag::Entity, tag::Column::Slug)).is_in(filter.tags.clone()));
+
+    if filter.tag_mode == TagMatchMode::All {
+        let mut slugs = filter.tags.clone();
+        slugs.sort();
+        slugs.dedup();
+        query
+            .group_by_col((model_tag::Entity, model_tag::Column::ModelId))
+            .and_having(Expr::cust_with_values(
+                "COUNT(DISTINCT model_tags.tag_id) = ?",
+                [slugs.len() as i64],
+            ));
+    }
+
+    query.to_owned()
+}
+
+/// 在写入事务中锁定父模型及其祖先链，复核挂到该父模型下后不成环、不超过层级上限。
+/// 修改父子关系的写入都会先锁定新的祖先链，因此并发修改同一条链时会串行执行
+async fn variant_parent_allowed(
+    txn: &DatabaseTransaction,
+    model_id: Option<i32>,
+    parent_id: i32,
+) -> Result<bool, DbErr> {
+    let Some(chain_len) = lock_ancestor_chain(txn, Some(parent_id), model_id).await? else {
+        return Ok(false);
+    };
+    let subtree_height = match model_id {
+        Some(id) => variant_subtree_height(txn, id).await?,
+        None => 0,
+    };
+    Ok(chain_len + subtree_height <= model::MAX_VARIANT_DEPTH)
+}
+
+/// 合并时复核：来源不能是目标的祖先，且来源的变体挂到目标下后不超过层级上限
+async fn merge_hierarchy_allowed(
+    txn: &DatabaseTransaction,
+    target: &model::Model,
+    source_id: i32,
+) -> Result<bool, DbErr> {
+    let Some(target_depth) = lock_ancestor_chain(txn, target.parent_id, Some(source_id)).await?
+    else {
+        return Ok(false);
+    };
+    Ok(target_depth + variant_subtree_height(txn, source_id).await? <= model::MAX_VARIANT_DEPTH)
+}
+
+/// 从 `start` 沿 parent_id 向上逐行加锁，返回链上的模型数；
+/// 遇到 `forbidden`（成环）或链长超过层级上限时返回 `None`
+async fn lock_ancestor_chain(
+    txn: &DatabaseTransaction,
+    start: Option<i32>,
+    forbidden: Option<i32>,
+) -> Result<Option<usize>, DbErr> {
+    let mut chain_len = 0;
+    let mut next_ancestor = start;
+    while let Some(ancestor_id) = next_ancestor {
+        if forbidden == Some(ancestor_id) {
+            return Ok(None);
+        }
+        chain_len += 1;
+        if chain_len > model::MAX_VARIANT_DEPTH {
+            return Ok(None);
+        }
+        next_ancestor = model::Entity::find_by_id(ancestor_id)
+            .lock_exclusive()
+            .one(txn)
+            .await?
+            .and_then(|m| m.parent_id);
+    }
+    Ok(Some(chain_len))
+}
+
+/// 模型下方变体树的高度，超过层级上限后不再继续向下查找
+async fn variant_subtree_height(txn: &DatabaseTransaction, model_id: i32) -> Result<usize, DbErr> {
+    let mut height = 0;
+    let mut level = vec![model_id];
+
+    while height <= model::MAX_VARIANT_DEPTH {
+        let next_level: Vec<i32> = model::Entity::find()
+            .select_only()
+            .column(model::Column::Id)
+            .filter(model::Column::ParentId.is_in(level))
+            .into_tuple()
+            .all(txn)
+            .await?;
+        if next_level.is_empty() {
+            break;
+        }
+        height += 1;
+        level = next_level;
+    }
+    Ok(height)
+}
+
+/// 按名称替换模型的标签，不存在的标签按 slug 创建；并发创建同名标签时以先写入的为准
+async fn replace_model_tags(
+    txn: &DatabaseTransaction,
+    model_id: i32,
+    tag_names: Vec<String>,
+) -> Result<(), DbErr> {
+    let mut tag_ids = Vec::new();
+    for name in tag_names {
+        let name = name.trim().to_string();
+        let slug = tag::slugify(&name);
+        if slug.is_empty() {
+            continue;
+        }
+
+        let new_tag = tag::ActiveModel {
+            name: Set(name),
+            slug: Set(slug.clone()),
+            created_at: Set(chrono::Utc::now().naive_utc()),
+            ..Default::default()
+        };
+        tag::Entity::insert(new_tag)
+            .on_conflict(
+                OnConflict::column(tag::Column::Slug)
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .exec_without_returning(txn)
+            .await?;
+        let tag_id = tag::Entity::find()
+            .filter(tag::Column::Slug.eq(slug.as_str()))
+            .one(txn)
+            .await?
+            .ok_or_else(|| DbErr::RecordNotFound(format!("tag {}", slug)))?
+            .id;
+        if !tag_ids.contains(&tag_id) {
+            tag_ids.push(tag_id);
+        }
+    }
+
+    model_tag::Entity::delete_many()
+        .filter(model_tag::Column::ModelId.eq(model_id))
+        .exec(txn)
+        .await?;
+
+    if !tag_ids.is_empty() {
+        let links = tag_ids.into_iter().map(|tag_id| model_tag::ActiveModel {
+            model_id: Set(model_id),
+            tag_id: Set(tag_id),
+        });
+        model_tag::Entity::insert_many(links).exec(txn).await?;
+    }
+    Ok(())
+}
+
+/// 保存模型当前行的快照作为下一个修订号；调用方需已在同一事务中锁定该行
+async fn insert_revision(
+    txn: &DatabaseTransaction,
+    existing: &model::Model,
+    edited_by: Option<i32>,
+    change_reason: Option<&str>,
+) -> Result<(), DbErr> {
+    let last_revision = model_revision::Entity::find()
+        .filter(model_revision::Column::ModelId.eq(existing.id))
+        .order_by_desc(model_revision::Column::Revision)
+        .one(txn)
+        .await?
+        .map(|r| r.revision)
+        .unwrap_or(0);
+
+    let snapshot = serde_json::to_value(existing).map_err(|e| DbErr::Custom(e.to_string()))?;
+
+    let revision = model_revision::ActiveModel {
+        model_id: Set(existing.id),
+        revision: Set(last_revision + 1),
+        snapshot: Set(snapshot),
+        edited_by: Set(edited_by),
+        change_reason: Set(change_reason.map(str::to_string)),
+        created_at: Set(chrono::Utc::now().naive_utc()),
+        ..Default::default()
+    };
+    revision.insert(txn).await?;
+    Ok(())
+}
+
+/// 合并两条备注：一方为空时取另一方，内容相同时只保留一份，否则按目标、来源的顺序换行拼接
+fn merge_notes(target: Option<String>, source: Option<String>) -> Option<String> {
+    let non_empty = |n: Option<String>| n.filter(|n| !n.trim().is_empty());
+    match (non_empty(target), non_empty(source)) {
+        (Some(t), Some(s)) if t.trim() == s.trim() => Some(t),
+        (Some(t), Some(s)) => Some(format!("{}\n{}", t, s)),
+        (t, s) => t.or(s),
+    }
+}
+
+/// `cutoff` 之后记录过价格的模型 ID
+fn priced_since(cutoff: chrono::NaiveDateTime) -> SelectStatement {
+    Query::select()
+        .column(price_history::Column::ModelId)
+        .from(price_history::Entity)
+        .and_where(price_history::Column::RecordedAt.gte(cutoff))
+        .to_owned()
+}
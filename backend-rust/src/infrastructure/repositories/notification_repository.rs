@@ -0,0 +1,377 @@
+#![allow(dead_code)]
+
+use crate::domain::entities::enums::NotificationKind;
+use crate::domain::entities::enums::{DeliveryChannel, DeliveryStatus};
+use crate::domain::entities::{
+    model, notification, notification_delivery, notification_preference, user_model_favorite,
+};
+use async_trait::async_trait;
+use sea_orm::sea_query::Expr;
+use sea_orm::*;
+
+#[async_trait]
+pub trait NotificationRepositoryTrait: Send + Sync {
+    /// 按时间倒序分页
+    async fn find_by_user(
+        &self,
+        user_id: i32,
+        unread_only: bool,
+        page: u64,
+        per_page: u64,
+    ) -> Result<(Vec<notification::Model>, u64), DbErr>;
+    async fn count_unread(&self, user_id: i32) -> Result<u64, DbErr>;
+    /// 将用户的通知标记为已读，`ids` 为空表示全部，返回实际更新的条数
+    async fn acknowledge(&self, user_id: i32, ids: Option<Vec<i32>>) -> Result<u64, DbErr>;
+
+    async fn find_preferences(
+        &self,
+        user_id: i32,
+    ) -> Result<Option<notification_preference::Model>, DbErr>;
+    async fn save_preferences(
+        &self,
+        preferences: notification_preference::Model,
+    ) -> Result<notification_preference::Model, DbErr>;
+
+    /// 尚未拆分为投递记录的通知，按 id 升序
+    async fn find_undispatched(&self, limit: u64) -> Result<Vec<notification::Model>, DbErr>;
+    /// 为通知创建各渠道的投递记录并标记为已分发
+    async fn schedule_deliveries(
+        &self,
+        notification_id: i32,
+        channels: Vec<DeliveryChannel>,
+    ) -> Result<(), DbErr>;
+    /// 领取到期的待投递记录，并将其下次尝试时间推迟 `lease_secs` 秒，
+    /// 避免多个实例或进程崩溃后重复投递
+    async fn claim_due_deliveries(
+        &self,
+        limit: u64,
+        lease_secs: i64,
+    ) -> Result<Vec<(notification_delivery::Model, notification::Model)>, DbErr>;
+    async fn mark_delivered(&self, delivery_id: i32) -> Result<(), DbErr>;
+    /// 记录一次失败，`next_attempt_at` 为空表示不再重试
+    async fn mark_failed(
+        &self,
+        delivery_id: i32,
+        error: String,
+        next_attempt_at: Option<chrono::NaiveDateTime>,
+    ) -> Result<(), DbErr>;
+
+    /// 模型上设置了目标价的心愿单
+    async fn find_wishlist_targets(&self, model_id: i32) -> Result<Vec<WishlistTarget>, DbErr>;
+    /// 模型上尚未确认的降价提醒
+    async fn find_unread_price_drops(
+        &self,
+        model_id: i32,
+    ) -> Result<Vec<notification::Model>, DbErr>;
+    async fn insert_notifications(
+        &self,
+        notifications: Vec<notification::ActiveModel>,
+    ) -> Result<(), DbErr>;
+}
+
+/// 心愿单目标价及模型名称，用于生成降价提醒
+#[derive(Debug, FromQueryResult)]
+pub struct WishlistTarget {
+    pub user_id: i32,
+    pub target_price: f64,
+    pub target_currency: String,
+    pub model_name: String,
+}
+
+#[derive(Clone)]
+pub struct NotificationRepository {
+    db: DatabaseConnection,
+}
+
+impl NotificationRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl NotificationRepositoryTrait for NotificationRepository {
+    async fn find_by_user(
+        &self,
+        user_id: i32,
+        unread_only: bool,
+        page: u64,
+        per_page: u64,
+    ) -> Result<(Vec<notification::Model>, u64), DbErr> {
+        let page = page.max(1);
+        let per_page = per_page.max(1);
+
+        let mut query =
+            notification::Entity::find().filter(notification::Column::UserId.eq(user_id));
+        if unread_only {
+            query = query.filter(notification::Column::ReadAt.is_null());
+        }
+        let paginator = query
+            .order_by_desc(notification::Column::CreatedAt)
+            .order_by_desc(notification::Column::Id)
+            .paginate(&self.db, per_page);
+
+        let total = paginator.num_items().await?;
+        let notifications = paginator.fetch_page(page - 1).await?;
+        Ok((notifications, total))
+    }
+
+    async fn count_unread(&self, user_id: i32) -> Result<u64, DbErr> {
+        notification::Entity::find()
+            .filter(notification::Column::UserId.eq(user_id))
+            .filter(notification::Column::ReadAt.is_null())
+            .count(&self.db)
+            .await
+    }
+
+    async fn acknowledge(&self, user_id: i32, ids: Option<Vec<i32>>) -> Result<u64, DbErr> {
+        let mut update = notification::Entity::update_many()
+            .col_expr(
+                notification::Column::ReadAt,
+                Expr::value(chrono::Utc::now().naive_utc()),
+            )
+            .filter(notification::Column::UserId.eq(user_id))
+            .filter(notification::Column::ReadAt.is_null());
+        if let Some(ids) = ids {
+            update = update.filter(notification::Column::Id.is_in(ids));
+        }
+        Ok(update.exec(&self.db).await?.rows_affected)
+    }
+
+    async fn find_preferences(
+        &self,
+        user_id: i32,
+    ) -> Result<Option<notification_preference::Model>, DbErr> {
+        notification_preference::Entity::find_by_id(user_id)
+            .one(&self.db)
+            .await
+    }
+
+    async fn save_preferences(
+        &self,
+        preferences: notification_preference::Model,
+    ) -> Result<notification_preference::Model, DbErr> {
+        let user_id = preferences.user_id;
+        let active = notification_preference::ActiveModel {
+            updated_at: Set(chrono::Utc::now().naive_utc()),
+            ..preferences.into_active_model().reset_all()
+        };
+        notification_preference::Entity::insert(active)
+            .on_conflict(
+                sea_query::OnConflict::column(notification_preference::Column::UserId)
+                    .update_columns([
+                        notification_preference::Column::InAppEnabled,
+                        notification_preference::Column::WebhookEnabled,
+                        notification_preference::Column::WebhookUrl,
+                        notification_preference::Column::WebhookSecret,
+                        notification_preference::Column::EmailEnabled,
+                        notification_preference::Column::Email,
+                        notification_preference::Column::UpdatedAt,
+                    ])
+                    .to_owned(),
+            )
+            .exec_without_returning(&self.db)
+            .await?;
+        notification_preference::Entity::find_by_id(user_id)
+            .one(&self.db)
+            .await?
+            .ok_or_else(|| DbErr::RecordNotFound("notification_preferences".to_string()))
+    }
+
+    async fn find_undispatched(&self, limit: u64) -> Result<Vec<notification::Model>, DbErr> {
+        notification::Entity::find()
+            .filter(notification::Column::DispatchedAt.is_null())
+            .order_by_asc(notification::Column::Id)
+            .limit(limit)
+            .all(&self.db)
+            .await
+    }
+
+    async fn schedule_deliveries(
+        &self,
+        notification_id: i32,
+        channels: Vec<DeliveryChannel>,
+    ) -> Result<(), DbErr> {
+        let now = chrono::Utc::now().naive_utc();
+        let txn = self.db.begin().await?;
+
+        if !channels.is_empty() {
+            let deliveries =
+                channels
+                    .into_iter()
+                    .map(|channel| notification_delivery::ActiveModel {
+                        notification_id: Set(notification_id),
+                        channel: Set(channel),
+                        status: Set(DeliveryStatus::Pending),
+                        attempts: Set(0),
+                        next_attempt_at: Set(now),
+                        ..Default::default()
+                    });
+            notification_delivery::Entity::insert_many(deliveries)
+                .on_conflict(
+                    sea_query::OnConflict::columns([
+                        notification_delivery::Column::NotificationId,
+                        notification_delivery::Column::Channel,
+                    ])
+                    .do_nothing()
+                    .to_owned(),
+                )
+                .exec_without_returning(&txn)
+                .await?;
+        }
+
+        notification::Entity::update_many()
+            .col_expr(notification::Column::DispatchedAt, Expr::value(now))
+            .filter(notification::Column::Id.eq(notification_id))
+            .exec(&txn)
+            .await?;
+
+        txn.commit().await
+    }
+
+    async fn claim_due_deliveries(
+        &self,
+        limit: u64,
+        lease_secs: i64,
+    ) -> Result<Vec<(notification_delivery::Model, notification::Model)>, DbErr> {
+        let now = chrono::Utc::now().naive_utc();
+        let lease_until = now + chrono::Duration::seconds(lease_secs);
+
+        let deliveries =
+            notification_delivery::Model::find_by_statement(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                r#"
+                UPDATE notification_deliveries
+                SET next_attempt_at = $1
+                WHERE id IN (
+                    SELECT id FROM notification_deliveries
+                    WHERE status = 'pending' AND next_attempt_at <= $2
+                    ORDER BY next_attempt_at, id
+                    LIMIT $3
+                    FOR UPDATE SKIP LOCKED
+                )
+                RETURNING *
+                "#,
+                [lease_until.into(), now.into(), (limit as i64).into()],
+            ))
+            .all(&self.db)
+            .await?;
+        if deliveries.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let notification_ids: Vec<i32> = deliveries.iter().map(|d| d.notification_id).collect();
+        let notifications: std::collections::HashMap<i32, notification::Model> =
+            notification::Entity::find()
+                .filter(notification::Column::Id.is_in(notification_ids))
+                .all(&self.db)
+                .await?
+                .into_iter()
+                .map(|n| (n.id, n))
+                .collect();
+
+        Ok(deliveries
+            .into_iter()
+            .filter_map(|d| {
+                let notification = notifications.get(&d.notification_id)?.clone();
+                Some((d, notification))
+            })
+            .collect())
+    }
+
+    async fn mark_delivered(&self, delivery_id: i32) -> Result<(), DbErr> {
+        notification_delivery::Entity::update_many()
+            .col_expr(
+                notification_delivery::Column::Status,
+                Expr::value(DeliveryStatus::Sent),
+            )
+            .col_expr(
+                notification_delivery::Column::Attempts,
+                Expr::col(notification_delivery::Column::Attempts).add(1),
+            )
+            .col_expr(
+                notification_delivery::Column::DeliveredAt,
+                Expr::value(chrono::Utc::now().naive_utc()),
+            )
+            .col_expr(
+                notification_delivery::Column::LastError,
+                Expr::value(Option::<String>::None),
+            )
+            .filter(notification_delivery::Column::Id.eq(delivery_id))
+            .exec(&self.db)
+            .await?;
+        Ok(())
+    }
+
+    async fn mark_failed(
+        &self,
+        delivery_id: i32,
+        error: String,
+        next_attempt_at: Option<chrono::NaiveDateTime>,
+    ) -> Result<(), DbErr> {
+        let mut update = notification_delivery::Entity::update_many()
+            .col_expr(
+                notification_delivery::Column::Attempts,
+                Expr::col(notification_delivery::Column::Attempts).add(1),
+            )
+            .col_expr(notification_delivery::Column::LastError, Expr::value(error));
+        update = match next_attempt_at {
+            Some(at) => update.col_expr(
+                notification_delivery::Column::NextAttemptAt,
+                Expr::value(at),
+            ),
+            None => update.col_expr(
+                notification_delivery::Column::Status,
+                Expr::value(DeliveryStatus::Failed),
+            ),
+        };
+        update
+            .filter(notification_delivery::Column::Id.eq(delivery_id))
+            .exec(&self.db)
+            .await?;
+        Ok(())
+    }
+
+    async fn find_wishlist_targets(&self, model_id: i32) -> Result<Vec<WishlistTarget>, DbErr> {
+        user_model_favorite::Entity::find()
+            .select_only()
+            .column(user_model_favorite::Column::UserId)
+            .column(user_model_favorite::Column::TargetPrice)
+            .column(user_model_favorite::Column::TargetCurrency)
+            .column_as(model::Column::Name, "model_name")
+            .join(
+                JoinType::InnerJoin,
+                user_model_favorite::Relation::Model.def(),
+            )
+            .filter(user_model_favorite::Column::ModelId.eq(model_id))
+            .filter(user_model_favorite::Column::TargetPrice.is_not_null())
+            .into_model::<WishlistTarget>()
+            .all(&self.db)
+            .await
+    }
+
+    async fn find_unread_price_drops(
+        &self,
+        model_id: i32,
+    ) -> Result<Vec<notification::Model>, DbErr> {
+        notification::Entity::find()
+            .filter(notification::Column::ModelId.eq(model_id))
+            .filter(notification::Column::Kind.eq(NotificationKind::PriceDrop))
+            .filter(notification::Column::ReadAt.is_null())
+            .all(&self.db)
+            .await
+    }
+
+    async fn insert_notifications(
+        &self,
+        notifications: Vec<notification::ActiveModel>,
+    ) -> Result<(), DbErr> {
+        if notifications.is_empty() {
+            return Ok(());
+        }
+        notification::Entity::insert_many(notifications)
+            .exec(&self.db)
+            .await?;
+        Ok(())
+    }
+}
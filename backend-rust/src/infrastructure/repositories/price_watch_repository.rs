@@ -0,0 +1,211 @@
+#![allow(dead_code)]
+
+use crate::domain::entities::{model, price_history, price_watch, shop};
+use async_trait::async_trait;
+use sea_orm::sea_query::Expr;
+use sea_orm::ActiveValue::Set;
+use sea_orm::*;
+
+#[async_trait]
+pub trait PriceWatchRepositoryTrait: Send + Sync {
+    async fn find_by_model(&self, model_id: i32) -> Result<Vec<price_watch::Model>, DbErr>;
+    async fn find_by_id(&self, id: i32) -> Result<Option<price_watch::Model>, DbErr>;
+    async fn find_by_model_and_url(
+        &self,
+        model_id: i32,
+        url: &str,
+    ) -> Result<Option<price_watch::Model>, DbErr>;
+    async fn find_model(&self, model_id: i32) -> Result<Option<model::Model>, DbErr>;
+    async fn find_shop(&self, shop_id: i32) -> Result<Option<shop::Model>, DbErr>;
+    async fn create(&self, watch: price_watch::ActiveModel) -> Result<price_watch::Model, DbErr>;
+    async fn update(
+        &self,
+        id: i32,
+        watch: price_watch::ActiveModel,
+    ) -> Result<price_watch::Model, DbErr>;
+    async fn delete(&self, id: i32) -> Result<DeleteResult, DbErr>;
+    /// 领取到期的监控，并把 `next_run_at` 推迟到租期结束，避免多实例重复抓取
+    async fn claim_due(
+        &self,
+        limit: u64,
+        lease_secs: i64,
+    ) -> Result<Vec<price_watch::Model>, DbErr>;
+    /// 该监控最近一次写入的价格记录
+    async fn find_latest_price(&self, watch_id: i32)
+        -> Result<Option<price_history::Model>, DbErr>;
+    /// 记录一次成功的抓取；`price` 非空时同时写入价格记录
+    async fn record_success(
+        &self,
+        watch_id: i32,
+        fetched_price: f64,
+        price: Option<price_history::ActiveModel>,
+        next_run_at: chrono::NaiveDateTime,
+    ) -> Result<Option<price_history::Model>, DbErr>;
+    async fn record_failure(
+        &self,
+        watch_id: i32,
+        error: String,
+        next_run_at: chrono::NaiveDateTime,
+        disable: bool,
+    ) -> Result<(), DbErr>;
+}
+
+#[derive(Clone)]
+pub struct PriceWatchRepository {
+    db: DatabaseConnection,
+}
+
+impl PriceWatchRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl PriceWatchRepositoryTrait for PriceWatchRepository {
+    async fn find_by_model(&self, model_id: i32) -> Result<Vec<price_watch::Model>, DbErr> {
+        price_watch::Entity::find()
+            .filter(price_watch::Column::ModelId.eq(model_id))
+            .order_by_asc(price_watch::Column::Id)
+            .all(&self.db)
+            .await
+    }
+
+    async fn find_by_id(&self, id: i32) -> Result<Option<price_watch::Model>, DbErr> {
+        price_watch::Entity::find_by_id(id).one(&self.db).await
+    }
+
+    async fn find_by_model_and_url(
+        &self,
+        model_id: i32,
+        url: &str,
+    ) -> Result<Option<price_watch::Model>, DbErr> {
+        price_watch::Entity::find()
+            .filter(price_watch::Column::ModelId.eq(model_id))
+            .filter(price_watch::Column::Url.eq(url))
+            .one(&self.db)
+            .await
+    }
+
+    async fn find_model(&self, model_id: i32) -> Result<Option<model::Model>, DbErr> {
+        model::Entity::find_by_id(model_id).one(&self.db).await
+    }
+
+    async fn find_shop(&self, shop_id: i32) -> Result<Option<shop::Model>, DbErr> {
+        shop::Entity::find_by_id(shop_id).one(&self.db).await
+    }
+
+    async fn create(&self, watch: price_watch::ActiveModel) -> Result<price_watch::Model, DbErr> {
+        watch.insert(&self.db).await
+    }
+
+    async fn update(
+        &self,
+        id: i32,
+        mut watch: price_watch::ActiveModel,
+    ) -> Result<price_watch::Model, DbErr> {
+        watch.id = Set(id);
+        watch.update(&self.db).await
+    }
+
+    async fn delete(&self, id: i32) -> Result<DeleteResult, DbErr> {
+        price_watch::Entity::delete_by_id(id).exec(&self.db).await
+    }
+
+    async fn claim_due(
+        &self,
+        limit: u64,
+        lease_secs: i64,
+    ) -> Result<Vec<price_watch::Model>, DbErr> {
+        let now = chrono::Utc::now().naive_utc();
+        let lease_until = now + chrono::Duration::seconds(lease_secs);
+
+        price_watch::Model::find_by_statement(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            r#"
+            UPDATE price_watches
+            SET next_run_at = $1
+            WHERE id IN (
+                SELECT id FROM price_watches
+                WHERE enabled AND next_run_at <= $2
+                ORDER BY next_run_at, id
+                LIMIT $3
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING *
+            "#,
+            [lease_until.into(), now.into(), (limit as i64).into()],
+        ))
+        .all(&self.db)
+        .await
+    }
+
+    async fn find_latest_price(
+        &self,
+        watch_id: i32,
+    ) -> Result<Option<price_history::Model>, DbErr> {
+        price_history::Entity::find()
+            .filter(price_history::Column::WatchId.eq(watch_id))
+            .order_by_desc(price_history::Column::RecordedAt)
+            .order_by_desc(price_history::Column::Id)
+            .one(&self.db)
+            .await
+    }
+
+    async fn record_success(
+        &self,
+        watch_id: i32,
+        fetched_price: f64,
+        price: Option<price_history::ActiveModel>,
+        next_run_at: chrono::NaiveDateTime,
+    ) -> Result<Option<price_history::Model>, DbErr> {
+        let now = chrono::Utc::now().naive_utc();
+        let txn = self.db.begin().await?;
+
+        let inserted = match price {
+            Some(price) => Some(price.insert(&txn).await?),
+            None => None,
+        };
+        price_watch::Entity::update_many()
+            .col_expr(price_watch::Column::LastRunAt, Expr::value(now))
+            .col_expr(price_watch::Column::LastPrice, Expr::value(fetched_price))
+            .col_expr(
+                price_watch::Column::LastError,
+                Expr::value(Option::<String>::None),
+            )
+            .col_expr(price_watch::Column::ConsecutiveFailures, Expr::value(0))
+            .col_expr(price_watch::Column::NextRunAt, Expr::value(next_run_at))
+            .filter(price_watch::Column::Id.eq(watch_id))
+            .exec(&txn)
+            .await?;
+
+        txn.commit().await?;
+        Ok(inserted)
+    }
+
+    async fn record_failure(
+        &self,
+        watch_id: i32,
+        error: String,
+        next_run_at: chrono::NaiveDateTime,
+        disable: bool,
+    ) -> Result<(), DbErr> {
+        let mut update = price_watch::Entity::update_many()
+            .col_expr(
+                price_watch::Column::LastRunAt,
+                Expr::value(chrono::Utc::now().naive_utc()),
+            )
+            .col_expr(price_watch::Column::LastError, Expr::value(error))
+            .col_expr(
+                price_watch::Column::ConsecutiveFailures,
+                Expr::col(price_watch::Column::ConsecutiveFailures).add(1),
+            )
+            .col_expr(price_watch::Column::NextRunAt, Expr::value(next_run_at))
+            .filter(price_watch::Column::Id.eq(watch_id));
+        if disable {
+            update = update.col_expr(price_watch::Column::Enabled, Expr::value(false));
+        }
+        update.exec(&self.db).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,185 @@
+#![allow(dead_code)]
+
+use crate::domain::entities::{manufacturer, model, series};
+use async_trait::async_trait;
+use sea_orm::sea_query::{Expr, Func, Query, SimpleExpr};
+use sea_orm::*;
+use std::collections::HashMap;
+
+/// 全文检索命中的记录 ID 及相关度
+#[derive(Debug, FromQueryResult)]
+pub struct RankedId {
+    pub id: i32,
+    pub rank: f32,
+}
+
+#[async_trait]
+pub trait SearchRepositoryTrait: Send + Sync {
+    /// 当前数据库是否支持 tsvector 全文检索（仅 Postgres）
+    fn supports_full_text(&self) -> bool;
+    async fn rank_models(&self, tsquery: &str, limit: u64) -> Result<Vec<RankedId>, DbErr>;
+    async fn rank_manufacturers(&self, tsquery: &str, limit: u64) -> Result<Vec<RankedId>, DbErr>;
+    /// 通用回退：任一检索词出现在名称/备注/系列名称中的模型
+    async fn find_model_candidates(
+        &self,
+        terms: &[String],
+        limit: u64,
+    ) -> Result<Vec<model::Model>, DbErr>;
+    async fn find_manufacturer_candidates(
+        &self,
+        terms: &[String],
+        limit: u64,
+    ) -> Result<Vec<manufacturer::Model>, DbErr>;
+    async fn find_models(&self, ids: Vec<i32>) -> Result<Vec<model::Model>, DbErr>;
+    async fn find_manufacturers(&self, ids: Vec<i32>) -> Result<Vec<manufacturer::Model>, DbErr>;
+    async fn find_series_names(&self, series_ids: Vec<i32>) -> Result<HashMap<i32, String>, DbErr>;
+}
+
+#[derive(Clone)]
+pub struct SearchRepository {
+    db: DatabaseConnection,
+}
+
+impl SearchRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+}
+
+/// `LOWER(column) LIKE '%term%'`，检索词只含字母数字与中日文字符，无需转义
+fn lower_contains<C: ColumnTrait>(column: C, term: &str) -> SimpleExpr {
+    Expr::expr(Func::lower(Expr::col(column))).like(format!("%{}%", term))
+}
+
+#[async_trait]
+impl SearchRepositoryTrait for SearchRepository {
+    fn supports_full_text(&self) -> bool {
+        self.db.get_database_backend() == DbBackend::Postgres
+    }
+
+    async fn rank_models(&self, tsquery: &str, limit: u64) -> Result<Vec<RankedId>, DbErr> {
+        RankedId::find_by_statement(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            r#"
+            SELECT m.id, ts_rank(doc.vector, q.query) AS rank
+            FROM models m
+            LEFT JOIN series s ON s.id = m.series_id
+            CROSS JOIN LATERAL (
+                SELECT m.search_vector || COALESCE(setweight(to_tsvector('simple',
+                    modelhub_search_text(s.name || ' ' || COALESCE(array_to_string(s.aliases, ' '), ''))
+                ), 'B'), ''::tsvector) AS vector
+            ) doc
+            CROSS JOIN to_tsquery('simple', $1) AS q(query)
+            WHERE doc.vector @@ q.query
+            ORDER BY rank DESC, m.id
+            LIMIT $2
+            "#,
+            [tsquery.into(), (limit as i64).into()],
+        ))
+        .all(&self.db)
+        .await
+    }
+
+    async fn rank_manufacturers(&self, tsquery: &str, limit: u64) -> Result<Vec<RankedId>, DbErr> {
+        RankedId::find_by_statement(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            r#"
+            SELECT mf.id, ts_rank(mf.search_vector, q.query) AS rank
+            FROM manufacturers mf
+            CROSS JOIN to_tsquery('simple', $1) AS q(query)
+            WHERE mf.search_vector @@ q.query
+            ORDER BY rank DESC, mf.id
+            LIMIT $2
+            "#,
+            [tsquery.into(), (limit as i64).into()],
+        ))
+        .all(&self.db)
+        .await
+    }
+
+    async fn find_model_candidates(
+        &self,
+        terms: &[String],
+        limit: u64,
+    ) -> Result<Vec<model::Model>, DbErr> {
+        let mut condition = Condition::any();
+        for term in terms {
+            let matching_series = Query::select()
+                .column(series::Column::Id)
+                .from(series::Entity)
+                .and_where(lower_contains(series::Column::Name, term))
+                .to_owned();
+
+            condition = condition
+                .add(lower_contains(model::Column::Name, term))
+                .add(lower_contains(model::Column::Notes, term))
+                .add(model::Column::SeriesId.in_subquery(matching_series));
+        }
+
+        model::Entity::find()
+            .filter(condition)
+            .order_by_asc(model::Column::Id)
+            .limit(limit)
+            .all(&self.db)
+            .await
+    }
+
+    async fn find_manufacturer_candidates(
+        &self,
+        terms: &[String],
+        limit: u64,
+    ) -> Result<Vec<manufacturer::Model>, DbErr> {
+        let mut condition = Condition::any();
+        for term in terms {
+            condition = condition
+                .add(lower_contains(manufacturer::Column::Name, term))
+                .add(lower_contains(manufacturer::Column::FullName, term));
+        }
+
+        manufacturer::Entity::find()
+            .filter(condition)
+            .order_by_asc(manufacturer::Column::Id)
+            .limit(limit)
+            .all(&self.db)
+            .await
+    }
+
+    async fn find_models(&self, ids: Vec<i32>) -> Result<Vec<model::Model>, DbErr> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        model::Entity::find()
+            .filter(model::Column::Id.is_in(ids))
+            .all(&self.db)
+            .await
+    }
+
+    async fn find_manufacturers(&self, ids: Vec<i32>) -> Result<Vec<manufacturer::Model>, DbErr> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        manufacturer::Entity::find()
+            .filter(manufacturer::Column::Id.is_in(ids))
+            .all(&self.db)
+            .await
+    }
+
+    async fn find_series_names(&self, series_ids: Vec<i32>) -> Result<HashMap<i32, String>, DbErr> {
+        if series_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let names: Vec<(i32, String)> = series::Entity::find()
+            .select_only()
+            .column(series::Column::Id)
+            .column(series::Column::Name)
+            .filter(series::Column::Id.is_in(series_ids))
+            .into_tuple()
+            .all(&self.db)
+            .await?;
+
+        Ok(names.into_iter().collect())
+    }
+}
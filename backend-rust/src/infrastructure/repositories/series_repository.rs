@@ -0,0 +1,95 @@
+#![allow(dead_code)]
+
+use crate::domain::entities::{model, series};
+use async_trait::async_trait;
+use sea_orm::sea_query::{Expr, Func};
+use sea_orm::*;
+use std::collections::HashMap;
+
+#[async_trait]
+pub trait SeriesRepositoryTrait: Send + Sync {
+    async fn find_all(&self) -> Result<Vec<series::Model>, DbErr>;
+    async fn find_by_id(&self, id: i32) -> Result<Option<series::Model>, DbErr>;
+    /// 按名称或别名查找，忽略大小写
+    async fn find_by_name_or_alias(&self, name: &str) -> Result<Option<series::Model>, DbErr>;
+    async fn count_models(&self) -> Result<HashMap<i32, i64>, DbErr>;
+    async fn create(&self, series_data: series::ActiveModel) -> Result<series::Model, DbErr>;
+    async fn update(
+        &self,
+        id: i32,
+        series_data: series::ActiveModel,
+    ) -> Result<series::Model, DbErr>;
+    async fn delete(&self, id: i32) -> Result<DeleteResult, DbErr>;
+}
+
+#[derive(Clone)]
+pub struct SeriesRepository {
+    db: DatabaseConnection,
+}
+
+impl SeriesRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl SeriesRepositoryTrait for SeriesRepository {
+    async fn find_all(&self) -> Result<Vec<series::Model>, DbErr> {
+        series::Entity::find()
+            .order_by_asc(series::Column::AirStartYear)
+            .order_by_asc(series::Column::Name)
+            .all(&self.db)
+            .await
+    }
+
+    async fn find_by_id(&self, id: i32) -> Result<Option<series::Model>, DbErr> {
+        series::Entity::find_by_id(id).one(&self.db).await
+    }
+
+    async fn find_by_name_or_alias(&self, name: &str) -> Result<Option<series::Model>, DbErr> {
+        let name = name.trim().to_lowercase();
+        series::Entity::find()
+            .filter(
+                Condition::any()
+                    .add(Expr::expr(Func::lower(Expr::col(series::Column::Name))).eq(name.clone()))
+                    .add(Expr::cust_with_values(
+                        "EXISTS (SELECT 1 FROM unnest(aliases) AS alias WHERE lower(alias) = ?)",
+                        [name],
+                    )),
+            )
+            .one(&self.db)
+            .await
+    }
+
+    async fn count_models(&self) -> Result<HashMap<i32, i64>, DbErr> {
+        let counts: Vec<(i32, i64)> = model::Entity::find()
+            .select_only()
+            .column(model::Column::SeriesId)
+            .column_as(model::Column::Id.count(), "models_count")
+            .filter(model::Column::SeriesId.is_not_null())
+            .group_by(model::Column::SeriesId)
+            .into_tuple()
+            .all(&self.db)
+            .await?;
+
+        Ok(counts.into_iter().collect())
+    }
+
+    async fn create(&self, series_data: series::ActiveModel) -> Result<series::Model, DbErr> {
+        series_data.insert(&self.db).await
+    }
+
+    async fn update(
+        &self,
+        id: i32,
+        mut series_data: series::ActiveModel,
+    ) -> Result<series::Model, DbErr> {
+        series_data.id = Set(id);
+        series_data.update(&self.db).await
+    }
+
+    async fn delete(&self, id: i32) -> Result<DeleteResult, DbErr> {
+        series::Entity::delete_by_id(id).exec(&self.db).await
+    }
+}
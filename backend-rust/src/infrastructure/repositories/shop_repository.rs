@@ -0,0 +1,70 @@
+#![allow(dead_code)]
+
+use crate::domain::entities::shop;
+use async_trait::async_trait;
+use sea_orm::sea_query::{Expr, Func};
+use sea_orm::*;
+
+#[async_trait]
+pub trait ShopRepositoryTrait: Send + Sync {
+    async fn find_all(&self) -> Result<Vec<shop::Model>, DbErr>;
+    async fn find_by_id(&self, id: i32) -> Result<Option<shop::Model>, DbErr>;
+    /// 按名称查找，忽略大小写
+    async fn find_by_name(&self, name: &str) -> Result<Option<shop::Model>, DbErr>;
+    async fn create(&self, shop_data: shop::ActiveModel) -> Result<shop::Model, DbErr>;
+    async fn update(&self, id: i32, shop_data: shop::ActiveModel) -> Result<shop::Model, DbErr>;
+    async fn delete(&self, id: i32) -> Result<DeleteResult, DbErr>;
+}
+
+#[derive(Clone)]
+pub struct ShopRepository {
+    db: DatabaseConnection,
+}
+
+impl ShopRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl ShopRepositoryTrait for ShopRepository {
+    async fn find_all(&self) -> Result<Vec<shop::Model>, DbErr> {
+        shop::Entity::find()
+            .order_by_asc(shop::Column::Platform)
+            .order_by_asc(shop::Column::Name)
+            .all(&self.db)
+            .await
+    }
+
+    async fn find_by_id(&self, id: i32) -> Result<Option<shop::Model>, DbErr> {
+        shop::Entity::find_by_id(id).one(&self.db).await
+    }
+
+    async fn find_by_name(&self, name: &str) -> Result<Option<shop::Model>, DbErr> {
+        shop::Entity::find()
+            .filter(
+                Expr::expr(Func::lower(Expr::col(shop::Column::Name)))
+                    .eq(name.trim().to_lowercase()),
+            )
+            .one(&self.db)
+            .await
+    }
+
+    async fn create(&self, shop_data: shop::ActiveModel) -> Result<shop::Model, DbErr> {
+        shop_data.insert(&self.db).await
+    }
+
+    async fn update(
+        &self,
+        id: i32,
+        mut shop_data: shop::ActiveModel,
+    ) -> Result<shop::Model, DbErr> {
+        shop_data.id = Set(id);
+        shop_data.update(&self.db).await
+    }
+
+    async fn delete(&self, id: i32) -> Result<DeleteResult, DbErr> {
+        shop::Entity::delete_by_id(id).exec(&self.db).await
+    }
+}
@@ -0,0 +1,46 @@
+#![allow(dead_code)]
+
+use crate::domain::entities::{model_tag, tag};
+use async_trait::async_trait;
+use sea_orm::*;
+use std::collections::HashMap;
+
+#[async_trait]
+pub trait TagRepositoryTrait: Send + Sync {
+    async fn find_all(&self) -> Result<Vec<tag::Model>, DbErr>;
+    async fn count_models(&self) -> Result<HashMap<i32, i64>, DbErr>;
+}
+
+#[derive(Clone)]
+pub struct TagRepository {
+    db: DatabaseConnection,
+}
+
+impl TagRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl TagRepositoryTrait for TagRepository {
+    async fn find_all(&self) -> Result<Vec<tag::Model>, DbErr> {
+        tag::Entity::find()
+            .order_by_asc(tag::Column::Name)
+            .all(&self.db)
+            .await
+    }
+
+    async fn count_models(&self) -> Result<HashMap<i32, i64>, DbErr> {
+        let counts: Vec<(i32, i64)> = model_tag::Entity::find()
+            .select_only()
+            .column(model_tag::Column::TagId)
+            .column_as(model_tag::Column::ModelId.count(), "models_count")
+            .group_by(model_tag::Column::TagId)
+            .into_tuple()
+            .all(&self.db)
+            .await?;
+
+        Ok(counts.into_iter().collect())
+    }
+}
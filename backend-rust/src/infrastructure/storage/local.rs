@@ -0,0 +1,52 @@
+use super::ObjectStorage;
+use async_trait::async_trait;
+use std::path::{Path, PathBuf};
+
+/// 本地存储的文件由服务自身在该路径下提供静态访问
+pub const LOCAL_URL_PREFIX: &str = "/uploads";
+
+/// 本地文件系统存储，对象键即相对于根目录的路径
+pub struct LocalStorage {
+    root: PathBuf,
+    public_url: String,
+}
+
+impl LocalStorage {
+    pub fn new(root: impl AsRef<Path>, public_url: &str) -> Self {
+        Self {
+            root: root.as_ref().to_path_buf(),
+            public_url: public_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    fn path_of(&self, key: &str) -> anyhow::Result<PathBuf> {
+        // 对象键由服务端生成，这里仍拒绝可能越出根目录的路径
+        if key.split('/').any(|part| part.is_empty() || part == "..") {
+            anyhow::bail!("非法的对象键: {}", key);
+        }
+        Ok(self.root.join(key))
+    }
+}
+
+#[async_trait]
+impl ObjectStorage for LocalStorage {
+    async fn put(&self, key: &str, data: Vec<u8>, _content_type: &str) -> anyhow::Result<()> {
+        let path = self.path_of(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(path, data).await?;
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> anyhow::Result<()> {
+        match tokio::fs::remove_file(self.path_of(key)?).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn public_url(&self, key: &str) -> String {
+        format!("{}/{}", self.public_url, key)
+    }
+}
@@ -0,0 +1,34 @@
+use crate::config::{StorageBackend, StorageConfig};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+pub mod local;
+pub mod s3;
+
+/// 图片等二进制文件的存储后端，对象键形如 `models/12/<uuid>.jpg`
+#[async_trait]
+pub trait ObjectStorage: Send + Sync {
+    async fn put(&self, key: &str, data: Vec<u8>, content_type: &str) -> anyhow::Result<()>;
+    /// 删除对象，对象不存在时视为成功
+    async fn delete(&self, key: &str) -> anyhow::Result<()>;
+    /// 客户端访问对象的地址
+    fn public_url(&self, key: &str) -> String;
+}
+
+pub type SharedStorage = Arc<dyn ObjectStorage>;
+
+pub fn create_storage(config: &StorageConfig) -> SharedStorage {
+    match &config.backend {
+        StorageBackend::Local { dir } => Arc::new(local::LocalStorage::new(
+            dir,
+            config
+                .public_url
+                .as_deref()
+                .unwrap_or(local::LOCAL_URL_PREFIX),
+        )),
+        StorageBackend::S3(s3_config) => Arc::new(s3::S3Storage::new(
+            s3_config.clone(),
+            config.public_url.clone(),
+        )),
+    }
+}
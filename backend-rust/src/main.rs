@@ -3,7 +3,27 @@ mod domain;
 mod infrastructure;
 mod presentation;
 mod services;
+#[cfg(test)]
+mod test_support;
 
+use config::database::create_connection;
+use infrastructure::database::migrations::Migrator;
+use infrastructure::notifications::create_channels;
+use infrastructure::price_sources::create_sources;
+use infrastructure::repositories::currency_repository::CurrencyRepository;
+use infrastructure::repositories::model_repository::ModelRepository;
+use infrastructure::repositories::notification_repository::NotificationRepository;
+use infrastructure::repositories::price_watch_repository::PriceWatchRepository;
+use infrastructure::storage::create_storage;
+use sea_orm_migration::MigratorTrait;
+use services::currency_service::CurrencyService;
+use services::event_hub::EventHub;
+use services::model_service::ModelService;
+use services::notification_dispatcher::NotificationDispatcher;
+use services::price_alert_service::PriceAlertService;
+use services::price_watch_service::PriceWatchService;
+use services::scheduler::JobScheduler;
+use services::status_transition_service::StatusTransitionService;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 #[tokio::main]
@@ -20,8 +40,59 @@ async fn main() -> anyhow::Result<()> {
     let config = config::Config::from_env()?;
     tracing::info!("配置加载成功: {:?}", config);
 
+    // 连接数据库并执行迁移
+    let db = create_connection(&config.database.url, config.database.max_connections).await?;
+    Migrator::up(&db, None).await?;
+
+    // 服务层发布、事件流订阅的数据变更广播
+    let events = EventHub::new();
+
+    // 启动通知分发任务
+    NotificationDispatcher::new(
+        NotificationRepository::new(db.clone()),
+        create_channels(&config.notifications, events.clone()),
+        &config.notifications,
+    )
+    .spawn();
+
+    // 启动定时任务
+    let price_alerts = PriceAlertService::new(
+        NotificationRepository::new(db.clone()),
+        CurrencyService::new(CurrencyRepository::new(db.clone())),
+    );
+    let mut scheduler = JobScheduler::new();
+    if config.scraper.enabled {
+        scheduler.add(
+            &config.scraper.schedule,
+            PriceWatchService::new(
+                PriceWatchRepository::new(db.clone()),
+                CurrencyService::new(CurrencyRepository::new(db.clone())),
+                create_sources(&config.scraper),
+                events.clone(),
+                price_alerts.clone(),
+                &config.scraper,
+            ),
+        )?;
+    }
+    if config.status_transitions.enabled {
+        scheduler.add(
+            &config.status_transitions.schedule,
+            StatusTransitionService::new(
+                ModelService::new(
+                    ModelRepository::new(db.clone()),
+                    CurrencyService::new(CurrencyRepository::new(db.clone())),
+                    create_storage(&config.storage),
+                    events.clone(),
+                    price_alerts,
+                ),
+                &config.status_transitions,
+            ),
+        )?;
+    }
+    scheduler.spawn();
+
     // 创建路由
-    let app = presentation::routes::create_routes(config.clone()).await?;
+    let app = presentation::routes::create_routes(config.clone(), db, events);
 
     // 启动服务器
     let bind_addr = (config.server.host.as_str(), config.server.port);
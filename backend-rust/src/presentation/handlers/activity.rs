@@ -0,0 +1,20 @@
+use super::service_error_response;
+use crate::domain::models::activity_dto::*;
+use crate::presentation::state::AppState;
+use axum::{
+    extract::{Query, State},
+    response::{Json, Response},
+};
+
+pub async fn get_activity(
+    State(state): State<AppState>,
+    Query(params): Query<ActivityParams>,
+) -> Result<Json<ActivityListResponse>, Response> {
+    match state.activity_service.get_activity(params).await {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => {
+            tracing::error!("获取动态失败: {:?}", e);
+            Err(service_error_response(&e))
+        }
+    }
+}
@@ -42,7 +42,8 @@ pub async fn get_profile(
     match state.auth_service.get_user_by_id(user_id).await {
         Ok(Some(user)) => Ok(Json(json!({
             "id": user.id,
-            "username": user.username
+            "username": user.username,
+            "is_admin": user.is_admin
         }))),
         Ok(None) => Err(StatusCode::NOT_FOUND),
         Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
@@ -0,0 +1,54 @@
+use super::service_error_response;
+use crate::domain::models::backup_dto::{BackupArchive, BackupParams, RestoreSummary};
+use crate::presentation::state::AppState;
+use axum::{
+    extract::{Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+
+/// 恢复接口的请求体上限，整库备份通常远大于默认的 2MB
+pub const RESTORE_BODY_LIMIT: usize = 512 * 1024 * 1024;
+
+pub async fn get_backup(
+    State(state): State<AppState>,
+    Query(params): Query<BackupParams>,
+) -> Result<Response, StatusCode> {
+    match state
+        .backup_service
+        .create_backup(params.include_password_hashes)
+        .await
+    {
+        Ok(archive) => {
+            let filename = format!(
+                "modelhub-backup-{}.json",
+                archive.created_at.format("%Y%m%d%H%M%S")
+            );
+            Ok((
+                [(
+                    header::CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"{}\"", filename),
+                )],
+                Json(archive),
+            )
+                .into_response())
+        }
+        Err(e) => {
+            tracing::error!("生成数据库备份失败: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+pub async fn restore_backup(
+    State(state): State<AppState>,
+    Json(archive): Json<BackupArchive>,
+) -> Result<Json<RestoreSummary>, Response> {
+    match state.backup_service.restore(archive).await {
+        Ok(summary) => Ok(Json(summary)),
+        Err(e) => {
+            tracing::error!("恢复数据库备份失败: {:?}", e);
+            Err(service_error_response(&e))
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use super::{service_error_response, service_error_status, user_id_from_claims};
+use crate::config::auth::Claims;
+use crate::domain::models::build_log_dto::{BuildLogResponse, CreateBuildLogEntryForm};
+use crate::presentation::state::AppState;
+use axum::{
+    extract::{Multipart, Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+    Extension,
+};
+
+pub async fn get_build_log(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(model_id): Path<i32>,
+) -> Result<Json<BuildLogResponse>, StatusCode> {
+    let user_id = user_id_from_claims(&claims)?;
+
+    match state
+        .build_log_service
+        .get_build_log(user_id, model_id)
+        .await
+    {
+        Ok(log) => Ok(Json(log)),
+        Err(e) => {
+            tracing::error!("获取制作日志失败: {:?}", e);
+            Err(service_error_status(&e))
+        }
+    }
+}
+
+/// multipart 表单：`entry_date`、`content`、`hours_spent`、`stage` 文本字段，
+/// 以及任意个 `images` 文件字段
+pub async fn create_build_log_entry(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(model_id): Path<i32>,
+    mut multipart: Multipart,
+) -> Result<(StatusCode, Json<BuildLogResponse>), Response> {
+    let user_id = user_id_from_claims(&claims).map_err(IntoResponse::into_response)?;
+
+    let mut form = CreateBuildLogEntryForm::default();
+    while let Some(field) = multipart.next_field().await.map_err(|e| {
+        tracing::error!("解析制作日志表单失败: {:?}", e);
+        e.into_response()
+    })? {
+        let name = field.name().unwrap_or_default().to_string();
+        if name == "images" {
+            let bytes = field.bytes().await.map_err(IntoResponse::into_response)?;
+            form.images.push(bytes.to_vec());
+            continue;
+        }
+
+        let value = field.text().await.map_err(IntoResponse::into_response)?;
+        match name.as_str() {
+            "entry_date" => form.entry_date = Some(value),
+            "content" => form.content = Some(value),
+            "hours_spent" => form.hours_spent = Some(value),
+            "stage" => form.stage = Some(value),
+            _ => {}
+        }
+    }
+
+    match state
+        .build_log_service
+        .add_entry(user_id, model_id, form)
+        .await
+    {
+        Ok(log) => Ok((StatusCode::CREATED, Json(log))),
+        Err(e) => {
+            tracing::error!("新增制作日志失败: {:?}", e);
+            Err(service_error_response(&e))
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use super::{service_error_response, user_id_from_claims};
+use crate::config::auth::Claims;
+use crate::domain::models::calendar_dto::*;
+use crate::presentation::state::AppState;
+use axum::{
+    extract::{Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
+    Extension,
+};
+
+pub async fn get_calendar(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Query(params): Query<CalendarParams>,
+) -> Result<Json<CalendarResponse>, Response> {
+    let user_id = user_id_from_claims(&claims).map_err(IntoResponse::into_response)?;
+
+    match state.calendar_service.get_calendar(user_id, params).await {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => {
+            tracing::error!("获取发售日历失败: {:?}", e);
+            Err(service_error_response(&e))
+        }
+    }
+}
+
+pub async fn get_calendar_token(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> Result<Json<CalendarTokenResponse>, StatusCode> {
+    let user_id = user_id_from_claims(&claims)?;
+
+    match state.calendar_service.get_token(user_id).await {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => {
+            tracing::error!("获取日历订阅令牌失败: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+pub async fn regenerate_calendar_token(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> Result<Json<CalendarTokenResponse>, StatusCode> {
+    let user_id = user_id_from_claims(&claims)?;
+
+    match state.calendar_service.regenerate_token(user_id).await {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => {
+            tracing::error!("重新生成日历订阅令牌失败: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// iCalendar 订阅源，令牌无效时返回 401
+pub async fn get_calendar_feed(
+    State(state): State<AppState>,
+    Query(params): Query<CalendarFeedParams>,
+) -> Result<Response, StatusCode> {
+    match state.calendar_service.render_feed(&params.token).await {
+        Ok(Some(body)) => Ok((
+            [
+                (header::CONTENT_TYPE, "text/calendar; charset=utf-8"),
+                (header::CACHE_CONTROL, "no-cache"),
+            ],
+            body,
+        )
+            .into_response()),
+        Ok(None) => Err(StatusCode::UNAUTHORIZED),
+        Err(e) => {
+            tracing::error!("生成日历订阅源失败: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
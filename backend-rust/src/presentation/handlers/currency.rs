@@ -0,0 +1,84 @@
+use super::service_error_response;
+use crate::domain::models::currency_dto::*;
+use crate::presentation::state::AppState;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+use validator::Validate;
+
+pub async fn get_currencies(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<CurrencyResponse>>, StatusCode> {
+    match state.currency_service.get_currencies().await {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => {
+            tracing::error!("获取币种列表失败: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+pub async fn get_exchange_rates(
+    State(state): State<AppState>,
+    Query(params): Query<ExchangeRateListParams>,
+) -> Result<Json<Vec<ExchangeRateResponse>>, StatusCode> {
+    match state.currency_service.get_rates(params).await {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => {
+            tracing::error!("获取汇率失败: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+pub async fn create_exchange_rate(
+    State(state): State<AppState>,
+    Json(request): Json<CreateExchangeRateRequest>,
+) -> Result<Json<ExchangeRateResponse>, Response> {
+    if let Err(e) = request.validate() {
+        tracing::error!("验证错误: {:?}", e);
+        return Err(StatusCode::BAD_REQUEST.into_response());
+    }
+
+    match state.currency_service.create_rate(request).await {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => {
+            tracing::error!("登记汇率失败: {:?}", e);
+            Err(service_error_response(&e))
+        }
+    }
+}
+
+pub async fn import_exchange_rates(
+    State(state): State<AppState>,
+    Json(request): Json<ImportExchangeRatesRequest>,
+) -> Result<Json<ImportExchangeRatesResponse>, Response> {
+    if let Err(e) = request.validate() {
+        tracing::error!("验证错误: {:?}", e);
+        return Err(StatusCode::BAD_REQUEST.into_response());
+    }
+
+    match state.currency_service.import_rates(request).await {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => {
+            tracing::error!("导入汇率失败: {:?}", e);
+            Err(service_error_response(&e))
+        }
+    }
+}
+
+pub async fn delete_exchange_rate(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> Result<StatusCode, StatusCode> {
+    match state.currency_service.delete_rate(id).await {
+        Ok(true) => Ok(StatusCode::NO_CONTENT),
+        Ok(false) => Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("删除汇率失败: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use super::user_id_from_claims;
+use crate::config::auth::Claims;
+use crate::domain::models::event_dto::ChangeEvent;
+use crate::presentation::state::AppState;
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::sse::{Event, KeepAlive, Sse},
+    Extension,
+};
+use futures::{future, Stream, StreamExt};
+use std::convert::Infallible;
+use std::sync::Arc;
+use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
+
+/// 客户端错过了部分事件，需要重新拉取数据
+const RESYNC_EVENT: &str = "resync";
+
+/// 数据变更事件流（SSE）。断线重连时浏览器会自动携带 `Last-Event-ID`，
+/// 服务端补发其后的事件；无法补发时先发送一条 `resync` 事件
+pub async fn stream_events(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    headers: HeaderMap,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, StatusCode> {
+    let user_id = user_id_from_claims(&claims)?;
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok());
+
+    let subscription = state.events.subscribe(last_event_id);
+    let resync = futures::stream::iter(subscription.resync.then(resync_event));
+    let backlog = futures::stream::iter(subscription.backlog)
+        .filter_map(move |event| future::ready(to_sse_event(&event, user_id)));
+    let live = BroadcastStream::new(subscription.receiver).filter_map(move |received| {
+        future::ready(match received {
+            Ok(event) => to_sse_event(&event, user_id),
+            Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+                tracing::warn!("事件流订阅者积压过多，跳过 {} 条事件", skipped);
+                Some(resync_event())
+            }
+        })
+    });
+
+    let stream = resync.chain(backlog).chain(live).map(Ok);
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+fn to_sse_event(event: &Arc<ChangeEvent>, user_id: i32) -> Option<Event> {
+    if !event.visible_to(user_id) {
+        return None;
+    }
+    match Event::default()
+        .id(event.id.to_string())
+        .event(event.kind.as_str())
+        .json_data(event.as_ref())
+    {
+        Ok(sse_event) => Some(sse_event),
+        Err(e) => {
+            tracing::error!("序列化变更事件失败: {:?}", e);
+            None
+        }
+    }
+}
+
+fn resync_event() -> Event {
+    Event::default().event(RESYNC_EVENT).data("{}")
+}
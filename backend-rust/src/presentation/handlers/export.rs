@@ -0,0 +1,109 @@
+use super::{model::ModelListParams, service_error_response, user_id_from_claims};
+use crate::config::auth::Claims;
+use crate::domain::models::export_dto::{ExportFormat, ExportScope};
+use crate::presentation::state::AppState;
+use axum::{
+    body::Body,
+    extract::{Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Extension,
+};
+use futures::TryStreamExt;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct ExportParams {
+    #[serde(default)]
+    pub format: ExportFormat,
+    /// 额外输出换算到该币种的最新价格
+    pub currency: Option<String>,
+}
+
+/// 导出全部模型目录，筛选参数与 `GET /api/models` 相同
+pub async fn export_models(
+    State(state): State<AppState>,
+    Query(list): Query<ModelListParams>,
+    Query(params): Query<ExportParams>,
+) -> Result<Response, Response> {
+    export(state, ExportScope::Catalog, list, params, "models").await
+}
+
+/// 导出当前用户收藏或购买过的模型
+pub async fn export_user_models(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Query(list): Query<ModelListParams>,
+    Query(params): Query<ExportParams>,
+) -> Result<Response, Response> {
+    let user_id = user_id_from_claims(&claims).map_err(IntoResponse::into_response)?;
+    export(
+        state,
+        ExportScope::User(user_id),
+        list,
+        params,
+        "my-collection",
+    )
+    .await
+}
+
+async fn export(
+    state: AppState,
+    scope: ExportScope,
+    list: ModelListParams,
+    params: ExportParams,
+    file_stem: &str,
+) -> Result<Response, Response> {
+    let format = params.format;
+    // 在开始输出前校验币种，流式响应一旦开始就无法再返回错误状态码
+    let currency = match state.currency_service.target(params.currency).await {
+        Ok(currency) => currency,
+        Err(e) => {
+            tracing::error!("导出币种无效: {:?}", e);
+            return Err(service_error_response(&e));
+        }
+    };
+
+    let filename = format!(
+        "{}-{}.{}",
+        file_stem,
+        chrono::Utc::now().format("%Y%m%d"),
+        format.extension()
+    );
+
+    let body = match format {
+        ExportFormat::Xlsx => match state
+            .export_service
+            .export_xlsx(scope, list.filter(), currency)
+            .await
+        {
+            Ok(bytes) => Body::from(bytes),
+            Err(e) => {
+                tracing::error!("导出 XLSX 失败: {:?}", e);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR.into_response());
+            }
+        },
+        _ => {
+            let stream = state
+                .export_service
+                .export_stream(scope, list.filter(), format, currency)
+                .map_err(|e| {
+                    tracing::error!("导出数据失败: {:?}", e);
+                    std::io::Error::other(e.to_string())
+                });
+            Body::from_stream(stream)
+        }
+    };
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        body,
+    )
+        .into_response())
+}
@@ -0,0 +1,90 @@
+use super::{service_error_response, service_error_status, user_id_from_claims};
+use crate::config::auth::Claims;
+use crate::domain::models::image_dto::ModelImageResponse;
+use crate::presentation::state::AppState;
+use axum::{
+    extract::{Multipart, Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+    Extension,
+};
+
+pub async fn get_model_images(
+    State(state): State<AppState>,
+    Path(model_id): Path<i32>,
+) -> Result<Json<Vec<ModelImageResponse>>, StatusCode> {
+    match state.image_service.get_images(model_id).await {
+        Ok(images) => Ok(Json(images)),
+        Err(e) => {
+            tracing::error!("获取模型图片失败: {:?}", e);
+            Err(service_error_status(&e))
+        }
+    }
+}
+
+/// multipart 表单：`file` 为图片文件，可选 `primary=true` 设为主图
+pub async fn upload_model_image(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(model_id): Path<i32>,
+    mut multipart: Multipart,
+) -> Result<(StatusCode, Json<ModelImageResponse>), Response> {
+    let user_id = user_id_from_claims(&claims).map_err(IntoResponse::into_response)?;
+
+    let mut file = None;
+    let mut primary = false;
+    while let Some(field) = multipart.next_field().await.map_err(|e| {
+        tracing::error!("解析上传表单失败: {:?}", e);
+        e.into_response()
+    })? {
+        match field.name() {
+            Some("file") => {
+                let bytes = field.bytes().await.map_err(IntoResponse::into_response)?;
+                file = Some(bytes.to_vec());
+            }
+            Some("primary") => {
+                let value = field.text().await.map_err(IntoResponse::into_response)?;
+                primary = matches!(value.trim(), "true" | "1" | "on");
+            }
+            _ => {}
+        }
+    }
+
+    match state
+        .image_service
+        .upload_image(model_id, file.unwrap_or_default(), primary, Some(user_id))
+        .await
+    {
+        Ok(image) => Ok((StatusCode::CREATED, Json(image))),
+        Err(e) => {
+            tracing::error!("上传模型图片失败: {:?}", e);
+            Err(service_error_response(&e))
+        }
+    }
+}
+
+pub async fn set_primary_image(
+    State(state): State<AppState>,
+    Path((model_id, image_id)): Path<(i32, i32)>,
+) -> Result<Json<ModelImageResponse>, StatusCode> {
+    match state.image_service.set_primary(model_id, image_id).await {
+        Ok(image) => Ok(Json(image)),
+        Err(e) => {
+            tracing::error!("设置主图失败: {:?}", e);
+            Err(service_error_status(&e))
+        }
+    }
+}
+
+pub async fn delete_model_image(
+    State(state): State<AppState>,
+    Path((model_id, image_id)): Path<(i32, i32)>,
+) -> Result<StatusCode, StatusCode> {
+    match state.image_service.delete_image(model_id, image_id).await {
+        Ok(_) => Ok(StatusCode::NO_CONTENT),
+        Err(e) => {
+            tracing::error!("删除模型图片失败: {:?}", e);
+            Err(service_error_status(&e))
+        }
+    }
+}
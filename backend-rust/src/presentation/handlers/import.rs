@@ -0,0 +1,29 @@
+use super::{service_error_response, user_id_from_claims};
+use crate::config::auth::Claims;
+use crate::domain::models::import_dto::{ImportModelsRequest, ImportReport};
+use crate::presentation::state::AppState;
+use axum::{
+    extract::State,
+    response::{IntoResponse, Json, Response},
+    Extension,
+};
+
+pub async fn import_models(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Json(request): Json<ImportModelsRequest>,
+) -> Result<Json<ImportReport>, Response> {
+    let user_id = user_id_from_claims(&claims).map_err(IntoResponse::into_response)?;
+
+    match state
+        .import_service
+        .import_models(request, Some(user_id))
+        .await
+    {
+        Ok(report) => Ok(Json(report)),
+        Err(e) => {
+            tracing::error!("导入模型失败: {:?}", e);
+            Err(service_error_response(&e))
+        }
+    }
+}
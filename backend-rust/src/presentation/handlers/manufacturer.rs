@@ -1,11 +1,12 @@
 #![allow(dead_code)]
 
+use super::{etag_header, service_error_status, version_from_if_match};
 use crate::domain::models::manufacturer_dto::*;
 use crate::presentation::state::AppState;
 use axum::{
     extract::{Path, State},
-    http::StatusCode,
-    response::Json,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Json, Response},
 };
 use validator::Validate;
 
@@ -24,9 +25,12 @@ pub async fn get_manufacturers(
 pub async fn get_manufacturer_by_id(
     State(state): State<AppState>,
     Path(id): Path<i32>,
-) -> Result<Json<Option<ManufacturerResponse>>, StatusCode> {
+) -> Result<Response, StatusCode> {
     match state.manufacturer_service.get_manufacturer_by_id(id).await {
-        Ok(response) => Ok(Json(response)),
+        Ok(response) => {
+            let etag = response.as_ref().map(|m| etag_header(m.version));
+            Ok((etag, Json(response)).into_response())
+        }
         Err(e) => {
             tracing::error!("获取厂商详情失败: {:?}", e);
             Err(StatusCode::INTERNAL_SERVER_ERROR)
@@ -59,22 +63,55 @@ pub async fn create_manufacturer(
 pub async fn update_manufacturer(
     State(state): State<AppState>,
     Path(id): Path<i32>,
-    Json(request): Json<UpdateManufacturerRequest>,
-) -> Result<Json<ManufacturerResponse>, StatusCode> {
+    headers: HeaderMap,
+    Json(mut request): Json<UpdateManufacturerRequest>,
+) -> Result<Response, StatusCode> {
     if let Err(e) = request.validate() {
         tracing::error!("验证错误: {:?}", e);
         return Err(StatusCode::BAD_REQUEST);
     }
 
+    if let Some(version) = version_from_if_match(&headers)? {
+        request.version = Some(version);
+    }
+
     match state
         .manufacturer_service
         .update_manufacturer(id, request)
         .await
     {
-        Ok(response) => Ok(Json(response)),
+        Ok(response) => Ok((etag_header(response.version), Json(response)).into_response()),
         Err(e) => {
             tracing::error!("更新厂商失败: {:?}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            Err(service_error_status(&e))
+        }
+    }
+}
+
+pub async fn patch_manufacturer(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    headers: HeaderMap,
+    Json(mut request): Json<PatchManufacturerRequest>,
+) -> Result<Response, StatusCode> {
+    if let Err(e) = request.validate() {
+        tracing::error!("验证错误: {:?}", e);
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    if let Some(version) = version_from_if_match(&headers)? {
+        request.version = Some(version);
+    }
+
+    match state
+        .manufacturer_service
+        .patch_manufacturer(id, request)
+        .await
+    {
+        Ok(response) => Ok((etag_header(response.version), Json(response)).into_response()),
+        Err(e) => {
+            tracing::error!("部分更新厂商失败: {:?}", e);
+            Err(service_error_status(&e))
         }
     }
 }
@@ -82,12 +119,19 @@ pub async fn update_manufacturer(
 pub async fn delete_manufacturer(
     State(state): State<AppState>,
     Path(id): Path<i32>,
+    headers: HeaderMap,
 ) -> Result<StatusCode, StatusCode> {
-    match state.manufacturer_service.delete_manufacturer(id).await {
+    let expected_version = version_from_if_match(&headers)?;
+
+    match state
+        .manufacturer_service
+        .delete_manufacturer(id, expected_version)
+        .await
+    {
         Ok(_) => Ok(StatusCode::NO_CONTENT),
         Err(e) => {
             tracing::error!("删除厂商失败: {:?}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            Err(service_error_status(&e))
         }
     }
 }
@@ -0,0 +1,34 @@
+use crate::domain::entities::enums::{BuildStage, Grade, ModelStatus};
+use crate::domain::models::meta_dto::{EnumOption, EnumsResponse};
+use axum::response::Json;
+use sea_orm::{ActiveEnum, Iterable};
+
+/// 返回前端下拉框使用的枚举取值及中文标签
+pub async fn get_enums() -> Json<EnumsResponse> {
+    let status = ModelStatus::iter()
+        .map(|s| EnumOption {
+            value: s.to_value(),
+            label: s.label().to_string(),
+        })
+        .collect();
+
+    let grade = Grade::iter()
+        .map(|g| EnumOption {
+            value: g.to_value(),
+            label: g.label().to_string(),
+        })
+        .collect();
+
+    let build_stage = BuildStage::iter()
+        .map(|s| EnumOption {
+            value: s.to_value(),
+            label: s.label().to_string(),
+        })
+        .collect();
+
+    Json(EnumsResponse {
+        status,
+        grade,
+        build_stage,
+    })
+}
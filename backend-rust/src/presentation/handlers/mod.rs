@@ -1,10 +1,31 @@
-use axum::http::StatusCode;
+use axum::{
+    http::{header, HeaderMap, HeaderName, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use serde_json::json;
 
 use crate::config::auth::Claims;
+use crate::services::error::ServiceError;
 
+pub mod activity;
 pub mod auth;
+pub mod backup;
+pub mod build_log;
+pub mod calendar;
+pub mod currency;
+pub mod events;
+pub mod export;
+pub mod image;
+pub mod import;
 pub mod manufacturer;
+pub mod meta;
 pub mod model;
+pub mod notification;
+pub mod price_watch;
+pub mod search;
+pub mod series;
+pub mod shop;
+pub mod tag;
 
 pub fn user_id_from_claims(claims: &Claims) -> Result<i32, StatusCode> {
     claims
@@ -12,3 +33,64 @@ pub fn user_id_from_claims(claims: &Claims) -> Result<i32, StatusCode> {
         .parse::<i32>()
         .map_err(|_| StatusCode::UNAUTHORIZED)
 }
+
+/// 将服务层错误映射为HTTP状态码，未识别的错误统一返回 500
+pub fn service_error_status(err: &anyhow::Error) -> StatusCode {
+    match err.downcast_ref::<ServiceError>() {
+        Some(ServiceError::NotFound) => StatusCode::NOT_FOUND,
+        Some(ServiceError::PreconditionFailed) => StatusCode::PRECONDITION_FAILED,
+        Some(ServiceError::Validation(_)) => StatusCode::UNPROCESSABLE_ENTITY,
+        Some(ServiceError::PossibleDuplicates(_)) => StatusCode::CONFLICT,
+        Some(ServiceError::Conflict(_)) => StatusCode::CONFLICT,
+        None => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+/// 与 `service_error_status` 相同，但字段级校验错误会附带 `{"errors": [...]}` 响应体，
+/// 疑似重复会附带 `{"message": "...", "duplicates": [...]}` 响应体，其他冲突附带 `{"message": "..."}`
+pub fn service_error_response(err: &anyhow::Error) -> Response {
+    match err.downcast_ref::<ServiceError>() {
+        Some(ServiceError::Validation(errors)) => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(json!({ "errors": errors })),
+        )
+            .into_response(),
+        Some(e @ ServiceError::PossibleDuplicates(duplicates)) => (
+            StatusCode::CONFLICT,
+            Json(json!({ "message": e.to_string(), "duplicates": duplicates })),
+        )
+            .into_response(),
+        Some(e @ ServiceError::Conflict(_)) => (
+            StatusCode::CONFLICT,
+            Json(json!({ "message": e.to_string() })),
+        )
+            .into_response(),
+        _ => service_error_status(err).into_response(),
+    }
+}
+
+pub fn etag_header(version: i64) -> [(HeaderName, String); 1] {
+    [(header::ETAG, format!("\"{}\"", version))]
+}
+
+/// 解析 If-Match 请求头中的版本号，`*` 表示不校验；格式无法识别时直接返回 412
+pub fn version_from_if_match(headers: &HeaderMap) -> Result<Option<i64>, StatusCode> {
+    let Some(value) = headers.get(header::IF_MATCH) else {
+        return Ok(None);
+    };
+
+    let value = value
+        .to_str()
+        .map_err(|_| StatusCode::PRECONDITION_FAILED)?
+        .trim();
+    if value == "*" {
+        return Ok(None);
+    }
+
+    value
+        .trim_start_matches("W/")
+        .trim_matches('"')
+        .parse::<i64>()
+        .map(Some)
+        .map_err(|_| StatusCode::PRECONDITION_FAILED)
+}
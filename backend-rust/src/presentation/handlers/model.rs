@@ -1,13 +1,19 @@
 #![allow(dead_code)]
 
-use super::user_id_from_claims;
+use super::{
+    etag_header, service_error_response, service_error_status, user_id_from_claims,
+    version_from_if_match,
+};
 use crate::config::auth::Claims;
+use crate::domain::entities::tag;
+use crate::domain::models::currency_dto::CurrencyParams;
 use crate::domain::models::model_dto::*;
+use crate::domain::models::shop_dto::ShopPriceResponse;
 use crate::presentation::state::AppState;
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
-    response::Json,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Json, Response},
     Extension,
 };
 use serde::Deserialize;
@@ -28,15 +34,45 @@ fn default_per_page() -> u64 {
     20
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ModelListParams {
+    #[serde(default = "default_page")]
+    pub page: u64,
+    #[serde(default = "default_per_page", alias = "page_size")]
+    pub per_page: u64,
+    /// 逗号分隔的标签 slug，如 `p-bandai,limited`
+    pub tags: Option<String>,
+    #[serde(default)]
+    pub tag_mode: TagMatchMode,
+}
+
+impl ModelListParams {
+    pub fn filter(&self) -> ModelFilter {
+        let tags = self
+            .tags
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .map(tag::slugify)
+            .filter(|t| !t.is_empty())
+            .collect();
+
+        ModelFilter {
+            tags,
+            tag_mode: self.tag_mode,
+        }
+    }
+}
+
 pub async fn get_models(
     State(state): State<AppState>,
-    Query(params): Query<PaginationParams>,
+    Query(params): Query<ModelListParams>,
 ) -> Result<Json<ModelListResponse>, StatusCode> {
     let user_id = None;
 
     match state
         .model_service
-        .get_models(user_id, params.page, params.per_page)
+        .get_models(user_id, params.filter(), params.page, params.per_page)
         .await
     {
         Ok(response) => Ok(Json(response)),
@@ -50,11 +86,14 @@ pub async fn get_models(
 pub async fn get_model_by_id(
     State(state): State<AppState>,
     Path(id): Path<i32>,
-) -> Result<Json<Option<ModelResponse>>, StatusCode> {
+) -> Result<Response, StatusCode> {
     let user_id = None;
 
     match state.model_service.get_model_by_id(id, user_id).await {
-        Ok(response) => Ok(Json(response)),
+        Ok(response) => {
+            let etag = response.as_ref().map(|m| etag_header(m.version));
+            Ok((etag, Json(response)).into_response())
+        }
         Err(e) => {
             tracing::error!("获取模型详情失败: {:?}", e);
             Err(StatusCode::INTERNAL_SERVER_ERROR)
@@ -81,52 +120,269 @@ pub async fn get_model_variants(
     }
 }
 
+#[derive(Debug, Deserialize)]
+pub struct CreateModelParams {
+    /// 忽略疑似重复提示，强制创建
+    #[serde(default)]
+    pub force: bool,
+}
+
 pub async fn create_model(
     State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Query(params): Query<CreateModelParams>,
     Json(request): Json<CreateModelRequest>,
-) -> Result<Json<ModelResponse>, StatusCode> {
+) -> Result<Json<ModelResponse>, Response> {
     if let Err(e) = request.validate() {
         tracing::error!("验证错误: {:?}", e);
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(StatusCode::BAD_REQUEST.into_response());
     }
 
-    match state.model_service.create_model(request).await {
+    let user_id = user_id_from_claims(&claims).map_err(IntoResponse::into_response)?;
+    match state
+        .model_service
+        .create_model(request, params.force, Some(user_id))
+        .await
+    {
         Ok(response) => Ok(Json(response)),
         Err(e) => {
             tracing::error!("创建模型失败: {:?}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            Err(service_error_response(&e))
         }
     }
 }
 
 pub async fn update_model(
     State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
     Path(id): Path<i32>,
-    Json(request): Json<UpdateModelRequest>,
-) -> Result<Json<ModelResponse>, StatusCode> {
+    headers: HeaderMap,
+    Json(mut request): Json<UpdateModelRequest>,
+) -> Result<Response, Response> {
     if let Err(e) = request.validate() {
         tracing::error!("验证错误: {:?}", e);
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(StatusCode::BAD_REQUEST.into_response());
     }
 
-    match state.model_service.update_model(id, request).await {
-        Ok(response) => Ok(Json(response)),
+    let user_id = user_id_from_claims(&claims).map_err(IntoResponse::into_response)?;
+    if let Some(version) = version_from_if_match(&headers).map_err(IntoResponse::into_response)? {
+        request.version = Some(version);
+    }
+
+    match state
+        .model_service
+        .update_model(id, request, Some(user_id))
+        .await
+    {
+        Ok(response) => Ok((etag_header(response.version), Json(response)).into_response()),
         Err(e) => {
             tracing::error!("更新模型失败: {:?}", e);
+            Err(service_error_response(&e))
+        }
+    }
+}
+
+pub async fn patch_model(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<i32>,
+    headers: HeaderMap,
+    Json(mut request): Json<PatchModelRequest>,
+) -> Result<Response, Response> {
+    if let Err(e) = request.validate() {
+        tracing::error!("验证错误: {:?}", e);
+        return Err(StatusCode::BAD_REQUEST.into_response());
+    }
+
+    let user_id = user_id_from_claims(&claims).map_err(IntoResponse::into_response)?;
+    if let Some(version) = version_from_if_match(&headers).map_err(IntoResponse::into_response)? {
+        request.version = Some(version);
+    }
+
+    match state
+        .model_service
+        .patch_model(id, request, Some(user_id))
+        .await
+    {
+        Ok(response) => Ok((etag_header(response.version), Json(response)).into_response()),
+        Err(e) => {
+            tracing::error!("部分更新模型失败: {:?}", e);
+            Err(service_error_response(&e))
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DuplicateReportParams {
+    /// 相似度阈值（0~1），默认 0.85
+    pub threshold: Option<f64>,
+}
+
+pub async fn get_duplicate_report(
+    State(state): State<AppState>,
+    Query(params): Query<DuplicateReportParams>,
+) -> Result<Json<Vec<DuplicatePair>>, StatusCode> {
+    match state
+        .model_service
+        .get_duplicate_report(params.threshold)
+        .await
+    {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => {
+            tracing::error!("获取重复模型报表失败: {:?}", e);
             Err(StatusCode::INTERNAL_SERVER_ERROR)
         }
     }
 }
 
+pub async fn get_model_revisions(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> Result<Json<Vec<ModelRevisionResponse>>, StatusCode> {
+    match state.model_service.get_model_revisions(id).await {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => {
+            tracing::error!("获取模型修订历史失败: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// 各店铺的最新报价，最便宜的在前
+pub async fn get_model_prices_by_shop(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Query(params): Query<CurrencyParams>,
+) -> Result<Json<Vec<ShopPriceResponse>>, Response> {
+    match state
+        .model_service
+        .get_prices_by_shop(id, params.currency)
+        .await
+    {
+        Ok(Some(response)) => Ok(Json(response)),
+        Ok(None) => Err(StatusCode::NOT_FOUND.into_response()),
+        Err(e) => {
+            tracing::error!("获取店铺比价失败: {:?}", e);
+            Err(service_error_response(&e))
+        }
+    }
+}
+
+pub async fn get_model_family(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Query(params): Query<CurrencyParams>,
+) -> Result<Json<ModelFamilyResponse>, Response> {
+    match state
+        .model_service
+        .get_model_family(id, params.currency)
+        .await
+    {
+        Ok(Some(response)) => Ok(Json(response)),
+        Ok(None) => Err(StatusCode::NOT_FOUND.into_response()),
+        Err(e) => {
+            tracing::error!("获取模型变体树失败: {:?}", e);
+            Err(service_error_response(&e))
+        }
+    }
+}
+
+pub async fn get_model_prices(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Query(params): Query<CurrencyParams>,
+) -> Result<Json<Vec<PriceHistoryResponse>>, Response> {
+    match state.model_service.get_prices(id, params.currency).await {
+        Ok(Some(response)) => Ok(Json(response)),
+        Ok(None) => Err(StatusCode::NOT_FOUND.into_response()),
+        Err(e) => {
+            tracing::error!("获取价格记录失败: {:?}", e);
+            Err(service_error_response(&e))
+        }
+    }
+}
+
+pub async fn add_model_price(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<i32>,
+    Json(request): Json<CreatePriceRequest>,
+) -> Result<Json<PriceHistoryResponse>, Response> {
+    let user_id = user_id_from_claims(&claims).map_err(IntoResponse::into_response)?;
+    if let Err(e) = request.validate() {
+        tracing::error!("验证错误: {:?}", e);
+        return Err(StatusCode::BAD_REQUEST.into_response());
+    }
+
+    match state.model_service.add_price(id, request, user_id).await {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => {
+            tracing::error!("记录价格失败: {:?}", e);
+            Err(service_error_response(&e))
+        }
+    }
+}
+
+pub async fn restore_model_revision(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path((id, revision)): Path<(i32, i32)>,
+    headers: HeaderMap,
+) -> Result<Response, Response> {
+    let user_id = user_id_from_claims(&claims).map_err(IntoResponse::into_response)?;
+    let expected_version = version_from_if_match(&headers).map_err(IntoResponse::into_response)?;
+
+    match state
+        .model_service
+        .restore_model_revision(id, revision, expected_version, Some(user_id))
+        .await
+    {
+        Ok(Some(response)) => Ok((etag_header(response.version), Json(response)).into_response()),
+        Ok(None) => Err(StatusCode::NOT_FOUND.into_response()),
+        Err(e) => {
+            tracing::error!("恢复模型修订失败: {:?}", e);
+            Err(service_error_response(&e))
+        }
+    }
+}
+
+pub async fn merge_model(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<i32>,
+    headers: HeaderMap,
+    Json(mut request): Json<MergeModelRequest>,
+) -> Result<Response, Response> {
+    let user_id = user_id_from_claims(&claims).map_err(IntoResponse::into_response)?;
+    if let Some(version) = version_from_if_match(&headers).map_err(IntoResponse::into_response)? {
+        request.version = Some(version);
+    }
+
+    match state
+        .model_service
+        .merge_models(id, request, Some(user_id))
+        .await
+    {
+        Ok(response) => Ok((etag_header(response.model.version), Json(response)).into_response()),
+        Err(e) => {
+            tracing::error!("合并模型失败: {:?}", e);
+            Err(service_error_response(&e))
+        }
+    }
+}
+
 pub async fn delete_model(
     State(state): State<AppState>,
     Path(id): Path<i32>,
+    headers: HeaderMap,
 ) -> Result<StatusCode, StatusCode> {
-    match state.model_service.delete_model(id).await {
+    let expected_version = version_from_if_match(&headers)?;
+
+    match state.model_service.delete_model(id, expected_version).await {
         Ok(_) => Ok(StatusCode::NO_CONTENT),
         Err(e) => {
             tracing::error!("删除模型失败: {:?}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            Err(service_error_status(&e))
         }
     }
 }
@@ -179,8 +435,8 @@ pub async fn mark_purchase(
     Extension(claims): Extension<Claims>,
     Path(id): Path<i32>,
     Json(request): Json<TogglePurchaseRequest>,
-) -> Result<StatusCode, StatusCode> {
-    let user_id = user_id_from_claims(&claims)?;
+) -> Result<StatusCode, Response> {
+    let user_id = user_id_from_claims(&claims).map_err(IntoResponse::into_response)?;
 
     match state
         .model_service
@@ -190,7 +446,7 @@ pub async fn mark_purchase(
         Ok(_) => Ok(StatusCode::NO_CONTENT),
         Err(e) => {
             tracing::error!("标记购买失败: {:?}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            Err(service_error_response(&e))
         }
     }
 }
@@ -199,8 +455,8 @@ pub async fn unmark_purchase(
     State(state): State<AppState>,
     Extension(claims): Extension<Claims>,
     Path(id): Path<i32>,
-) -> Result<StatusCode, StatusCode> {
-    let user_id = user_id_from_claims(&claims)?;
+) -> Result<StatusCode, Response> {
+    let user_id = user_id_from_claims(&claims).map_err(IntoResponse::into_response)?;
 
     let request = TogglePurchaseRequest { notes: None };
 
@@ -212,7 +468,7 @@ pub async fn unmark_purchase(
         Ok(_) => Ok(StatusCode::NO_CONTENT),
         Err(e) => {
             tracing::error!("取消购买失败: {:?}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            Err(service_error_response(&e))
         }
     }
 }
@@ -237,6 +493,109 @@ pub async fn get_favorites(
     }
 }
 
+pub async fn get_wishlist(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Query(params): Query<CurrencyParams>,
+) -> Result<Json<Vec<WishlistItemResponse>>, Response> {
+    let user_id = user_id_from_claims(&claims).map_err(IntoResponse::into_response)?;
+
+    match state
+        .model_service
+        .get_wishlist(user_id, params.currency)
+        .await
+    {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => {
+            tracing::error!("获取心愿单失败: {:?}", e);
+            Err(service_error_response(&e))
+        }
+    }
+}
+
+pub async fn get_collection_value(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Query(params): Query<CurrencyParams>,
+) -> Result<Json<CollectionValueResponse>, Response> {
+    let user_id = user_id_from_claims(&claims).map_err(IntoResponse::into_response)?;
+
+    match state
+        .model_service
+        .get_collection_value(user_id, params.currency)
+        .await
+    {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => {
+            tracing::error!("计算收藏总价值失败: {:?}", e);
+            Err(service_error_response(&e))
+        }
+    }
+}
+
+pub async fn update_wishlist(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(model_id): Path<i32>,
+    Json(request): Json<UpdateWishlistRequest>,
+) -> Result<Json<WishlistItemResponse>, Response> {
+    let user_id = user_id_from_claims(&claims).map_err(IntoResponse::into_response)?;
+    if let Err(e) = request.validate() {
+        tracing::error!("验证错误: {:?}", e);
+        return Err(StatusCode::BAD_REQUEST.into_response());
+    }
+
+    match state
+        .model_service
+        .update_wishlist(user_id, model_id, request)
+        .await
+    {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => {
+            tracing::error!("更新心愿单失败: {:?}", e);
+            Err(service_error_response(&e))
+        }
+    }
+}
+
+pub async fn update_purchase(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(model_id): Path<i32>,
+    Json(request): Json<UpdatePurchaseRequest>,
+) -> Result<Json<PurchaseResponse>, Response> {
+    let user_id = user_id_from_claims(&claims).map_err(IntoResponse::into_response)?;
+    if let Err(e) = request.validate() {
+        tracing::error!("验证错误: {:?}", e);
+        return Err(StatusCode::BAD_REQUEST.into_response());
+    }
+
+    match state
+        .model_service
+        .update_purchase(user_id, model_id, request)
+        .await
+    {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => {
+            tracing::error!("更新购买记录失败: {:?}", e);
+            Err(service_error_response(&e))
+        }
+    }
+}
+
+/// 立即执行一次模型状态流转（仅管理员）
+pub async fn run_status_transitions(
+    State(state): State<AppState>,
+) -> Result<Json<StatusTransitionReport>, StatusCode> {
+    match state.status_transition_service.run_transitions().await {
+        Ok(report) => Ok(Json(report)),
+        Err(e) => {
+            tracing::error!("执行状态流转失败: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
 pub async fn get_purchases(
     State(state): State<AppState>,
     Extension(claims): Extension<Claims>,
@@ -0,0 +1,92 @@
+use super::{service_error_response, user_id_from_claims};
+use crate::config::auth::Claims;
+use crate::domain::models::notification_dto::*;
+use crate::presentation::state::AppState;
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+    Extension,
+};
+use validator::Validate;
+
+pub async fn get_alerts(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Query(params): Query<NotificationListParams>,
+) -> Result<Json<NotificationListResponse>, StatusCode> {
+    let user_id = user_id_from_claims(&claims)?;
+
+    match state
+        .notification_service
+        .get_notifications(user_id, params)
+        .await
+    {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => {
+            tracing::error!("获取通知列表失败: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// 请求体可省略，省略时确认全部未读通知
+pub async fn acknowledge_alerts(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    request: Option<Json<AcknowledgeNotificationsRequest>>,
+) -> Result<Json<AcknowledgeNotificationsResponse>, StatusCode> {
+    let user_id = user_id_from_claims(&claims)?;
+    let request = request.map(|Json(r)| r).unwrap_or_default();
+
+    match state
+        .notification_service
+        .acknowledge(user_id, request)
+        .await
+    {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => {
+            tracing::error!("确认通知失败: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+pub async fn get_notification_preferences(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> Result<Json<NotificationPreferencesResponse>, StatusCode> {
+    let user_id = user_id_from_claims(&claims)?;
+
+    match state.notification_service.get_preferences(user_id).await {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => {
+            tracing::error!("获取通知偏好失败: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+pub async fn update_notification_preferences(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Json(request): Json<UpdateNotificationPreferencesRequest>,
+) -> Result<Json<NotificationPreferencesResponse>, Response> {
+    let user_id = user_id_from_claims(&claims).map_err(IntoResponse::into_response)?;
+    if let Err(e) = request.validate() {
+        tracing::error!("验证错误: {:?}", e);
+        return Err(StatusCode::BAD_REQUEST.into_response());
+    }
+
+    match state
+        .notification_service
+        .update_preferences(user_id, request)
+        .await
+    {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => {
+            tracing::error!("更新通知偏好失败: {:?}", e);
+            Err(service_error_response(&e))
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use super::{service_error_response, user_id_from_claims};
+use crate::config::auth::Claims;
+use crate::domain::models::price_watch_dto::*;
+use crate::presentation::state::AppState;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+    Extension,
+};
+use validator::Validate;
+
+pub async fn get_price_watches(
+    State(state): State<AppState>,
+    Path(model_id): Path<i32>,
+) -> Result<Json<Vec<PriceWatchResponse>>, StatusCode> {
+    match state.price_watch_service.get_watches(model_id).await {
+        Ok(Some(response)) => Ok(Json(response)),
+        Ok(None) => Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("获取价格监控失败: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+pub async fn create_price_watch(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(model_id): Path<i32>,
+    Json(request): Json<CreatePriceWatchRequest>,
+) -> Result<Json<PriceWatchResponse>, Response> {
+    let user_id = user_id_from_claims(&claims).map_err(IntoResponse::into_response)?;
+    if let Err(e) = request.validate() {
+        tracing::error!("验证错误: {:?}", e);
+        return Err(StatusCode::BAD_REQUEST.into_response());
+    }
+
+    match state
+        .price_watch_service
+        .create_watch(model_id, request, user_id)
+        .await
+    {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => {
+            tracing::error!("登记价格监控失败: {:?}", e);
+            Err(service_error_response(&e))
+        }
+    }
+}
+
+pub async fn preview_price(
+    State(state): State<AppState>,
+    Path(model_id): Path<i32>,
+    Json(request): Json<PreviewPriceRequest>,
+) -> Result<Json<PreviewPriceResponse>, Response> {
+    if let Err(e) = request.validate() {
+        tracing::error!("验证错误: {:?}", e);
+        return Err(StatusCode::BAD_REQUEST.into_response());
+    }
+
+    match state.price_watch_service.preview(model_id, request).await {
+        Ok(Some(response)) => Ok(Json(response)),
+        Ok(None) => Err(StatusCode::NOT_FOUND.into_response()),
+        Err(e) => {
+            tracing::error!("试抓取价格失败: {:?}", e);
+            Err(service_error_response(&e))
+        }
+    }
+}
+
+pub async fn patch_price_watch(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Json(request): Json<PatchPriceWatchRequest>,
+) -> Result<Json<PriceWatchResponse>, Response> {
+    if let Err(e) = request.validate() {
+        tracing::error!("验证错误: {:?}", e);
+        return Err(StatusCode::BAD_REQUEST.into_response());
+    }
+
+    match state.price_watch_service.patch_watch(id, request).await {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => {
+            tracing::error!("更新价格监控失败: {:?}", e);
+            Err(service_error_response(&e))
+        }
+    }
+}
+
+pub async fn delete_price_watch(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> Result<StatusCode, StatusCode> {
+    match state.price_watch_service.delete_watch(id).await {
+        Ok(true) => Ok(StatusCode::NO_CONTENT),
+        Ok(false) => Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("删除价格监控失败: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+pub async fn check_price_watch(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> Result<Json<PriceCheckResponse>, Response> {
+    match state.price_watch_service.check_now(id).await {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => {
+            tracing::error!("抓取价格失败: {:?}", e);
+            Err(service_error_response(&e))
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use crate::domain::models::search_dto::SearchResponse;
+use crate::presentation::state::AppState;
+use axum::{
+    extract::{Query, State},
+    response::{Json, Response},
+};
+use serde::Deserialize;
+
+use super::service_error_response;
+
+fn default_limit() -> u64 {
+    20
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SearchParams {
+    #[serde(default)]
+    pub q: String,
+    #[serde(default = "default_limit")]
+    pub limit: u64,
+}
+
+pub async fn search(
+    State(state): State<AppState>,
+    Query(params): Query<SearchParams>,
+) -> Result<Json<SearchResponse>, Response> {
+    match state.search_service.search(&params.q, params.limit).await {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => {
+            tracing::error!("搜索失败: {:?}", e);
+            Err(service_error_response(&e))
+        }
+    }
+}
@@ -0,0 +1,106 @@
+#![allow(dead_code)]
+
+use super::model::PaginationParams;
+use super::service_error_response;
+use crate::domain::models::model_dto::ModelListResponse;
+use crate::domain::models::series_dto::*;
+use crate::presentation::state::AppState;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+use validator::Validate;
+
+pub async fn get_series(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<SeriesResponse>>, StatusCode> {
+    match state.series_service.get_series().await {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => {
+            tracing::error!("获取系列列表失败: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+pub async fn get_series_by_id(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> Result<Json<Option<SeriesResponse>>, StatusCode> {
+    match state.series_service.get_series_by_id(id).await {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => {
+            tracing::error!("获取系列详情失败: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+pub async fn get_series_models(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Query(params): Query<PaginationParams>,
+) -> Result<Json<ModelListResponse>, StatusCode> {
+    match state
+        .model_service
+        .get_models_by_series(id, params.page, params.per_page)
+        .await
+    {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => {
+            tracing::error!("获取系列模型列表失败: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+pub async fn create_series(
+    State(state): State<AppState>,
+    Json(request): Json<CreateSeriesRequest>,
+) -> Result<Json<SeriesResponse>, Response> {
+    if let Err(e) = request.validate() {
+        tracing::error!("验证错误: {:?}", e);
+        return Err(StatusCode::BAD_REQUEST.into_response());
+    }
+
+    match state.series_service.create_series(request).await {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => {
+            tracing::error!("创建系列失败: {:?}", e);
+            Err(service_error_response(&e))
+        }
+    }
+}
+
+pub async fn update_series(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Json(request): Json<UpdateSeriesRequest>,
+) -> Result<Json<SeriesResponse>, Response> {
+    if let Err(e) = request.validate() {
+        tracing::error!("验证错误: {:?}", e);
+        return Err(StatusCode::BAD_REQUEST.into_response());
+    }
+
+    match state.series_service.update_series(id, request).await {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => {
+            tracing::error!("更新系列失败: {:?}", e);
+            Err(service_error_response(&e))
+        }
+    }
+}
+
+pub async fn delete_series(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> Result<StatusCode, StatusCode> {
+    match state.series_service.delete_series(id).await {
+        Ok(_) => Ok(StatusCode::NO_CONTENT),
+        Err(e) => {
+            tracing::error!("删除系列失败: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
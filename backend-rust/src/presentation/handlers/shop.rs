@@ -0,0 +1,86 @@
+use super::service_error_response;
+use crate::domain::models::shop_dto::*;
+use crate::presentation::state::AppState;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+use validator::Validate;
+
+pub async fn get_shops(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<ShopResponse>>, StatusCode> {
+    match state.shop_service.get_shops().await {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => {
+            tracing::error!("获取店铺列表失败: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+pub async fn get_shop_by_id(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> Result<Json<ShopResponse>, StatusCode> {
+    match state.shop_service.get_shop_by_id(id).await {
+        Ok(Some(response)) => Ok(Json(response)),
+        Ok(None) => Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("获取店铺详情失败: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+pub async fn create_shop(
+    State(state): State<AppState>,
+    Json(request): Json<CreateShopRequest>,
+) -> Result<Json<ShopResponse>, Response> {
+    if let Err(e) = request.validate() {
+        tracing::error!("验证错误: {:?}", e);
+        return Err(StatusCode::BAD_REQUEST.into_response());
+    }
+
+    match state.shop_service.create_shop(request).await {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => {
+            tracing::error!("创建店铺失败: {:?}", e);
+            Err(service_error_response(&e))
+        }
+    }
+}
+
+pub async fn patch_shop(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Json(request): Json<PatchShopRequest>,
+) -> Result<Json<ShopResponse>, Response> {
+    if let Err(e) = request.validate() {
+        tracing::error!("验证错误: {:?}", e);
+        return Err(StatusCode::BAD_REQUEST.into_response());
+    }
+
+    match state.shop_service.patch_shop(id, request).await {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => {
+            tracing::error!("更新店铺失败: {:?}", e);
+            Err(service_error_response(&e))
+        }
+    }
+}
+
+pub async fn delete_shop(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> Result<StatusCode, StatusCode> {
+    match state.shop_service.delete_shop(id).await {
+        Ok(true) => Ok(StatusCode::NO_CONTENT),
+        Ok(false) => Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("删除店铺失败: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
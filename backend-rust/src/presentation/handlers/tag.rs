@@ -0,0 +1,13 @@
+use crate::domain::models::tag_dto::TagResponse;
+use crate::presentation::state::AppState;
+use axum::{extract::State, http::StatusCode, response::Json};
+
+pub async fn get_tags(State(state): State<AppState>) -> Result<Json<Vec<TagResponse>>, StatusCode> {
+    match state.tag_service.get_tags().await {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => {
+            tracing::error!("获取标签列表失败: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use crate::config::auth::Claims;
+
+use axum::{extract::Request, http::StatusCode, middleware::Next, response::Response};
+
+/// 需挂在 `auth_middleware` 之后，依赖其写入的 Claims
+pub async fn admin_middleware(request: Request, next: Next) -> Result<Response, StatusCode> {
+    let is_admin = request
+        .extensions()
+        .get::<Claims>()
+        .map(|claims| claims.is_admin)
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+
+    if !is_admin {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    Ok(next.run(request).await)
+}
@@ -10,12 +10,35 @@ use tracing::warn;
 
 pub async fn auth_middleware(
     State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let token = bearer_token(&request).map(str::to_string);
+    authorize(&state, token, request, next).await
+}
+
+/// 仅用于事件流：浏览器的 EventSource 无法设置请求头，
+/// 因此在没有 Authorization 请求头时也接受 `access_token` 查询参数
+pub async fn event_stream_auth_middleware(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let token = bearer_token(&request)
+        .or_else(|| query_token(&request))
+        .map(str::to_string);
+    authorize(&state, token, request, next).await
+}
+
+async fn authorize(
+    state: &AppState,
+    token: Option<String>,
     mut request: Request,
     next: Next,
 ) -> Result<Response, StatusCode> {
-    let token = extract_bearer_token(&request).ok_or(StatusCode::UNAUTHORIZED)?;
+    let token = token.ok_or(StatusCode::UNAUTHORIZED)?;
 
-    match state.auth_service.verify_token(token) {
+    match state.auth_service.verify_token(&token) {
         Ok(claims) => {
             request.extensions_mut().insert(claims);
             let response = next.run(request).await;
@@ -28,10 +51,18 @@ pub async fn auth_middleware(
     }
 }
 
-fn extract_bearer_token(request: &Request) -> Option<&str> {
+fn bearer_token(request: &Request) -> Option<&str> {
     request
         .headers()
         .get(header::AUTHORIZATION)
         .and_then(|header| header.to_str().ok())
         .and_then(|value| value.strip_prefix("Bearer "))
 }
+
+fn query_token(request: &Request) -> Option<&str> {
+    request.uri().query().and_then(|query| {
+        query
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("access_token="))
+    })
+}
@@ -1,54 +1,188 @@
-use crate::config::{database::create_connection, Config};
+use crate::config::Config;
+use crate::config::StorageBackend;
+use crate::infrastructure::price_sources::create_sources;
 use crate::infrastructure::repositories::{
-    manufacturer_repository::ManufacturerRepository, model_repository::ModelRepository,
-    user_repository::UserRepository,
+    activity_repository::ActivityRepository, backup_repository::BackupRepository,
+    build_log_repository::BuildLogRepository, calendar_repository::CalendarRepository,
+    currency_repository::CurrencyRepository, export_repository::ExportRepository,
+    manufacturer_repository::ManufacturerRepository, model_image_repository::ModelImageRepository,
+    model_repository::ModelRepository, notification_repository::NotificationRepository,
+    price_watch_repository::PriceWatchRepository, search_repository::SearchRepository,
+    series_repository::SeriesRepository, shop_repository::ShopRepository,
+    tag_repository::TagRepository, user_repository::UserRepository,
 };
+use crate::infrastructure::storage::{create_storage, local::LOCAL_URL_PREFIX};
 use crate::presentation::{
-    handlers::{auth, manufacturer, model},
-    middleware::{auth::auth_middleware, cors::cors_layer},
+    handlers::{
+        activity, auth, backup, build_log, calendar, currency, events, export, image, import,
+        manufacturer, meta, model, notification, price_watch, search, series, shop, tag,
+    },
+    middleware::{
+        admin::admin_middleware,
+        auth::{auth_middleware, event_stream_auth_middleware},
+        cors::cors_layer,
+    },
     state::AppState,
 };
+use crate::services::build_log_service::MAX_ENTRY_IMAGES;
+use crate::services::event_hub::EventHub;
 use crate::services::{
-    auth_service::AuthService, manufacturer_service::ManufacturerService,
-    model_service::ModelService,
+    activity_service::ActivityService, auth_service::AuthService, backup_service::BackupService,
+    build_log_service::BuildLogService, calendar_service::CalendarService,
+    currency_service::CurrencyService, export_service::ExportService, image_service::ImageService,
+    import_service::ImportService, manufacturer_service::ManufacturerService,
+    model_service::ModelService, notification_service::NotificationService,
+    price_alert_service::PriceAlertService, price_watch_service::PriceWatchService,
+    search_service::SearchService, series_service::SeriesService, shop_service::ShopService,
+    status_transition_service::StatusTransitionService, tag_service::TagService,
 };
 use axum::{
-    middleware::from_fn_with_state,
-    routing::{get, post, put},
+    extract::DefaultBodyLimit,
+    middleware::{from_fn, from_fn_with_state},
+    routing::{delete, get, patch, post, put},
     Router,
 };
+use sea_orm::DatabaseConnection;
+use tower_http::services::ServeDir;
 
-pub async fn create_routes(config: Config) -> anyhow::Result<Router> {
-    let db = create_connection(&config.database.url, config.database.max_connections).await?;
-
+pub fn create_routes(config: Config, db: DatabaseConnection, events: EventHub) -> Router {
     let user_repository = UserRepository::new(db.clone());
     let model_repository = ModelRepository::new(db.clone());
-    let manufacturer_repository = ManufacturerRepository::new(db);
+    let manufacturer_repository = ManufacturerRepository::new(db.clone());
+    let series_repository = SeriesRepository::new(db.clone());
+    let tag_repository = TagRepository::new(db.clone());
+    let search_repository = SearchRepository::new(db.clone());
+    let export_repository = ExportRepository::new(db.clone());
+    let image_repository = ModelImageRepository::new(db.clone());
+    let build_log_repository = BuildLogRepository::new(db.clone());
+    let notification_repository = NotificationRepository::new(db.clone());
+    let activity_repository = ActivityRepository::new(db.clone());
+    let currency_repository = CurrencyRepository::new(db.clone());
+    let shop_repository = ShopRepository::new(db.clone());
+    let price_watch_repository = PriceWatchRepository::new(db.clone());
+    let calendar_repository = CalendarRepository::new(db.clone());
+    let backup_repository = BackupRepository::new(db);
 
     let auth_service = AuthService::new(
         user_repository,
         config.auth.jwt_secret.clone(),
         config.auth.jwt_expiration,
     );
-    let model_service = ModelService::new(model_repository);
-    let manufacturer_service = ManufacturerService::new(manufacturer_repository);
+    let image_storage = create_storage(&config.storage);
+    let currency_service = CurrencyService::new(currency_repository);
+    let price_alert_service =
+        PriceAlertService::new(notification_repository.clone(), currency_service.clone());
+    let model_service = ModelService::new(
+        model_repository.clone(),
+        currency_service.clone(),
+        image_storage.clone(),
+        events.clone(),
+        price_alert_service.clone(),
+    );
+    let import_service = ImportService::new(
+        model_repository,
+        model_service.clone(),
+        manufacturer_repository.clone(),
+        series_repository.clone(),
+    );
+    let status_transition_service =
+        StatusTransitionService::new(model_service.clone(), &config.status_transitions);
+    let image_service = ImageService::new(
+        image_repository,
+        image_storage.clone(),
+        config.storage.max_image_bytes,
+    );
+    let build_log_service = BuildLogService::new(
+        build_log_repository,
+        image_storage,
+        config.storage.max_image_bytes,
+    );
+    let manufacturer_service = ManufacturerService::new(manufacturer_repository, events.clone());
+    let series_service = SeriesService::new(series_repository);
+    let shop_service = ShopService::new(shop_repository);
+    let price_watch_service = PriceWatchService::new(
+        price_watch_repository,
+        currency_service.clone(),
+        create_sources(&config.scraper),
+        events.clone(),
+        price_alert_service,
+        &config.scraper,
+    );
+    let tag_service = TagService::new(tag_repository);
+    let search_service = SearchService::new(search_repository);
+    let notification_service = NotificationService::new(
+        notification_repository,
+        config.notifications.smtp.is_some(),
+        config.notifications.allow_private_webhooks,
+    );
+    let export_service = ExportService::new(export_repository, currency_service.clone());
+    let activity_service = ActivityService::new(activity_repository, currency_service.clone());
+    let backup_service = BackupService::new(backup_repository);
+    let calendar_service = CalendarService::new(calendar_repository);
 
     let app_state = AppState {
         auth_service,
+        activity_service,
+        currency_service,
         model_service,
+        status_transition_service,
         manufacturer_service,
+        series_service,
+        shop_service,
+        tag_service,
+        search_service,
+        price_watch_service,
+        import_service,
+        export_service,
+        backup_service,
+        image_service,
+        build_log_service,
+        calendar_service,
+        notification_service,
+        events,
     };
 
     let public_models_router = Router::new()
         .route("/models", get(model::get_models))
         .route("/models/:id", get(model::get_model_by_id))
-        .route("/models/:id/variants", get(model::get_model_variants));
+        .route("/models/:id/variants", get(model::get_model_variants))
+        .route("/models/:id/family", get(model::get_model_family))
+        .route("/models/:id/revisions", get(model::get_model_revisions))
+        .route("/models/:id/images", get(image::get_model_images))
+        .route("/models/:id/prices", get(model::get_model_prices))
+        .route(
+            "/models/:id/prices/by-shop",
+            get(model::get_model_prices_by_shop),
+        );
 
     let protected_models_router = Router::new()
         .route("/models", post(model::create_model))
         .route(
             "/models/:id",
-            put(model::update_model).delete(model::delete_model),
+            put(model::update_model)
+                .patch(model::patch_model)
+                .delete(model::delete_model),
+        )
+        .route("/models/:id/merge", post(model::merge_model))
+        .route("/models/:id/prices", post(model::add_model_price))
+        .route(
+            "/models/:id/images",
+            // 预留 64KB 给 multipart 表单的边界与其他字段
+            post(image::upload_model_image).layer(DefaultBodyLimit::max(
+                config.storage.max_image_bytes + 64 * 1024,
+            )),
+        )
+        .route(
+            "/models/:id/images/:image_id",
+            delete(image::delete_model_image),
+        )
+        .route(
+            "/models/:id/images/:image_id/primary",
+            put(image::set_primary_image),
+        )
+        .route(
+            "/models/:id/revisions/:rev/restore",
+            post(model::restore_model_revision),
         )
         .route(
             "/models/:id/favorite",
@@ -70,34 +204,161 @@ pub async fn create_routes(config: Config) -> anyhow::Result<Router> {
         .route("/manufacturers", post(manufacturer::create_manufacturer))
         .route(
             "/manufacturers/:id",
-            put(manufacturer::update_manufacturer).delete(manufacturer::delete_manufacturer),
+            put(manufacturer::update_manufacturer)
+                .patch(manufacturer::patch_manufacturer)
+                .delete(manufacturer::delete_manufacturer),
+        );
+
+    let public_series_router = Router::new()
+        .route("/series", get(series::get_series))
+        .route("/series/:id", get(series::get_series_by_id))
+        .route("/series/:id/models", get(series::get_series_models));
+
+    let protected_series_router = Router::new()
+        .route("/series", post(series::create_series))
+        .route(
+            "/series/:id",
+            put(series::update_series).delete(series::delete_series),
         );
 
+    let public_shops_router = Router::new()
+        .route("/shops", get(shop::get_shops))
+        .route("/shops/:id", get(shop::get_shop_by_id));
+
+    let protected_shops_router = Router::new()
+        .route("/shops", post(shop::create_shop))
+        .route(
+            "/shops/:id",
+            patch(shop::patch_shop).delete(shop::delete_shop),
+        );
+
+    let import_router = Router::new().route("/import/models", post(import::import_models));
+
+    let export_router = Router::new()
+        .route("/export/models", get(export::export_models))
+        .route("/user/export", get(export::export_user_models));
+
+    let activity_router = Router::new()
+        .route("/activity", get(activity::get_activity))
+        .route("/calendar", get(calendar::get_calendar));
+
     let user_router = Router::new()
         .route("/user/profile", get(auth::get_profile))
         .route("/user/favorites", get(model::get_favorites))
-        .route("/user/purchases", get(model::get_purchases));
+        .route("/user/favorites/:model_id", put(model::update_wishlist))
+        .route("/user/wishlist", get(model::get_wishlist))
+        .route("/user/collection/value", get(model::get_collection_value))
+        .route("/user/alerts", get(notification::get_alerts))
+        .route("/user/alerts/ack", post(notification::acknowledge_alerts))
+        .route(
+            "/user/notification-preferences",
+            get(notification::get_notification_preferences)
+                .put(notification::update_notification_preferences),
+        )
+        .route(
+            "/user/calendar-token",
+            get(calendar::get_calendar_token).post(calendar::regenerate_calendar_token),
+        )
+        .route("/user/purchases", get(model::get_purchases))
+        .route("/user/purchases/:model_id", put(model::update_purchase))
+        .route(
+            "/models/:id/price-watches",
+            get(price_watch::get_price_watches),
+        )
+        .route(
+            "/user/purchases/:model_id/log",
+            get(build_log::get_build_log)
+                .post(build_log::create_build_log_entry)
+                .layer(DefaultBodyLimit::max(
+                    config.storage.max_image_bytes * MAX_ENTRY_IMAGES + 64 * 1024,
+                )),
+        );
+
+    // 管理接口在登录校验之外还要求管理员身份
+    let admin_router = Router::new()
+        .route("/models/duplicates", get(model::get_duplicate_report))
+        .route("/admin/backup", get(backup::get_backup))
+        .route(
+            "/admin/status-transitions/run",
+            post(model::run_status_transitions),
+        )
+        .route(
+            "/models/:id/price-watches",
+            post(price_watch::create_price_watch),
+        )
+        .route(
+            "/models/:id/price-watches/preview",
+            post(price_watch::preview_price),
+        )
+        .route(
+            "/price-watches/:id",
+            patch(price_watch::patch_price_watch).delete(price_watch::delete_price_watch),
+        )
+        .route(
+            "/price-watches/:id/check",
+            post(price_watch::check_price_watch),
+        )
+        .route("/exchange-rates", post(currency::create_exchange_rate))
+        .route(
+            "/exchange-rates/import",
+            post(currency::import_exchange_rates),
+        )
+        .route(
+            "/exchange-rates/:id",
+            delete(currency::delete_exchange_rate),
+        )
+        .route(
+            "/admin/restore",
+            post(backup::restore_backup).layer(DefaultBodyLimit::max(backup::RESTORE_BODY_LIMIT)),
+        )
+        .route_layer(from_fn(admin_middleware));
 
     let auth_layer = from_fn_with_state(app_state.clone(), auth_middleware);
 
     let protected_router = Router::new()
         .merge(protected_models_router)
         .merge(protected_manufacturers_router)
+        .merge(protected_series_router)
+        .merge(protected_shops_router)
+        .merge(import_router)
+        .merge(export_router)
+        .merge(activity_router)
         .merge(user_router)
+        .merge(admin_router)
         .route_layer(auth_layer);
 
+    // 只有事件流允许通过 `access_token` 查询参数传递令牌
+    let event_stream_router = Router::new()
+        .route("/events", get(events::stream_events))
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            event_stream_auth_middleware,
+        ));
+
     let public_router = Router::new()
         .route("/health", get(auth::health))
         .route("/auth/login", post(auth::login))
+        .route("/meta/enums", get(meta::get_enums))
+        .route("/tags", get(tag::get_tags))
+        .route("/currencies", get(currency::get_currencies))
+        .route("/exchange-rates", get(currency::get_exchange_rates))
+        .route("/search", get(search::search))
+        // 手机日历订阅无法携带 JWT，由查询参数中的令牌鉴权
+        .route("/calendar.ics", get(calendar::get_calendar_feed))
         .merge(public_models_router)
-        .merge(public_manufacturers_router);
+        .merge(public_manufacturers_router)
+        .merge(public_series_router)
+        .merge(public_shops_router);
 
-    let api_router = public_router.merge(protected_router);
+    let api_router = public_router
+        .merge(protected_router)
+        .merge(event_stream_router);
 
-    let app = Router::new()
-        .nest("/api", api_router)
-        .layer(cors_layer())
-        .with_state(app_state);
+    let mut app = Router::new().nest("/api", api_router);
+    // 本地存储的图片由服务自身提供静态访问
+    if let StorageBackend::Local { dir } = &config.storage.backend {
+        app = app.nest_service(LOCAL_URL_PREFIX, ServeDir::new(dir));
+    }
 
-    Ok(app)
+    app.layer(cors_layer()).with_state(app_state)
 }
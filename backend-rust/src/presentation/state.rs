@@ -1,15 +1,52 @@
 use crate::infrastructure::repositories::{
-    manufacturer_repository::ManufacturerRepository, model_repository::ModelRepository,
-    user_repository::UserRepository,
+    activity_repository::ActivityRepository, backup_repository::BackupRepository,
+    build_log_repository::BuildLogRepository, calendar_repository::CalendarRepository,
+    currency_repository::CurrencyRepository, export_repository::ExportRepository,
+    manufacturer_repository::ManufacturerRepository, model_image_repository::ModelImageRepository,
+    model_repository::ModelRepository, notification_repository::NotificationRepository,
+    price_watch_repository::PriceWatchRepository, search_repository::SearchRepository,
+    series_repository::SeriesRepository, shop_repository::ShopRepository,
+    tag_repository::TagRepository, user_repository::UserRepository,
 };
+use crate::services::event_hub::EventHub;
 use crate::services::{
-    auth_service::AuthService, manufacturer_service::ManufacturerService,
-    model_service::ModelService,
+    activity_service::ActivityService, auth_service::AuthService, backup_service::BackupService,
+    build_log_service::BuildLogService, calendar_service::CalendarService,
+    currency_service::CurrencyService, export_service::ExportService, image_service::ImageService,
+    import_service::ImportService, manufacturer_service::ManufacturerService,
+    model_service::ModelService, notification_service::NotificationService,
+    price_watch_service::PriceWatchService, search_service::SearchService,
+    series_service::SeriesService, shop_service::ShopService,
+    status_transition_service::StatusTransitionService, tag_service::TagService,
 };
 
 #[derive(Clone)]
 pub struct AppState {
     pub auth_service: AuthService<UserRepository>,
-    pub model_service: ModelService<ModelRepository>,
+    pub activity_service: ActivityService<ActivityRepository, CurrencyRepository>,
+    pub currency_service: CurrencyService<CurrencyRepository>,
+    pub model_service: ModelService<ModelRepository, CurrencyRepository, NotificationRepository>,
+    pub status_transition_service:
+        StatusTransitionService<ModelRepository, CurrencyRepository, NotificationRepository>,
     pub manufacturer_service: ManufacturerService<ManufacturerRepository>,
+    pub series_service: SeriesService<SeriesRepository>,
+    pub shop_service: ShopService<ShopRepository>,
+    pub tag_service: TagService<TagRepository>,
+    pub build_log_service: BuildLogService<BuildLogRepository>,
+    pub calendar_service: CalendarService<CalendarRepository>,
+    pub image_service: ImageService<ModelImageRepository>,
+    pub notification_service: NotificationService<NotificationRepository>,
+    pub price_watch_service:
+        PriceWatchService<PriceWatchRepository, CurrencyRepository, NotificationRepository>,
+    pub search_service: SearchService<SearchRepository>,
+    pub backup_service: BackupService<BackupRepository>,
+    pub export_service: ExportService<ExportRepository, CurrencyRepository>,
+    pub import_service: ImportService<
+        ModelRepository,
+        CurrencyRepository,
+        NotificationRepository,
+        ManufacturerRepository,
+        SeriesRepository,
+    >,
+    pub events: EventHub,
 }
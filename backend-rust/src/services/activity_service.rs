@@ -0,0 +1,134 @@
+#![allow(dead_code)]
+
+use crate::domain::models::activity_dto::*;
+use crate::infrastructure::repositories::activity_repository::{
+    ActivityRepositoryTrait, ActivityRow,
+};
+use crate::infrastructure::repositories::currency_repository::CurrencyRepositoryTrait;
+use crate::services::currency_service::CurrencyService;
+use crate::services::error::{FieldError, ServiceError};
+
+/// 每页最多返回的动态数
+const MAX_PER_PAGE: u64 = 100;
+
+#[derive(Clone)]
+pub struct ActivityService<T, C>
+where
+    T: ActivityRepositoryTrait + Clone,
+    C: CurrencyRepositoryTrait + Clone,
+{
+    activity_repository: T,
+    currency_service: CurrencyService<C>,
+}
+
+impl<T, C> ActivityService<T, C>
+where
+    T: ActivityRepositoryTrait + Clone,
+    C: CurrencyRepositoryTrait + Clone,
+{
+    pub fn new(activity_repository: T, currency_service: CurrencyService<C>) -> Self {
+        Self {
+            activity_repository,
+            currency_service,
+        }
+    }
+
+    pub async fn get_activity(
+        &self,
+        params: ActivityParams,
+    ) -> anyhow::Result<ActivityListResponse> {
+        let mut kinds = Vec::new();
+        for value in params
+            .kinds
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+        {
+            let kind = ActivityKind::parse(value).ok_or_else(|| {
+                ServiceError::Validation(vec![FieldError::new(
+                    "type",
+                    format!(
+                        "未知的动态类型: {}，可选 model_created、favorited、purchased、price_added",
+                        value
+                    ),
+                )])
+            })?;
+            if !kinds.contains(&kind) {
+                kinds.push(kind);
+            }
+        }
+
+        let currency = self.currency_service.target(params.currency).await?;
+
+        let filter = ActivityFilter {
+            user_id: params.user_id,
+            model_id: params.model_id,
+            kinds,
+        };
+        let per_page = params.per_page.clamp(1, MAX_PER_PAGE);
+        let (rows, total) = self
+            .activity_repository
+            .find_activity(&filter, params.page, per_page)
+            .await?;
+
+        let mut entries: Vec<ActivityEntry> =
+            rows.into_iter().filter_map(convert_to_entry).collect();
+        if let Some(currency) = currency {
+            let rates = self
+                .currency_service
+                .rate_table(
+                    entries
+                        .iter()
+                        .filter_map(|e| e.currency.clone())
+                        .chain([currency.clone()]),
+                )
+                .await?;
+            for entry in &mut entries {
+                if let (Some(price), Some(from)) = (entry.price, entry.currency.as_deref()) {
+                    entry.converted =
+                        Some(rates.convert(price, from, &currency, entry.created_at.date()));
+                }
+            }
+        }
+
+        Ok(ActivityListResponse {
+            entries,
+            total,
+            page: params.page.max(1),
+            per_page,
+            total_pages: total.div_ceil(per_page),
+        })
+    }
+}
+
+fn convert_to_entry(row: ActivityRow) -> Option<ActivityEntry> {
+    let kind = ActivityKind::parse(&row.kind)?;
+    let actor = row.username.as_deref().unwrap_or("未知用户");
+    let summary = match kind {
+        ActivityKind::ModelCreated => format!("{} 添加了模型 {}", actor, row.model_name),
+        ActivityKind::Favorited => format!("{} 收藏了 {}", actor, row.model_name),
+        ActivityKind::Purchased => format!("{} 购买了 {}", actor, row.model_name),
+        ActivityKind::PriceAdded => format!(
+            "{} 为 {} 记录了价格 {} {}",
+            actor,
+            row.model_name,
+            row.price.unwrap_or_default(),
+            row.currency.as_deref().unwrap_or_default()
+        ),
+    };
+
+    Some(ActivityEntry {
+        kind,
+        user_id: row.user_id,
+        username: row.username,
+        model_id: row.model_id,
+        model_name: row.model_name,
+        price: row.price,
+        currency: row.currency,
+        converted: None,
+        summary,
+        created_at: row.created_at,
+    })
+}
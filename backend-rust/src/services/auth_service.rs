@@ -37,6 +37,7 @@ impl<T: UserRepositoryTrait + Clone> AuthService<T> {
         let token = auth::generate_jwt(
             &user.id.to_string(),
             &user.username,
+            user.is_admin,
             &self.jwt_secret,
             self.jwt_expiration,
         )?;
@@ -44,6 +45,7 @@ impl<T: UserRepositoryTrait + Clone> AuthService<T> {
         let user_response = UserResponse {
             id: user.id,
             username: user.username,
+            is_admin: user.is_admin,
         };
 
         Ok(LoginResponse {
@@ -0,0 +1,238 @@
+#![allow(dead_code)]
+
+use crate::domain::models::backup_dto::{
+    BackupArchive, BackupTable, RestoreSummary, RestoredTable, BACKUP_FORMAT_VERSION,
+};
+use crate::infrastructure::repositories::backup_repository::{
+    BackupRepositoryTrait, BACKUP_TABLES, REPLACED_TABLES,
+};
+use crate::services::error::{FieldError, ServiceError};
+
+#[derive(Clone)]
+pub struct BackupService<T>
+where
+    T: BackupRepositoryTrait + Clone,
+{
+    backup_repository: T,
+}
+
+impl<T> BackupService<T>
+where
+    T: BackupRepositoryTrait + Clone,
+{
+    pub fn new(backup_repository: T) -> Self {
+        Self { backup_repository }
+    }
+
+    pub async fn create_backup(
+        &self,
+        include_password_hashes: bool,
+    ) -> anyhow::Result<BackupArchive> {
+        let schema_version = self.current_schema_version().await?;
+
+        let mut tables = Vec::with_capacity(BACKUP_TABLES.len());
+        for name in BACKUP_TABLES {
+            let mut columns = self.backup_repository.table_columns(name).await?;
+            if name == "users" && !include_password_hashes {
+                columns.retain(|c| c != "password_hash");
+            }
+            let rows = self.backup_repository.dump_table(name, &columns).await?;
+            tables.push(BackupTable {
+                name: name.to_string(),
+                columns,
+                rows,
+            });
+        }
+
+        Ok(BackupArchive {
+            format_version: BACKUP_FORMAT_VERSION,
+            schema_version,
+            created_at: chrono::Utc::now().naive_utc(),
+            includes_password_hashes: include_password_hashes,
+            tables,
+        })
+    }
+
+    /// 恢复到空数据库：仅允许用户表中已有账号（如发起恢复的管理员），其余表必须为空。
+    /// 现有账号会被备份中的用户替换，备份必须包含非空的用户表
+    pub async fn restore(&self, archive: BackupArchive) -> anyhow::Result<RestoreSummary> {
+        if archive.format_version != BACKUP_FORMAT_VERSION {
+            return Err(ServiceError::Validation(vec![FieldError::new(
+                "format_version",
+                format!(
+                    "不支持的备份格式版本 {}，当前仅支持 {}",
+                    archive.format_version, BACKUP_FORMAT_VERSION
+                ),
+            )])
+            .into());
+        }
+
+        let schema_version = self.current_schema_version().await?;
+        if archive.schema_version != schema_version {
+            return Err(ServiceError::Conflict(format!(
+                "备份的数据库结构版本 {} 与当前版本 {} 不一致",
+                archive.schema_version, schema_version
+            ))
+            .into());
+        }
+
+        let tables = self.check_tables(archive.tables).await?;
+        let users_have_hashes = archived_users(&tables)?
+            .columns
+            .iter()
+            .any(|c| c == "password_hash");
+
+        for name in BACKUP_TABLES
+            .iter()
+            .filter(|name| !REPLACED_TABLES.contains(name))
+        {
+            if self.backup_repository.count_rows(name).await? > 0 {
+                return Err(ServiceError::Conflict(format!(
+                    "目标数据库不为空（表 {} 中已有数据），只能恢复到空数据库",
+                    name
+                ))
+                .into());
+            }
+        }
+
+        // 不含密码哈希的用户使用随机密码的哈希占位，无法直接登录
+        let placeholder_hash = if users_have_hashes {
+            None
+        } else {
+            Some(bcrypt::hash(
+                uuid::Uuid::new_v4().to_string(),
+                bcrypt::DEFAULT_COST,
+            )?)
+        };
+
+        self.backup_repository
+            .restore_tables(&tables, placeholder_hash.as_deref())
+            .await?;
+
+        Ok(RestoreSummary {
+            schema_version,
+            tables: tables
+                .iter()
+                .map(|t| RestoredTable {
+                    name: t.name.clone(),
+                    rows: t.rows.len(),
+                })
+                .collect(),
+        })
+    }
+
+    async fn current_schema_version(&self) -> anyhow::Result<String> {
+        self.backup_repository
+            .schema_version()
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("数据库尚未执行任何迁移"))
+    }
+
+    /// 校验表名、列名与行格式，并按外键依赖顺序重新排列
+    async fn check_tables(&self, tables: Vec<BackupTable>) -> anyhow::Result<Vec<BackupTable>> {
+        let mut errors = Vec::new();
+        let mut ordered = Vec::with_capacity(tables.len());
+
+        for table in tables {
+            let Some(position) = BACKUP_TABLES.iter().position(|name| *name == table.name) else {
+                errors.push(FieldError::new(
+                    "tables",
+                    format!("未知的表 {}", table.name),
+                ));
+                continue;
+            };
+            if ordered
+                .iter()
+                .any(|(_, t): &(usize, BackupTable)| t.name == table.name)
+            {
+                errors.push(FieldError::new(
+                    "tables",
+                    format!("表 {} 重复出现", table.name),
+                ));
+                continue;
+            }
+
+            let known = self.backup_repository.table_columns(&table.name).await?;
+            for column in table.columns.iter().filter(|c| !known.contains(c)) {
+                errors.push(FieldError::new(
+                    "tables",
+                    format!("表 {} 不存在列 {}", table.name, column),
+                ));
+            }
+            if table.rows.iter().any(|row| !row.is_object()) {
+                errors.push(FieldError::new(
+                    "tables",
+                    format!("表 {} 的行数据必须是 JSON 对象", table.name),
+                ));
+            }
+            ordered.push((position, table));
+        }
+
+        if !errors.is_empty() {
+            return Err(ServiceError::Validation(errors).into());
+        }
+        ordered.sort_by_key(|(position, _)| *position);
+        Ok(ordered.into_iter().map(|(_, table)| table).collect())
+    }
+}
+
+/// 备份中的用户表。恢复时会清空现有账号，缺少用户数据的备份恢复后将没有可登录的账号，直接拒绝
+fn archived_users(tables: &[BackupTable]) -> Result<&BackupTable, ServiceError> {
+    tables
+        .iter()
+        .find(|t| t.name == "users" && !t.rows.is_empty())
+        .ok_or_else(|| {
+            ServiceError::Validation(vec![FieldError::new(
+                "tables",
+                "备份中没有用户数据（users 表缺失或为空），恢复后将无法登录",
+            )])
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn table(name: &str, columns: &[&str], rows: Vec<serde_json::Value>) -> BackupTable {
+        BackupTable {
+            name: name.to_string(),
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            rows,
+        }
+    }
+
+    #[test]
+    fn archive_without_users_table_is_rejected() {
+        let tables = vec![table(
+            "manufacturers",
+            &["id", "name"],
+            vec![json!({"id": 1})],
+        )];
+        assert!(matches!(
+            archived_users(&tables),
+            Err(ServiceError::Validation(errors)) if errors[0].field == "tables"
+        ));
+    }
+
+    #[test]
+    fn archive_with_empty_users_table_is_rejected() {
+        let tables = vec![table("users", &["id", "username"], Vec::new())];
+        assert!(archived_users(&tables).is_err());
+    }
+
+    #[test]
+    fn archive_with_users_is_accepted() {
+        let tables = vec![
+            table("manufacturers", &["id", "name"], Vec::new()),
+            table(
+                "users",
+                &["id", "username"],
+                vec![json!({"id": 1, "username": "admin"})],
+            ),
+        ];
+        let users = archived_users(&tables).unwrap();
+        assert_eq!(users.rows.len(), 1);
+        assert!(!users.columns.iter().any(|c| c == "password_hash"));
+    }
+}
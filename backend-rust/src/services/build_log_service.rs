@@ -0,0 +1,252 @@
+#![allow(dead_code)]
+
+use crate::domain::entities::enums::BuildStage;
+use crate::domain::entities::{build_log_entry, build_log_image};
+use crate::domain::models::build_log_dto::*;
+use crate::infrastructure::repositories::build_log_repository::BuildLogRepositoryTrait;
+use crate::infrastructure::storage::SharedStorage;
+use crate::services::error::{FieldError, ServiceError};
+use crate::services::image_service::{remove_objects, store_image, StoredImage};
+use crate::services::import_service::{parse_date, parse_enum};
+use sea_orm::ActiveValue::{NotSet, Set};
+
+/// 单条日志最多附带的图片数
+pub const MAX_ENTRY_IMAGES: usize = 10;
+
+/// 单条日志记录的工时上限（小时）
+const MAX_ENTRY_HOURS: f64 = 24.0;
+
+/// 校验后的日志条目
+struct ParsedEntry {
+    entry_date: chrono::NaiveDate,
+    content: Option<String>,
+    hours_spent: Option<f64>,
+    stage: Option<BuildStage>,
+}
+
+#[derive(Clone)]
+pub struct BuildLogService<T>
+where
+    T: BuildLogRepositoryTrait + Clone,
+{
+    build_log_repository: T,
+    storage: SharedStorage,
+    max_image_bytes: usize,
+}
+
+impl<T> BuildLogService<T>
+where
+    T: BuildLogRepositoryTrait + Clone,
+{
+    pub fn new(build_log_repository: T, storage: SharedStorage, max_image_bytes: usize) -> Self {
+        Self {
+            build_log_repository,
+            storage,
+            max_image_bytes,
+        }
+    }
+
+    /// 用户未购买该模型时返回 404
+    pub async fn get_build_log(
+        &self,
+        user_id: i32,
+        model_id: i32,
+    ) -> anyhow::Result<BuildLogResponse> {
+        let purchase = self
+            .build_log_repository
+            .find_purchase(user_id, model_id)
+            .await?
+            .ok_or(ServiceError::NotFound)?;
+
+        let entries = self.build_log_repository.find_entries(purchase.id).await?;
+        let entry_ids: Vec<i32> = entries.iter().map(|e| e.id).collect();
+        let mut images = self.build_log_repository.find_images(entry_ids).await?;
+
+        let mut current_stage = None;
+        let mut total_hours = 0.0;
+        let entries = entries
+            .into_iter()
+            .map(|entry| {
+                let previous_stage = entry.stage.and(current_stage);
+                current_stage = entry.stage.or(current_stage);
+                total_hours += entry.hours_spent.unwrap_or_default();
+                BuildLogEntryResponse {
+                    images: images
+                        .remove(&entry.id)
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|image| self.convert_to_image_response(image))
+                        .collect(),
+                    id: entry.id,
+                    entry_date: entry.entry_date,
+                    content: entry.content,
+                    hours_spent: entry.hours_spent,
+                    stage: entry.stage,
+                    previous_stage,
+                    created_at: entry.created_at,
+                }
+            })
+            .collect();
+
+        Ok(BuildLogResponse {
+            model_id,
+            purchase_id: purchase.id,
+            current_stage,
+            total_hours,
+            entries,
+        })
+    }
+
+    /// 新增日志条目并返回完整日志；图片先写入存储，落库失败时清理
+    pub async fn add_entry(
+        &self,
+        user_id: i32,
+        model_id: i32,
+        form: CreateBuildLogEntryForm,
+    ) -> anyhow::Result<BuildLogResponse> {
+        let purchase = self
+            .build_log_repository
+            .find_purchase(user_id, model_id)
+            .await?
+            .ok_or(ServiceError::NotFound)?;
+        let parsed = parse_entry(&form)?;
+
+        let mut stored: Vec<StoredImage> = Vec::with_capacity(form.images.len());
+        for data in form.images {
+            match store_image(
+                &self.storage,
+                &format!("build-logs/{}", purchase.id),
+                data,
+                self.max_image_bytes,
+            )
+            .await
+            {
+                Ok(image) => stored.push(image),
+                Err(e) => {
+                    self.remove_stored(&stored).await;
+                    return Err(e);
+                }
+            }
+        }
+
+        let entry = build_log_entry::ActiveModel {
+            id: NotSet,
+            purchase_id: Set(purchase.id),
+            entry_date: Set(parsed.entry_date),
+            content: Set(parsed.content),
+            hours_spent: Set(parsed.hours_spent),
+            stage: Set(parsed.stage),
+            created_at: NotSet,
+        };
+        let images = stored
+            .iter()
+            .map(|image| build_log_image::ActiveModel {
+                id: NotSet,
+                entry_id: NotSet,
+                storage_key: Set(image.storage_key.clone()),
+                thumbnail_key: Set(image.thumbnail_key.clone()),
+                content_type: Set(image.content_type.clone()),
+                width: Set(image.width),
+                height: Set(image.height),
+                size_bytes: Set(image.size_bytes),
+                created_at: NotSet,
+            })
+            .collect();
+
+        if let Err(e) = self.build_log_repository.create_entry(entry, images).await {
+            self.remove_stored(&stored).await;
+            return Err(e.into());
+        }
+
+        self.get_build_log(user_id, model_id).await
+    }
+
+    async fn remove_stored(&self, stored: &[StoredImage]) {
+        let keys: Vec<&str> = stored
+            .iter()
+            .flat_map(|image| [image.storage_key.as_str(), image.thumbnail_key.as_str()])
+            .collect();
+        remove_objects(&self.storage, &keys).await;
+    }
+
+    fn convert_to_image_response(&self, image: build_log_image::Model) -> BuildLogImageResponse {
+        BuildLogImageResponse {
+            id: image.id,
+            url: self.storage.public_url(&image.storage_key),
+            thumbnail_url: self.storage.public_url(&image.thumbnail_key),
+            width: image.width,
+            height: image.height,
+        }
+    }
+}
+
+fn parse_entry(form: &CreateBuildLogEntryForm) -> Result<ParsedEntry, ServiceError> {
+    let mut errors = Vec::new();
+    let non_empty = |value: &Option<String>| {
+        value
+            .as_deref()
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+    };
+
+    let entry_date = match non_empty(&form.entry_date) {
+        Some(value) => parse_date(&value).unwrap_or_else(|| {
+            errors.push(FieldError::new("entry_date", "日期格式应为 YYYY-MM-DD"));
+            chrono::NaiveDate::default()
+        }),
+        None => chrono::Local::now().date_naive(),
+    };
+
+    let hours_spent = non_empty(&form.hours_spent).and_then(|value| match value.parse::<f64>() {
+        Ok(hours) if (0.0..=MAX_ENTRY_HOURS).contains(&hours) => Some(hours),
+        _ => {
+            errors.push(FieldError::new(
+                "hours_spent",
+                format!("工时应为 0 到 {} 之间的数字", MAX_ENTRY_HOURS),
+            ));
+            None
+        }
+    });
+
+    let stage = non_empty(&form.stage).and_then(|value| match parse_enum::<BuildStage>(&value) {
+        Ok(stage) => Some(stage),
+        Err(_) => {
+            errors.push(FieldError::new(
+                "stage",
+                format!("未知的制作阶段: {}", value),
+            ));
+            None
+        }
+    });
+
+    if form.images.len() > MAX_ENTRY_IMAGES {
+        errors.push(FieldError::new(
+            "images",
+            format!("每条日志最多上传 {} 张图片", MAX_ENTRY_IMAGES),
+        ));
+    }
+
+    let content = non_empty(&form.content);
+    if content.is_none()
+        && hours_spent.is_none()
+        && stage.is_none()
+        && form.images.is_empty()
+        && errors.is_empty()
+    {
+        errors.push(FieldError::new(
+            "content",
+            "请至少填写内容、工时、制作阶段或上传图片",
+        ));
+    }
+
+    if !errors.is_empty() {
+        return Err(ServiceError::Validation(errors));
+    }
+    Ok(ParsedEntry {
+        entry_date,
+        content,
+        hours_spent,
+        stage,
+    })
+}
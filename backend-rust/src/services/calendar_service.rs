@@ -0,0 +1,268 @@
+#![allow(dead_code)]
+
+use crate::domain::entities::{calendar_token, model};
+use crate::domain::models::calendar_dto::*;
+use crate::infrastructure::repositories::calendar_repository::CalendarRepositoryTrait;
+use crate::services::error::{FieldError, ServiceError};
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime};
+
+/// `/calendar` 缺省的时间范围：过去 30 天到未来 180 天
+const DEFAULT_PAST_DAYS: i64 = 30;
+const DEFAULT_FUTURE_DAYS: i64 = 180;
+/// 单次查询最多覆盖的天数
+const MAX_RANGE_DAYS: i64 = 731;
+/// 订阅源覆盖的时间范围：过去 90 天到未来一年
+const FEED_PAST_DAYS: i64 = 90;
+const FEED_FUTURE_DAYS: i64 = 365;
+/// RFC 5545 规定内容行不超过 75 个字节（不含换行）
+const ICS_LINE_OCTETS: usize = 75;
+
+#[derive(Clone)]
+pub struct CalendarService<T>
+where
+    T: CalendarRepositoryTrait + Clone,
+{
+    calendar_repository: T,
+}
+
+impl<T> CalendarService<T>
+where
+    T: CalendarRepositoryTrait + Clone,
+{
+    pub fn new(calendar_repository: T) -> Self {
+        Self {
+            calendar_repository,
+        }
+    }
+
+    /// 按月分组的发售日历，包含全部模型的开订与发售日期以及当前用户购买记录中的尾款、发货日期
+    pub async fn get_calendar(
+        &self,
+        user_id: i32,
+        params: CalendarParams,
+    ) -> anyhow::Result<CalendarResponse> {
+        let today = chrono::Local::now().date_naive();
+        let from = params
+            .from
+            .unwrap_or(today - Duration::days(DEFAULT_PAST_DAYS));
+        let to = params
+            .to
+            .unwrap_or(today + Duration::days(DEFAULT_FUTURE_DAYS));
+        if from > to {
+            return Err(ServiceError::Validation(vec![FieldError::new(
+                "to",
+                "结束日期不能早于起始日期",
+            )])
+            .into());
+        }
+        if (to - from).num_days() >= MAX_RANGE_DAYS {
+            return Err(ServiceError::Validation(vec![FieldError::new(
+                "to",
+                format!("查询范围不能超过 {} 天", MAX_RANGE_DAYS),
+            )])
+            .into());
+        }
+
+        let events = self.collect_events(user_id, from, to).await?;
+        let mut months: Vec<CalendarMonth> = Vec::new();
+        for event in events {
+            let month = format!("{:04}-{:02}", event.date.year(), event.date.month());
+            match months.last_mut() {
+                Some(last) if last.month == month => last.events.push(event),
+                _ => months.push(CalendarMonth {
+                    month,
+                    events: vec![event],
+                }),
+            }
+        }
+
+        Ok(CalendarResponse { from, to, months })
+    }
+
+    /// 当前订阅令牌，尚未生成时新建
+    pub async fn get_token(&self, user_id: i32) -> anyhow::Result<CalendarTokenResponse> {
+        let token = match self.calendar_repository.find_token_by_user(user_id).await? {
+            Some(token) => token,
+            None => {
+                self.calendar_repository
+                    .upsert_token(user_id, generate_token())
+                    .await?
+            }
+        };
+        Ok(convert_token(token))
+    }
+
+    /// 重新生成订阅令牌，旧的订阅地址随即失效
+    pub async fn regenerate_token(&self, user_id: i32) -> anyhow::Result<CalendarTokenResponse> {
+        let token = self
+            .calendar_repository
+            .upsert_token(user_id, generate_token())
+            .await?;
+        Ok(convert_token(token))
+    }
+
+    /// 渲染令牌所属用户的 iCalendar 订阅源，令牌无效时返回 `None`
+    pub async fn render_feed(&self, token: &str) -> anyhow::Result<Option<String>> {
+        let Some(token) = self.calendar_repository.find_token(token.trim()).await? else {
+            return Ok(None);
+        };
+
+        let today = chrono::Local::now().date_naive();
+        let events = self
+            .collect_events(
+                token.user_id,
+                today - Duration::days(FEED_PAST_DAYS),
+                today + Duration::days(FEED_FUTURE_DAYS),
+            )
+            .await?;
+        Ok(Some(render_ics(&events, chrono::Utc::now().naive_utc())))
+    }
+
+    /// 区间内的全部事件，按日期、类型、模型名称排序
+    async fn collect_events(
+        &self,
+        user_id: i32,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> anyhow::Result<Vec<CalendarEvent>> {
+        let in_range = |date: Option<NaiveDate>| date.filter(|d| *d >= from && *d <= to);
+        let mut events = Vec::new();
+
+        for model in self
+            .calendar_repository
+            .find_scheduled_models(from, to)
+            .await?
+        {
+            if let Some(date) = in_range(model.preorder_open_date) {
+                events.push(build_event(date, CalendarEventKind::PreorderOpen, &model));
+            }
+            if let Some(date) = in_range(model.release_date) {
+                events.push(build_event(date, CalendarEventKind::Release, &model));
+            }
+        }
+
+        for (purchase, model) in self
+            .calendar_repository
+            .find_scheduled_purchases(user_id, from, to)
+            .await?
+        {
+            if let Some(date) = in_range(purchase.payment_due_date) {
+                events.push(build_event(date, CalendarEventKind::PaymentDue, &model));
+            }
+            if let Some(date) = in_range(purchase.ship_date) {
+                events.push(build_event(date, CalendarEventKind::Ship, &model));
+            }
+        }
+
+        events.sort_by(|a, b| {
+            a.date
+                .cmp(&b.date)
+                .then(a.kind.cmp(&b.kind))
+                .then_with(|| a.model_name.cmp(&b.model_name))
+        });
+        Ok(events)
+    }
+}
+
+/// 64 位十六进制随机串
+fn generate_token() -> String {
+    format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    )
+}
+
+fn convert_token(token: calendar_token::Model) -> CalendarTokenResponse {
+    CalendarTokenResponse {
+        feed_path: format!("/api/calendar.ics?token={}", token.token),
+        token: token.token,
+        created_at: token.created_at,
+    }
+}
+
+fn build_event(date: NaiveDate, kind: CalendarEventKind, model: &model::Model) -> CalendarEvent {
+    CalendarEvent {
+        date,
+        kind,
+        model_id: model.id,
+        model_name: model.name.clone(),
+        status: model.status,
+        summary: format!("{}：{}", kind.label(), model.name),
+    }
+}
+
+/// 生成 RFC 5545 日历，每个事件是一条全天 VEVENT
+fn render_ics(events: &[CalendarEvent], now: NaiveDateTime) -> String {
+    let stamp = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//modelhub//calendar//ZH".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        "X-WR-CALNAME:高达模型日历".to_string(),
+    ];
+    for event in events {
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!(
+            "UID:{}-{}@modelhub",
+            event.kind.as_str(),
+            event.model_id
+        ));
+        lines.push(format!("DTSTAMP:{}", stamp));
+        lines.push(format!(
+            "DTSTART;VALUE=DATE:{}",
+            event.date.format("%Y%m%d")
+        ));
+        lines.push(format!(
+            "DTEND;VALUE=DATE:{}",
+            (event.date + Duration::days(1)).format("%Y%m%d")
+        ));
+        lines.push(format!("SUMMARY:{}", escape_text(&event.summary)));
+        lines.push(format!(
+            "DESCRIPTION:{}",
+            escape_text(&format!("状态：{}", event.status.label()))
+        ));
+        lines.push("TRANSP:TRANSPARENT".to_string());
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    let mut output = String::new();
+    for line in lines {
+        fold_line(&line, &mut output);
+    }
+    output
+}
+
+/// 转义 TEXT 值中的反斜杠、分号、逗号和换行
+fn escape_text(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// 按字节数折行，续行以一个空格开头，不在 UTF-8 字符中间断开
+fn fold_line(line: &str, output: &mut String) {
+    let mut width = 0;
+    for c in line.chars() {
+        let len = c.len_utf8();
+        if width + len > ICS_LINE_OCTETS {
+            output.push_str("\r\n ");
+            width = 1;
+        }
+        output.push(c);
+        width += len;
+    }
+    output.push_str("\r\n");
+}
@@ -0,0 +1,598 @@
+#![allow(dead_code)]
+
+use crate::domain::entities::exchange_rate;
+use crate::domain::models::currency_dto::*;
+use crate::infrastructure::repositories::currency_repository::CurrencyRepositoryTrait;
+use crate::services::error::{FieldError, ServiceError};
+use crate::services::import_service::parse_date;
+use sea_orm::ActiveValue::Set;
+use std::collections::{HashMap, HashSet};
+
+/// 未指定币种时的默认币种
+pub const DEFAULT_CURRENCY: &str = "CNY";
+
+/// 汇率列表每次最多返回的条数
+const MAX_RATE_LIMIT: u64 = 1000;
+
+/// 一次换算所需的汇率快照：币种对 -> 按生效日期升序的 (日期, 汇率)
+pub struct RateTable {
+    rates: HashMap<(String, String), Vec<(chrono::NaiveDate, f64)>>,
+    minor_units: HashMap<String, i16>,
+}
+
+impl RateTable {
+    /// 将 `amount` 从 `from` 换算为 `to`，使用 `date` 当天及之前最近生效的汇率。
+    /// 依次尝试直接汇率、反向汇率，以及经第三币种的交叉汇率
+    pub fn convert(
+        &self,
+        amount: f64,
+        from: &str,
+        to: &str,
+        date: chrono::NaiveDate,
+    ) -> ConvertedAmount {
+        let found = self.rate(from, to, date);
+        ConvertedAmount {
+            currency: to.to_string(),
+            amount: found.map(|(rate, _)| self.round(amount * rate, to)),
+            rate: found.map(|(rate, _)| rate),
+            rate_date: found.map(|(_, date)| date),
+        }
+    }
+
+    pub fn rate(
+        &self,
+        from: &str,
+        to: &str,
+        date: chrono::NaiveDate,
+    ) -> Option<(f64, chrono::NaiveDate)> {
+        if from == to {
+            return Some((1.0, date));
+        }
+        if let Some(found) = self.single_rate(from, to, date) {
+            return Some(found);
+        }
+
+        // 交叉汇率：在所有可用的中间币种中取汇率日期最近的组合
+        let pivots: HashSet<&str> = self
+            .rates
+            .keys()
+            .flat_map(|(base, quote)| [base.as_str(), quote.as_str()])
+            .filter(|c| *c != from && *c != to)
+            .collect();
+        pivots
+            .into_iter()
+            .filter_map(|pivot| {
+                let (first, first_date) = self.single_rate(from, pivot, date)?;
+                let (second, second_date) = self.single_rate(pivot, to, date)?;
+                Some((first * second, first_date.min(second_date)))
+            })
+            .max_by_key(|(_, date)| *date)
+    }
+
+    /// 直接汇率或反向汇率
+    fn single_rate(
+        &self,
+        from: &str,
+        to: &str,
+        date: chrono::NaiveDate,
+    ) -> Option<(f64, chrono::NaiveDate)> {
+        let direct = self.latest(from, to, date);
+        let inverse = self
+            .latest(to, from, date)
+            .map(|(rate, date)| (1.0 / rate, date));
+        match (direct, inverse) {
+            (Some(d), Some(i)) => Some(if i.1 > d.1 { i } else { d }),
+            (d, i) => d.or(i),
+        }
+    }
+
+    fn latest(
+        &self,
+        base: &str,
+        quote: &str,
+        date: chrono::NaiveDate,
+    ) -> Option<(f64, chrono::NaiveDate)> {
+        let history = self.rates.get(&(base.to_string(), quote.to_string()))?;
+        let index = history.partition_point(|(effective, _)| *effective <= date);
+        history
+            .get(index.checked_sub(1)?)
+            .map(|(effective, rate)| (*rate, *effective))
+    }
+
+    /// 按币种的小数位数取整
+    pub fn round(&self, amount: f64, currency: &str) -> f64 {
+        let units = self.minor_units.get(currency).copied().unwrap_or(2);
+        let factor = 10_f64.powi(units as i32);
+        (amount * factor).round() / factor
+    }
+}
+
+#[derive(Clone)]
+pub struct CurrencyService<T>
+where
+    T: CurrencyRepositoryTrait + Clone,
+{
+    currency_repository: T,
+}
+
+impl<T> CurrencyService<T>
+where
+    T: CurrencyRepositoryTrait + Clone,
+{
+    pub fn new(currency_repository: T) -> Self {
+        Self {
+            currency_repository,
+        }
+    }
+
+    pub async fn get_currencies(&self) -> anyhow::Result<Vec<CurrencyResponse>> {
+        let currencies = self.currency_repository.find_currencies().await?;
+        Ok(currencies
+            .into_iter()
+            .map(|c| CurrencyResponse {
+                code: c.code,
+                name: c.name,
+                minor_units: c.minor_units,
+            })
+            .collect())
+    }
+
+    /// 规范化币种代码（去除空白并转为大写），未登记的币种返回字段级校验错误
+    pub async fn normalize(&self, code: &str, field: &'static str) -> anyhow::Result<String> {
+        let code = code.trim().to_uppercase();
+        if self
+            .currency_repository
+            .find_currency(&code)
+            .await?
+            .is_none()
+        {
+            return Err(ServiceError::Validation(vec![FieldError::new(
+                field,
+                format!(
+                    "不支持的币种 {}，应为 ISO 4217 代码，如 CNY、JPY、USD",
+                    code
+                ),
+            )])
+            .into());
+        }
+        Ok(code)
+    }
+
+    /// 解析 `?currency=` 参数，未传时返回 `None`
+    pub async fn target(&self, currency: Option<String>) -> anyhow::Result<Option<String>> {
+        match currency.filter(|c| !c.trim().is_empty()) {
+            Some(code) => Ok(Some(self.normalize(&code, "currency").await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// 加载涉及 `currencies` 的全部已生效汇率
+    pub async fn rate_table<I>(&self, currencies: I) -> anyhow::Result<RateTable>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut currencies: Vec<String> = currencies.into_iter().collect();
+        currencies.sort();
+        currencies.dedup();
+
+        let today = chrono::Utc::now().date_naive();
+        let mut rates: HashMap<(String, String), Vec<(chrono::NaiveDate, f64)>> = HashMap::new();
+        for rate in self
+            .currency_repository
+            .find_rates_involving(currencies, today)
+            .await?
+        {
+            rates
+                .entry((rate.base_currency, rate.quote_currency))
+                .or_default()
+                .push((rate.effective_date, rate.rate));
+        }
+        let minor_units = self
+            .currency_repository
+            .find_currencies()
+            .await?
+            .into_iter()
+            .map(|c| (c.code, c.minor_units))
+            .collect();
+
+        Ok(RateTable { rates, minor_units })
+    }
+
+    pub async fn get_rates(
+        &self,
+        params: ExchangeRateListParams,
+    ) -> anyhow::Result<Vec<ExchangeRateResponse>> {
+        let normalize = |code: Option<String>| {
+            code.map(|c| c.trim().to_uppercase())
+                .filter(|c| !c.is_empty())
+        };
+        let rates = self
+            .currency_repository
+            .find_rates(
+                normalize(params.base),
+                normalize(params.quote),
+                params.until,
+                params.limit.clamp(1, MAX_RATE_LIMIT),
+            )
+            .await?;
+        Ok(rates.into_iter().map(convert_rate).collect())
+    }
+
+    pub async fn create_rate(
+        &self,
+        request: CreateExchangeRateRequest,
+    ) -> anyhow::Result<ExchangeRateResponse> {
+        let known = self.known_currencies().await?;
+        let rate = check_rate(request, "manual", &known)
+            .map_err(|message| ServiceError::Validation(vec![FieldError::new("rate", message)]))?;
+        let (base, quote, date) = (
+            rate.base_currency.clone(),
+            rate.quote_currency.clone(),
+            rate.effective_date,
+        );
+
+        self.currency_repository
+            .upsert_rates(vec![to_active_model(rate)])
+            .await?;
+        let saved = self
+            .currency_repository
+            .find_rates(Some(base), Some(quote), Some(date), 1)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or(ServiceError::NotFound)?;
+        Ok(convert_rate(saved))
+    }
+
+    /// 逐条校验后写入通过的汇率，失败的条目在响应中列出
+    pub async fn import_rates(
+        &self,
+        request: ImportExchangeRatesRequest,
+    ) -> anyhow::Result<ImportExchangeRatesResponse> {
+        let source = request
+            .source
+            .filter(|s| !s.trim().is_empty())
+            .unwrap_or_else(|| "import".to_string());
+        let known = self.known_currencies().await?;
+        let mut valid = Vec::new();
+        let mut errors = Vec::new();
+
+        for (index, rate) in request.rates.into_iter().enumerate() {
+            match check_rate(rate, &source, &known) {
+                Ok(rate) => valid.push(rate),
+                Err(message) => errors.push(ExchangeRateImportError {
+                    line: index + 1,
+                    message,
+                }),
+            }
+        }
+
+        if let Some(csv_text) = request.csv {
+            let (rates, csv_errors) = parse_csv_rates(&csv_text);
+            errors.extend(csv_errors);
+            for (line, rate) in rates {
+                match check_rate(rate, &source, &known) {
+                    Ok(rate) => valid.push(rate),
+                    Err(message) => errors.push(ExchangeRateImportError { line, message }),
+                }
+            }
+        }
+
+        // 同一批次内重复的币种对与日期以最后一条为准，避免批量写入冲突
+        let mut deduplicated: HashMap<
+            (String, String, chrono::NaiveDate),
+            CreateExchangeRateRequest,
+        > = HashMap::new();
+        for rate in valid {
+            deduplicated.insert(
+                (
+                    rate.base_currency.clone(),
+                    rate.quote_currency.clone(),
+                    rate.effective_date,
+                ),
+                rate,
+            );
+        }
+
+        let imported = self
+            .currency_repository
+            .upsert_rates(deduplicated.into_values().map(to_active_model).collect())
+            .await?;
+        Ok(ImportExchangeRatesResponse { imported, errors })
+    }
+
+    pub async fn delete_rate(&self, id: i32) -> anyhow::Result<bool> {
+        Ok(self.currency_repository.delete_rate(id).await? > 0)
+    }
+
+    async fn known_currencies(&self) -> anyhow::Result<HashSet<String>> {
+        Ok(self
+            .currency_repository
+            .find_currencies()
+            .await?
+            .into_iter()
+            .map(|c| c.code)
+            .collect())
+    }
+}
+
+/// 规范化并校验一条汇率，未指定来源时使用 `default_source`
+fn check_rate(
+    mut rate: CreateExchangeRateRequest,
+    default_source: &str,
+    known: &HashSet<String>,
+) -> Result<CreateExchangeRateRequest, String> {
+    rate.base_currency = rate.base_currency.trim().to_uppercase();
+    rate.quote_currency = rate.quote_currency.trim().to_uppercase();
+    for code in [&rate.base_currency, &rate.quote_currency] {
+        if !known.contains(code) {
+            return Err(format!("不支持的币种 {}", code));
+        }
+    }
+    if rate.base_currency == rate.quote_currency {
+        return Err("基准币种与报价币种不能相同".to_string());
+    }
+    if !rate.rate.is_finite() || rate.rate <= 0.0 {
+        return Err("汇率必须大于 0".to_string());
+    }
+    if rate.source.as_deref().is_none_or(|s| s.trim().is_empty()) {
+        rate.source = Some(default_source.to_string());
+    }
+    Ok(rate)
+}
+
+/// 解析汇率 CSV，返回 (行号, 汇率) 与无法解析的行
+fn parse_csv_rates(
+    text: &str,
+) -> (
+    Vec<(usize, CreateExchangeRateRequest)>,
+    Vec<ExchangeRateImportError>,
+) {
+    let mut rates = Vec::new();
+    let mut errors = Vec::new();
+    // Excel 导出的 CSV 常带 BOM
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(text.trim_start_matches('\u{feff}').as_bytes());
+    let headers = match reader.headers() {
+        Ok(headers) => headers.clone(),
+        Err(e) => {
+            errors.push(ExchangeRateImportError {
+                line: 1,
+                message: format!("无法解析 CSV 表头: {}", e),
+            });
+            return (rates, errors);
+        }
+    };
+    let column = |name: &str| headers.iter().position(|h| h == name);
+    let (Some(date_col), Some(base_col), Some(quote_col), Some(rate_col)) = (
+        column("effective_date"),
+        column("base_currency"),
+        column("quote_currency"),
+        column("rate"),
+    ) else {
+        errors.push(ExchangeRateImportError {
+            line: 1,
+            message: "CSV 表头必须包含 effective_date、base_currency、quote_currency、rate"
+                .to_string(),
+        });
+        return (rates, errors);
+    };
+    let source_col = column("source");
+
+    for (index, record) in reader.records().enumerate() {
+        // 表头为第 1 行
+        let line = index + 2;
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => {
+                errors.push(ExchangeRateImportError {
+                    line,
+                    message: format!("无法解析 CSV: {}", e),
+                });
+                continue;
+            }
+        };
+        let field = |col: usize| record.get(col).unwrap_or_default();
+
+        let Some(effective_date) = parse_date(field(date_col)) else {
+            errors.push(ExchangeRateImportError {
+                line,
+                message: format!("无法识别的日期: {}", field(date_col)),
+            });
+            continue;
+        };
+        let Ok(rate) = field(rate_col).parse::<f64>() else {
+            errors.push(ExchangeRateImportError {
+                line,
+                message: format!("无法识别的汇率: {}", field(rate_col)),
+            });
+            continue;
+        };
+        rates.push((
+            line,
+            CreateExchangeRateRequest {
+                base_currency: field(base_col).to_string(),
+                quote_currency: field(quote_col).to_string(),
+                rate,
+                effective_date,
+                source: source_col
+                    .map(|col| field(col).to_string())
+                    .filter(|s| !s.is_empty()),
+            },
+        ));
+    }
+    (rates, errors)
+}
+
+fn to_active_model(rate: CreateExchangeRateRequest) -> exchange_rate::ActiveModel {
+    exchange_rate::ActiveModel {
+        base_currency: Set(rate.base_currency),
+        quote_currency: Set(rate.quote_currency),
+        rate: Set(rate.rate),
+        effective_date: Set(rate.effective_date),
+        source: Set(rate.source),
+        created_at: Set(chrono::Utc::now().naive_utc()),
+        ..Default::default()
+    }
+}
+
+fn convert_rate(rate: exchange_rate::Model) -> ExchangeRateResponse {
+    ExchangeRateResponse {
+        id: rate.id,
+        base_currency: rate.base_currency,
+        quote_currency: rate.quote_currency,
+        rate: rate.rate,
+        effective_date: rate.effective_date,
+        source: rate.source,
+        created_at: rate.created_at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn day(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, month, day).unwrap()
+    }
+
+    /// 每项为 (基准币种, 报价币种, 生效日期, 汇率)，同一币种对需按日期升序给出
+    fn table(rates: &[(&str, &str, NaiveDate, f64)]) -> RateTable {
+        let mut grouped: HashMap<(String, String), Vec<(NaiveDate, f64)>> = HashMap::new();
+        for (base, quote, date, rate) in rates {
+            grouped
+                .entry((base.to_string(), quote.to_string()))
+                .or_default()
+                .push((*date, *rate));
+        }
+        RateTable {
+            rates: grouped,
+            minor_units: HashMap::from([("CNY".to_string(), 2), ("JPY".to_string(), 0)]),
+        }
+    }
+
+    #[test]
+    fn uses_latest_rate_effective_on_or_before_date() {
+        let rates = table(&[
+            ("USD", "CNY", day(1, 1), 7.0),
+            ("USD", "CNY", day(6, 1), 7.2),
+            ("USD", "CNY", day(12, 1), 7.5),
+        ]);
+
+        assert_eq!(rates.rate("USD", "CNY", day(7, 15)), Some((7.2, day(6, 1))));
+        assert_eq!(rates.rate("USD", "CNY", day(6, 1)), Some((7.2, day(6, 1))));
+        assert_eq!(
+            rates.rate("USD", "CNY", day(12, 31)),
+            Some((7.5, day(12, 1)))
+        );
+
+        let converted = rates.convert(10.0, "USD", "CNY", day(7, 15));
+        assert_eq!(converted.amount, Some(72.0));
+        assert_eq!(converted.rate_date, Some(day(6, 1)));
+    }
+
+    #[test]
+    fn no_rate_before_first_effective_date() {
+        let rates = table(&[("USD", "CNY", day(6, 1), 7.2)]);
+
+        let converted = rates.convert(10.0, "USD", "CNY", day(5, 31));
+        assert_eq!(converted.currency, "CNY");
+        assert_eq!(converted.amount, None);
+        assert_eq!(converted.rate, None);
+        assert_eq!(converted.rate_date, None);
+    }
+
+    #[test]
+    fn prefers_newer_of_direct_and_inverse_rate() {
+        let rates = table(&[
+            ("USD", "JPY", day(1, 1), 150.0),
+            ("JPY", "USD", day(5, 1), 0.008),
+        ]);
+
+        // 反向汇率更新，取其倒数
+        let (rate, date) = rates.rate("USD", "JPY", day(6, 1)).unwrap();
+        assert!((rate - 125.0).abs() < 1e-9);
+        assert_eq!(date, day(5, 1));
+        // 反向汇率尚未生效时使用直接汇率
+        assert_eq!(
+            rates.rate("USD", "JPY", day(3, 1)),
+            Some((150.0, day(1, 1)))
+        );
+        // 反方向换算时直接汇率即为更新的那条
+        assert_eq!(
+            rates.rate("JPY", "USD", day(6, 1)),
+            Some((0.008, day(5, 1)))
+        );
+    }
+
+    #[test]
+    fn direct_rate_wins_when_dates_are_equal() {
+        let rates = table(&[
+            ("USD", "CNY", day(3, 1), 7.1),
+            ("CNY", "USD", day(3, 1), 0.125),
+        ]);
+
+        assert_eq!(rates.rate("USD", "CNY", day(3, 1)), Some((7.1, day(3, 1))));
+    }
+
+    #[test]
+    fn cross_rate_uses_pivot_with_most_recent_rates() {
+        let rates = table(&[
+            // 经 USD：两段汇率都在 3 月生效
+            ("EUR", "USD", day(3, 1), 1.1),
+            ("USD", "CNY", day(3, 10), 7.2),
+            // 经 HKD：其中一段只有 1 月的汇率，整体更旧
+            ("EUR", "HKD", day(1, 1), 8.5),
+            ("HKD", "CNY", day(5, 1), 0.92),
+        ]);
+
+        let (rate, date) = rates.rate("EUR", "CNY", day(6, 1)).unwrap();
+        assert!((rate - 1.1 * 7.2).abs() < 1e-9);
+        assert_eq!(date, day(3, 1));
+    }
+
+    #[test]
+    fn cross_rate_can_combine_inverse_rates() {
+        let rates = table(&[
+            ("USD", "EUR", day(2, 1), 0.9),
+            ("USD", "CNY", day(2, 1), 7.2),
+        ]);
+
+        let (rate, _) = rates.rate("EUR", "CNY", day(6, 1)).unwrap();
+        assert!((rate - 7.2 / 0.9).abs() < 1e-9);
+    }
+
+    #[test]
+    fn missing_rate_returns_none() {
+        let rates = table(&[("USD", "CNY", day(1, 1), 7.2)]);
+
+        assert_eq!(rates.rate("GBP", "CNY", day(6, 1)), None);
+        assert_eq!(rates.convert(5.0, "GBP", "CNY", day(6, 1)).amount, None);
+    }
+
+    #[test]
+    fn same_currency_needs_no_rate() {
+        let rates = table(&[]);
+
+        assert_eq!(rates.rate("CNY", "CNY", day(6, 1)), Some((1.0, day(6, 1))));
+        assert_eq!(
+            rates.convert(12.345, "CNY", "CNY", day(6, 1)).amount,
+            Some(12.35)
+        );
+    }
+
+    #[test]
+    fn rounds_to_currency_minor_units() {
+        let rates = table(&[("CNY", "JPY", day(1, 1), 21.37)]);
+
+        assert_eq!(
+            rates.convert(10.0, "CNY", "JPY", day(6, 1)).amount,
+            Some(214.0)
+        );
+        // 未登记的币种按两位小数取整
+        assert_eq!(rates.round(1.005_1, "XYZ"), 1.01);
+    }
+}
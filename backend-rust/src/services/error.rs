@@ -0,0 +1,44 @@
+use crate::domain::models::model_dto::DuplicateCandidate;
+use serde::Serialize;
+use thiserror::Error;
+
+/// 服务层中需要映射为特定HTTP状态码的业务错误，其余错误统一按内部错误处理
+#[derive(Debug, Error)]
+pub enum ServiceError {
+    #[error("资源不存在")]
+    NotFound,
+    #[error("资源已被其他人修改，请刷新后重试")]
+    PreconditionFailed,
+    #[error("参数校验失败")]
+    Validation(Vec<FieldError>),
+    #[error("存在疑似重复的模型")]
+    PossibleDuplicates(Vec<DuplicateCandidate>),
+    /// 请求与当前数据状态冲突，消息直接返回给前端
+    #[error("{0}")]
+    Conflict(String),
+}
+
+/// 字段级校验错误，原样返回给前端用于在表单对应字段下提示
+#[derive(Debug, Serialize)]
+pub struct FieldError {
+    pub field: &'static str,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            field,
+            message: message.into(),
+        }
+    }
+}
+
+/// 转换仓储层更新时的错误：记录不存在对应 404，乐观锁校验失败（`RecordNotUpdated`）对应 412
+pub fn map_update_error(err: sea_orm::DbErr) -> anyhow::Error {
+    match err {
+        sea_orm::DbErr::RecordNotFound(_) => ServiceError::NotFound.into(),
+        sea_orm::DbErr::RecordNotUpdated => ServiceError::PreconditionFailed.into(),
+        other => other.into(),
+    }
+}
@@ -0,0 +1,239 @@
+#![allow(dead_code)]
+
+use crate::domain::models::event_dto::{ChangeEvent, ChangeEventKind};
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
+
+/// 每个订阅者可积压的事件数，超过后该订阅者需要重新同步
+const CHANNEL_CAPACITY: usize = 1024;
+/// 保留最近的事件数，用于断线重连时按 `Last-Event-ID` 补发
+const REPLAY_CAPACITY: usize = 1000;
+
+/// 进程内的数据变更广播，服务层在写入成功后发布事件，SSE 连接订阅
+#[derive(Clone)]
+pub struct EventHub {
+    inner: Arc<EventHubInner>,
+}
+
+struct EventHubInner {
+    sender: broadcast::Sender<Arc<ChangeEvent>>,
+    recent: Mutex<VecDeque<Arc<ChangeEvent>>>,
+    next_id: AtomicU64,
+}
+
+/// 订阅结果：先发送 `backlog`，再转发 `receiver` 中的新事件
+pub struct EventSubscription {
+    pub backlog: Vec<Arc<ChangeEvent>>,
+    /// `Last-Event-ID` 对应的事件已不在补发缓冲中（或来自重启前的进程），客户端需要重新拉取数据
+    pub resync: bool,
+    pub receiver: broadcast::Receiver<Arc<ChangeEvent>>,
+}
+
+impl EventHub {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        // 以启动时间的毫秒数作为起始 ID，重启后的 ID 大于重启前发出的 ID
+        let first_id = chrono::Utc::now().timestamp_millis().max(1) as u64;
+        Self {
+            inner: Arc::new(EventHubInner {
+                sender,
+                recent: Mutex::new(VecDeque::with_capacity(REPLAY_CAPACITY)),
+                next_id: AtomicU64::new(first_id),
+            }),
+        }
+    }
+
+    /// 发布广播事件，数据无法序列化时只记录日志
+    pub fn publish<D: Serialize>(
+        &self,
+        kind: ChangeEventKind,
+        entity_id: i32,
+        actor_id: Option<i32>,
+        data: &D,
+    ) {
+        self.publish_to(kind, entity_id, actor_id, None, data);
+    }
+
+    /// `recipient_id` 非空时只推送给该用户
+    pub fn publish_to<D: Serialize>(
+        &self,
+        kind: ChangeEventKind,
+        entity_id: i32,
+        actor_id: Option<i32>,
+        recipient_id: Option<i32>,
+        data: &D,
+    ) {
+        let data = match serde_json::to_value(data) {
+            Ok(data) => data,
+            Err(e) => {
+                tracing::error!("序列化变更事件失败: {:?}", e);
+                return;
+            }
+        };
+
+        // 在锁内分配 ID 并发送，保证补发缓冲与广播的顺序一致
+        let mut recent = self.lock_recent();
+        let event = Arc::new(ChangeEvent {
+            id: self.inner.next_id.fetch_add(1, Ordering::Relaxed),
+            kind,
+            entity_id,
+            actor_id,
+            data,
+            created_at: chrono::Utc::now().naive_utc(),
+            recipient_id,
+        });
+        if recent.len() == REPLAY_CAPACITY {
+            recent.pop_front();
+        }
+        recent.push_back(event.clone());
+        // 没有订阅者时发送失败，可忽略
+        let _ = self.inner.sender.send(event);
+    }
+
+    pub fn subscribe(&self, last_event_id: Option<u64>) -> EventSubscription {
+        let recent = self.lock_recent();
+        let receiver = self.inner.sender.subscribe();
+
+        let Some(last_event_id) = last_event_id else {
+            return EventSubscription {
+                backlog: Vec::new(),
+                resync: false,
+                receiver,
+            };
+        };
+
+        let next_id = self.inner.next_id.load(Ordering::Relaxed);
+        let oldest_id = recent.front().map_or(next_id, |event| event.id);
+        // `Last-Event-ID` 由客户端提供，可能是任意值
+        let resync = last_event_id.saturating_add(1) < oldest_id || last_event_id >= next_id;
+        let backlog = if resync {
+            Vec::new()
+        } else {
+            recent
+                .iter()
+                .filter(|event| event.id > last_event_id)
+                .cloned()
+                .collect()
+        };
+
+        EventSubscription {
+            backlog,
+            resync,
+            receiver,
+        }
+    }
+
+    fn lock_recent(&self) -> std::sync::MutexGuard<'_, VecDeque<Arc<ChangeEvent>>> {
+        // 持锁期间不会 panic，中毒时直接沿用内部数据
+        self.inner
+            .recent
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Default for EventHub {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn publish(hub: &EventHub, count: usize) -> Vec<u64> {
+        let first = hub.inner.next_id.load(Ordering::Relaxed);
+        for entity_id in 0..count {
+            hub.publish(
+                ChangeEventKind::ModelCreated,
+                entity_id as i32,
+                None,
+                &json!({}),
+            );
+        }
+        (first..first + count as u64).collect()
+    }
+
+    fn ids(subscription: &EventSubscription) -> Vec<u64> {
+        subscription.backlog.iter().map(|event| event.id).collect()
+    }
+
+    #[test]
+    fn subscribe_without_last_event_id_has_no_backlog() {
+        let hub = EventHub::new();
+        publish(&hub, 3);
+
+        let subscription = hub.subscribe(None);
+        assert!(subscription.backlog.is_empty());
+        assert!(!subscription.resync);
+    }
+
+    #[test]
+    fn last_event_id_inside_buffer_replays_later_events() {
+        let hub = EventHub::new();
+        let published = publish(&hub, 5);
+
+        let subscription = hub.subscribe(Some(published[1]));
+        assert_eq!(ids(&subscription), published[2..]);
+        assert!(!subscription.resync);
+
+        // 已收到最新事件时无需补发
+        let subscription = hub.subscribe(Some(published[4]));
+        assert!(subscription.backlog.is_empty());
+        assert!(!subscription.resync);
+    }
+
+    #[test]
+    fn last_event_id_older_than_buffer_requires_resync() {
+        let hub = EventHub::new();
+        let published = publish(&hub, REPLAY_CAPACITY + 2);
+        let oldest = published[2];
+
+        let subscription = hub.subscribe(Some(published[0]));
+        assert!(subscription.resync);
+        assert!(subscription.backlog.is_empty());
+
+        // 紧挨着缓冲中最早事件的 ID 仍可完整补发
+        let subscription = hub.subscribe(Some(oldest - 1));
+        assert!(!subscription.resync);
+        assert_eq!(subscription.backlog.len(), REPLAY_CAPACITY);
+        assert_eq!(subscription.backlog[0].id, oldest);
+    }
+
+    #[test]
+    fn last_event_id_from_the_future_requires_resync() {
+        let hub = EventHub::new();
+        let published = publish(&hub, 3);
+
+        let subscription = hub.subscribe(Some(published[2] + 1));
+        assert!(subscription.resync);
+        assert!(subscription.backlog.is_empty());
+
+        // 客户端可传任意值，不能溢出
+        let subscription = hub.subscribe(Some(u64::MAX));
+        assert!(subscription.resync);
+    }
+
+    #[test]
+    fn last_event_id_from_before_restart_requires_resync() {
+        let before_restart = EventHub::new();
+        let published = publish(&before_restart, 3);
+
+        // 模拟重启：新进程的起始 ID 按启动时间计算，大于重启前发出的 ID，补发缓冲为空
+        let hub = EventHub::new();
+        hub.inner
+            .next_id
+            .fetch_max(published[2] + 10, Ordering::Relaxed);
+        let subscription = hub.subscribe(Some(published[1]));
+        assert!(subscription.resync);
+        assert!(subscription.backlog.is_empty());
+
+        let subscription = hub.subscribe(Some(0));
+        assert!(subscription.resync);
+    }
+}
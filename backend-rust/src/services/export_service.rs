@@ -0,0 +1,333 @@
+#![allow(dead_code)]
+
+use crate::domain::models::export_dto::{ExportFormat, ExportRow, ExportScope};
+use crate::domain::models::model_dto::ModelFilter;
+use crate::infrastructure::repositories::currency_repository::CurrencyRepositoryTrait;
+use crate::infrastructure::repositories::export_repository::ExportRepositoryTrait;
+use crate::services::currency_service::CurrencyService;
+use futures::{Stream, StreamExt, TryStreamExt};
+
+/// 每批从数据库读取的模型数
+const EXPORT_BATCH_SIZE: u64 = 500;
+
+/// 目录导出的列，与批量导入的目标字段同名
+const CATALOG_COLUMNS: [&str; 13] = [
+    "id",
+    "name",
+    "manufacturer",
+    "series",
+    "category",
+    "status",
+    "release_date",
+    "tags",
+    "rating",
+    "latest_price",
+    "latest_price_currency",
+    "latest_price_at",
+    "notes",
+];
+
+/// 指定换算币种时在目录列之后追加的列
+const CONVERTED_COLUMNS: [&str; 2] = ["converted_price", "converted_currency"];
+
+/// 用户导出在目录列之后追加的列
+const USER_COLUMNS: [&str; 2] = ["favorited", "purchased"];
+
+#[derive(Clone)]
+pub struct ExportService<T, C>
+where
+    T: ExportRepositoryTrait + Clone,
+    C: CurrencyRepositoryTrait + Clone,
+{
+    export_repository: T,
+    currency_service: CurrencyService<C>,
+}
+
+impl<T, C> ExportService<T, C>
+where
+    T: ExportRepositoryTrait + Clone + 'static,
+    C: CurrencyRepositoryTrait + Clone + 'static,
+{
+    pub fn new(export_repository: T, currency_service: CurrencyService<C>) -> Self {
+        Self {
+            export_repository,
+            currency_service,
+        }
+    }
+
+    /// 按批次产出导出数据，调用方可边读边写响应。
+    /// `currency` 为已规范化的换算币种，为空时不输出换算列
+    pub fn export_batches(
+        &self,
+        scope: ExportScope,
+        filter: ModelFilter,
+        currency: Option<String>,
+    ) -> impl Stream<Item = anyhow::Result<Vec<ExportRow>>> + Send + 'static {
+        let service = self.clone();
+        futures::stream::try_unfold(Some(0), move |cursor| {
+            let service = service.clone();
+            let filter = filter.clone();
+            let currency = currency.clone();
+            async move {
+                let Some(after_id) = cursor else {
+                    return Ok(None);
+                };
+                let rows = service
+                    .load_batch(scope, &filter, currency.as_deref(), after_id)
+                    .await?;
+                if rows.is_empty() {
+                    return Ok(None);
+                }
+                let next = if rows.len() < EXPORT_BATCH_SIZE as usize {
+                    None
+                } else {
+                    rows.last().map(|r| r.id)
+                };
+                Ok(Some((rows, next)))
+            }
+        })
+    }
+
+    /// 将导出数据编码为字节流；CSV 先输出带 BOM 的表头，便于 Excel 正确识别中文
+    pub fn export_stream(
+        &self,
+        scope: ExportScope,
+        filter: ModelFilter,
+        format: ExportFormat,
+        currency: Option<String>,
+    ) -> impl Stream<Item = anyhow::Result<Vec<u8>>> + Send + 'static {
+        let converted = currency.is_some();
+        let header = match format {
+            ExportFormat::Csv => {
+                let mut header = "\u{feff}".as_bytes().to_vec();
+                header.extend(csv_chunk(&[columns(scope, converted)]));
+                header
+            }
+            _ => Vec::new(),
+        };
+
+        let body = self
+            .export_batches(scope, filter, currency)
+            .and_then(move |rows| async move {
+                match format {
+                    ExportFormat::Csv => {
+                        let records: Vec<Vec<String>> = rows
+                            .iter()
+                            .map(|row| csv_values(row, scope, converted))
+                            .collect();
+                        Ok(csv_chunk(&records))
+                    }
+                    _ => jsonl_chunk(&rows),
+                }
+            });
+
+        futures::stream::once(async move { Ok(header) }).chain(body)
+    }
+
+    /// XLSX 为 zip 格式，无法流式输出，需读取全部数据后一次性生成
+    pub async fn export_xlsx(
+        &self,
+        scope: ExportScope,
+        filter: ModelFilter,
+        currency: Option<String>,
+    ) -> anyhow::Result<Vec<u8>> {
+        let converted = currency.is_some();
+        let columns = columns(scope, converted);
+        let batches: Vec<Vec<ExportRow>> = self
+            .export_batches(scope, filter, currency)
+            .try_collect()
+            .await?;
+
+        let mut workbook = rust_xlsxwriter::Workbook::new();
+        let worksheet = workbook.add_worksheet();
+        let header_format = rust_xlsxwriter::Format::new().set_bold();
+
+        for (col, name) in columns.iter().enumerate() {
+            worksheet.write_string_with_format(0, col as u16, *name, &header_format)?;
+        }
+        for (index, row) in batches.iter().flatten().enumerate() {
+            let line = index as u32 + 1;
+            for (col, value) in csv_values(row, scope, converted).iter().enumerate() {
+                let col = col as u16;
+                match columns[col as usize] {
+                    "id" | "rating" | "latest_price" | "converted_price" if !value.is_empty() => {
+                        worksheet.write_number(line, col, value.parse::<f64>()?)?;
+                    }
+                    _ => {
+                        worksheet.write_string(line, col, value)?;
+                    }
+                }
+            }
+        }
+        worksheet.autofit();
+
+        Ok(workbook.save_to_buffer()?)
+    }
+
+    async fn load_batch(
+        &self,
+        scope: ExportScope,
+        filter: &ModelFilter,
+        currency: Option<&str>,
+        after_id: i32,
+    ) -> anyhow::Result<Vec<ExportRow>> {
+        let models = self
+            .export_repository
+            .find_models_after(scope, filter, after_id, EXPORT_BATCH_SIZE)
+            .await?;
+        if models.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let model_ids: Vec<i32> = models.iter().map(|m| m.id).collect();
+        let mut manufacturer_ids: Vec<i32> = models.iter().map(|m| m.manufacturer_id).collect();
+        manufacturer_ids.sort_unstable();
+        manufacturer_ids.dedup();
+        let mut series_ids: Vec<i32> = models.iter().filter_map(|m| m.series_id).collect();
+        series_ids.sort_unstable();
+        series_ids.dedup();
+
+        let manufacturers = self
+            .export_repository
+            .find_manufacturer_names(manufacturer_ids)
+            .await?;
+        let series_names = self.export_repository.find_series_names(series_ids).await?;
+        let mut tags = self
+            .export_repository
+            .find_tag_names(model_ids.clone())
+            .await?;
+        let ratings = self
+            .export_repository
+            .find_ratings(model_ids.clone())
+            .await?;
+        let mut prices = self
+            .export_repository
+            .find_latest_prices(model_ids.clone())
+            .await?;
+        let rates = match currency {
+            Some(currency) => Some(
+                self.currency_service
+                    .rate_table(
+                        prices
+                            .values()
+                            .map(|p| p.currency.clone())
+                            .chain([currency.to_string()]),
+                    )
+                    .await?,
+            ),
+            None => None,
+        };
+        let flags = match scope {
+            ExportScope::User(user_id) => Some(
+                self.export_repository
+                    .find_user_flags(user_id, model_ids)
+                    .await?,
+            ),
+            ExportScope::Catalog => None,
+        };
+
+        let rows = models
+            .into_iter()
+            .map(|m| {
+                let price = prices.remove(&m.id);
+                let converted_price = currency.zip(rates.as_ref()).map(|(currency, rates)| {
+                    price.as_ref().and_then(|p| {
+                        rates
+                            .convert(p.price, &p.currency, currency, p.recorded_at.date())
+                            .amount
+                    })
+                });
+                ExportRow {
+                    id: m.id,
+                    manufacturer: manufacturers.get(&m.manufacturer_id).cloned(),
+                    series: m.series_id.and_then(|id| series_names.get(&id).cloned()),
+                    category: m.category.map(|c| c.label().to_string()),
+                    status: m.status.label().to_string(),
+                    release_date: m.release_date,
+                    tags: tags.remove(&m.id).unwrap_or_default(),
+                    rating: ratings.get(&m.id).copied(),
+                    latest_price: price.as_ref().map(|p| p.price),
+                    latest_price_currency: price.as_ref().map(|p| p.currency.clone()),
+                    latest_price_at: price.map(|p| p.recorded_at),
+                    notes: m.notes,
+                    converted_price,
+                    converted_currency: currency.map(str::to_string),
+                    favorited: flags
+                        .as_ref()
+                        .map(|(favorites, _)| favorites.contains(&m.id)),
+                    purchased: flags
+                        .as_ref()
+                        .map(|(_, purchases)| purchases.contains(&m.id)),
+                    name: m.name,
+                }
+            })
+            .collect();
+        Ok(rows)
+    }
+}
+
+fn columns(scope: ExportScope, converted: bool) -> Vec<&'static str> {
+    let mut columns = CATALOG_COLUMNS.to_vec();
+    if converted {
+        columns.extend(CONVERTED_COLUMNS);
+    }
+    if matches!(scope, ExportScope::User(_)) {
+        columns.extend(USER_COLUMNS);
+    }
+    columns
+}
+
+/// 与 `columns` 一一对应的单元格文本
+fn csv_values(row: &ExportRow, scope: ExportScope, converted: bool) -> Vec<String> {
+    let opt = |value: Option<String>| value.unwrap_or_default();
+    let mut values = vec![
+        row.id.to_string(),
+        row.name.clone(),
+        opt(row.manufacturer.clone()),
+        opt(row.series.clone()),
+        opt(row.category.clone()),
+        row.status.clone(),
+        opt(row.release_date.map(|d| d.to_string())),
+        row.tags.join(","),
+        opt(row.rating.map(|r| r.to_string())),
+        opt(row.latest_price.map(|p| p.to_string())),
+        opt(row.latest_price_currency.clone()),
+        opt(row
+            .latest_price_at
+            .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())),
+        opt(row.notes.clone()),
+    ];
+    if converted {
+        values.push(opt(row.converted_price.flatten().map(|p| p.to_string())));
+        values.push(opt(row.converted_currency.clone()));
+    }
+    if matches!(scope, ExportScope::User(_)) {
+        values.push(row.favorited.unwrap_or_default().to_string());
+        values.push(row.purchased.unwrap_or_default().to_string());
+    }
+    values
+}
+
+fn csv_chunk<R, S>(records: &[R]) -> Vec<u8>
+where
+    R: AsRef<[S]>,
+    S: AsRef<str>,
+{
+    let mut writer = csv::WriterBuilder::new()
+        .has_headers(false)
+        .from_writer(Vec::new());
+    for record in records {
+        // 写入内存缓冲区不会失败
+        let _ = writer.write_record(record.as_ref().iter().map(|v| v.as_ref()));
+    }
+    writer.into_inner().unwrap_or_default()
+}
+
+fn jsonl_chunk(rows: &[ExportRow]) -> anyhow::Result<Vec<u8>> {
+    let mut buffer = Vec::new();
+    for row in rows {
+        serde_json::to_writer(&mut buffer, row)?;
+        buffer.push(b'\n');
+    }
+    Ok(buffer)
+}
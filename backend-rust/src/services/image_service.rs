@@ -0,0 +1,239 @@
+#![allow(dead_code)]
+
+use crate::domain::entities::model_image;
+use crate::domain::models::image_dto::ModelImageResponse;
+use crate::infrastructure::repositories::model_image_repository::ModelImageRepositoryTrait;
+use crate::infrastructure::storage::SharedStorage;
+use crate::services::error::{FieldError, ServiceError};
+use image::{GenericImageView, ImageFormat};
+use sea_orm::ActiveValue::{NotSet, Set};
+use std::io::Cursor;
+
+/// 缩略图最长边像素
+const THUMBNAIL_SIZE: u32 = 320;
+
+/// 解码后的上传图片及其 JPEG 缩略图
+struct ProcessedImage {
+    format: ImageFormat,
+    width: u32,
+    height: u32,
+    thumbnail: Vec<u8>,
+}
+
+#[derive(Clone)]
+pub struct ImageService<T>
+where
+    T: ModelImageRepositoryTrait + Clone,
+{
+    image_repository: T,
+    storage: SharedStorage,
+    max_image_bytes: usize,
+}
+
+impl<T> ImageService<T>
+where
+    T: ModelImageRepositoryTrait + Clone,
+{
+    pub fn new(image_repository: T, storage: SharedStorage, max_image_bytes: usize) -> Self {
+        Self {
+            image_repository,
+            storage,
+            max_image_bytes,
+        }
+    }
+
+    pub async fn get_images(&self, model_id: i32) -> anyhow::Result<Vec<ModelImageResponse>> {
+        self.ensure_model(model_id).await?;
+        let images = self.image_repository.find_by_model(model_id).await?;
+        Ok(images
+            .into_iter()
+            .map(|image| self.convert_to_response(image))
+            .collect())
+    }
+
+    /// 图片写入存储后端后再落库，落库失败时清理已写入的文件
+    pub async fn upload_image(
+        &self,
+        model_id: i32,
+        data: Vec<u8>,
+        make_primary: bool,
+        uploaded_by: Option<i32>,
+    ) -> anyhow::Result<ModelImageResponse> {
+        self.ensure_model(model_id).await?;
+        let stored = store_image(
+            &self.storage,
+            &format!("models/{}", model_id),
+            data,
+            self.max_image_bytes,
+        )
+        .await?;
+
+        let image = model_image::ActiveModel {
+            id: NotSet,
+            model_id: Set(model_id),
+            storage_key: Set(stored.storage_key.clone()),
+            thumbnail_key: Set(stored.thumbnail_key.clone()),
+            content_type: Set(stored.content_type),
+            width: Set(stored.width),
+            height: Set(stored.height),
+            size_bytes: Set(stored.size_bytes),
+            is_primary: NotSet,
+            uploaded_by: Set(uploaded_by),
+            created_at: NotSet,
+        };
+        match self.image_repository.create(image, make_primary).await {
+            Ok(created) => Ok(self.convert_to_response(created)),
+            Err(e) => {
+                remove_objects(&self.storage, &[&stored.storage_key, &stored.thumbnail_key]).await;
+                Err(e.into())
+            }
+        }
+    }
+
+    pub async fn set_primary(
+        &self,
+        model_id: i32,
+        image_id: i32,
+    ) -> anyhow::Result<ModelImageResponse> {
+        let image = self
+            .image_repository
+            .set_primary(model_id, image_id)
+            .await?
+            .ok_or(ServiceError::NotFound)?;
+        Ok(self.convert_to_response(image))
+    }
+
+    pub async fn delete_image(&self, model_id: i32, image_id: i32) -> anyhow::Result<()> {
+        let image = self
+            .image_repository
+            .delete(model_id, image_id)
+            .await?
+            .ok_or(ServiceError::NotFound)?;
+        remove_objects(&self.storage, &[&image.storage_key, &image.thumbnail_key]).await;
+        Ok(())
+    }
+
+    async fn ensure_model(&self, model_id: i32) -> anyhow::Result<()> {
+        if !self.image_repository.model_exists(model_id).await? {
+            return Err(ServiceError::NotFound.into());
+        }
+        Ok(())
+    }
+
+    fn convert_to_response(&self, image: model_image::Model) -> ModelImageResponse {
+        ModelImageResponse {
+            id: image.id,
+            model_id: image.model_id,
+            url: self.storage.public_url(&image.storage_key),
+            thumbnail_url: self.storage.public_url(&image.thumbnail_key),
+            content_type: image.content_type,
+            width: image.width,
+            height: image.height,
+            size_bytes: image.size_bytes,
+            is_primary: image.is_primary,
+            created_at: image.created_at,
+        }
+    }
+}
+
+/// 已写入存储后端的原图与缩略图
+pub struct StoredImage {
+    pub storage_key: String,
+    pub thumbnail_key: String,
+    pub content_type: String,
+    pub width: i32,
+    pub height: i32,
+    pub size_bytes: i64,
+}
+
+/// 校验并解码图片、生成缩略图，然后将原图与缩略图写入存储后端的 `prefix` 目录下
+pub async fn store_image(
+    storage: &SharedStorage,
+    prefix: &str,
+    data: Vec<u8>,
+    max_image_bytes: usize,
+) -> anyhow::Result<StoredImage> {
+    if data.is_empty() {
+        return Err(invalid_file("请上传图片文件").into());
+    }
+    if data.len() > max_image_bytes {
+        return Err(invalid_file(format!(
+            "图片大小不能超过 {} MB",
+            max_image_bytes / 1024 / 1024
+        ))
+        .into());
+    }
+
+    let format = image::guess_format(&data)
+        .ok()
+        .filter(|f| {
+            matches!(
+                f,
+                ImageFormat::Jpeg | ImageFormat::Png | ImageFormat::WebP | ImageFormat::Gif
+            )
+        })
+        .ok_or_else(|| invalid_file("仅支持 JPEG、PNG、WebP 与 GIF 图片"))?;
+
+    let (data, processed) = tokio::task::spawn_blocking(move || {
+        let processed = process_image(&data, format);
+        (data, processed)
+    })
+    .await?;
+    let processed = processed.map_err(|_| invalid_file("图片文件已损坏或无法解析"))?;
+
+    let name = uuid::Uuid::new_v4();
+    let extension = processed.format.extensions_str()[0];
+    let storage_key = format!("{}/{}.{}", prefix, name, extension);
+    let thumbnail_key = format!("{}/{}_thumb.jpg", prefix, name);
+    let content_type = processed.format.to_mime_type().to_string();
+    let size_bytes = data.len() as i64;
+
+    storage.put(&storage_key, data, &content_type).await?;
+    if let Err(e) = storage
+        .put(&thumbnail_key, processed.thumbnail, "image/jpeg")
+        .await
+    {
+        remove_objects(storage, &[&storage_key]).await;
+        return Err(e);
+    }
+
+    Ok(StoredImage {
+        storage_key,
+        thumbnail_key,
+        content_type,
+        width: processed.width as i32,
+        height: processed.height as i32,
+        size_bytes,
+    })
+}
+
+/// 清理存储中的对象；记录已删除或写入失败时调用，失败只记日志
+pub async fn remove_objects(storage: &SharedStorage, keys: &[&str]) {
+    for key in keys {
+        if let Err(e) = storage.delete(key).await {
+            tracing::warn!("删除图片文件 {} 失败: {:?}", key, e);
+        }
+    }
+}
+
+fn invalid_file(message: impl Into<String>) -> ServiceError {
+    ServiceError::Validation(vec![FieldError::new("file", message)])
+}
+
+fn process_image(data: &[u8], format: ImageFormat) -> image::ImageResult<ProcessedImage> {
+    let decoded = image::load_from_memory_with_format(data, format)?;
+    let (width, height) = decoded.dimensions();
+
+    // JPEG 不支持透明通道，缩略图统一转为 RGB
+    let thumbnail =
+        image::DynamicImage::ImageRgb8(decoded.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE).to_rgb8());
+    let mut buffer = Cursor::new(Vec::new());
+    thumbnail.write_to(&mut buffer, ImageFormat::Jpeg)?;
+
+    Ok(ProcessedImage {
+        format,
+        width,
+        height,
+        thumbnail: buffer.into_inner(),
+    })
+}
@@ -0,0 +1,612 @@
+#![allow(dead_code)]
+
+use crate::domain::entities::enums::{Grade, ModelStatus};
+use crate::domain::entities::{manufacturer, model, tag};
+use crate::domain::models::import_dto::*;
+use crate::domain::models::model_dto::{CreateModelRequest, UpdateModelRequest};
+use crate::infrastructure::repositories::{
+    currency_repository::CurrencyRepositoryTrait,
+    manufacturer_repository::ManufacturerRepositoryTrait, model_repository::ModelRepositoryTrait,
+    notification_repository::NotificationRepositoryTrait, series_repository::SeriesRepositoryTrait,
+};
+use crate::services::error::{FieldError, ServiceError};
+use crate::services::model_service::{validate_tags, ModelService};
+use sea_orm::ActiveValue::Set;
+use std::collections::HashMap;
+
+/// 可导入的目标字段
+const IMPORT_FIELDS: [&str; 9] = [
+    "name",
+    "manufacturer",
+    "manufacturer_country",
+    "series",
+    "category",
+    "status",
+    "release_date",
+    "notes",
+    "tags",
+];
+
+/// 标签列中可用的分隔符
+const TAG_SEPARATORS: [char; 5] = [',', ';', '|', '、', '，'];
+
+/// 一行源数据：源列名 → 单元格文本
+type Record = HashMap<String, String>;
+
+/// 解析后的一行数据，`None` 表示该列缺失或为空，更新时不修改对应字段
+struct ParsedRow {
+    name: String,
+    manufacturer: String,
+    manufacturer_country: Option<String>,
+    series: Option<String>,
+    category: Option<Grade>,
+    status: Option<ModelStatus>,
+    release_date: Option<chrono::NaiveDate>,
+    notes: Option<String>,
+    tags: Option<Vec<String>>,
+}
+
+/// 跨行共享的导入状态
+#[derive(Default)]
+struct ImportContext {
+    /// 小写厂商名 → 厂商 ID；dry_run 下将要创建的厂商记为 `None`
+    manufacturers: HashMap<String, Option<i32>>,
+    created_manufacturers: Vec<String>,
+    /// (小写厂商名, 小写模型名) → 首次出现的行号
+    seen: HashMap<(String, String), usize>,
+}
+
+/// 模型的新建与更新交给 `ModelService`，与手动编辑走相同的校验、疑似重复检测与事件推送
+#[derive(Clone)]
+pub struct ImportService<M, C, N, F, S>
+where
+    M: ModelRepositoryTrait + Clone,
+    C: CurrencyRepositoryTrait + Clone,
+    N: NotificationRepositoryTrait + Clone,
+    F: ManufacturerRepositoryTrait + Clone,
+    S: SeriesRepositoryTrait + Clone,
+{
+    model_repository: M,
+    model_service: ModelService<M, C, N>,
+    manufacturer_repository: F,
+    series_repository: S,
+}
+
+impl<M, C, N, F, S> ImportService<M, C, N, F, S>
+where
+    M: ModelRepositoryTrait + Clone,
+    C: CurrencyRepositoryTrait + Clone,
+    N: NotificationRepositoryTrait + Clone,
+    F: ManufacturerRepositoryTrait + Clone,
+    S: SeriesRepositoryTrait + Clone,
+{
+    pub fn new(
+        model_repository: M,
+        model_service: ModelService<M, C, N>,
+        manufacturer_repository: F,
+        series_repository: S,
+    ) -> Self {
+        Self {
+            model_repository,
+            model_service,
+            manufacturer_repository,
+            series_repository,
+        }
+    }
+
+    /// 逐行导入模型：同厂商下同名模型视为已存在并按有值的列更新，否则新建；
+    /// 单行失败不影响其他行，结果逐行写入报告
+    pub async fn import_models(
+        &self,
+        mut request: ImportModelsRequest,
+        edited_by: Option<i32>,
+    ) -> anyhow::Result<ImportReport> {
+        if let Some(unknown) = request
+            .mapping
+            .keys()
+            .find(|field| !IMPORT_FIELDS.contains(&field.as_str()))
+        {
+            return Err(ServiceError::Validation(vec![FieldError::new(
+                "mapping",
+                format!("未知的目标字段: {}", unknown),
+            )])
+            .into());
+        }
+
+        let records = parse_records(request.format, std::mem::take(&mut request.data))
+            .map_err(|message| ServiceError::Validation(vec![FieldError::new("data", message)]))?;
+
+        let mut ctx = ImportContext::default();
+        let mut rows = Vec::with_capacity(records.len());
+        for (index, record) in records.iter().enumerate() {
+            let row = index + 1;
+            let result = match parse_row(record, &request.mapping) {
+                Ok(parsed) => self
+                    .import_row(row, parsed, &request, &mut ctx, edited_by)
+                    .await
+                    .unwrap_or_else(|e| {
+                        if e.downcast_ref::<ServiceError>().is_none() {
+                            tracing::error!("导入第{}行失败: {:?}", row, e);
+                        }
+                        row_result(row, ImportRowStatus::Error, None, None, error_messages(&e))
+                    }),
+                Err(messages) => {
+                    let name = mapped_value(record, &request.mapping, "name").map(str::to_string);
+                    row_result(row, ImportRowStatus::Error, None, name, messages)
+                }
+            };
+            rows.push(result);
+        }
+
+        let count = |status| rows.iter().filter(|r| r.status == status).count();
+        Ok(ImportReport {
+            dry_run: request.dry_run,
+            total: rows.len(),
+            created: count(ImportRowStatus::Created),
+            updated: count(ImportRowStatus::Updated),
+            skipped: count(ImportRowStatus::Skipped),
+            errors: count(ImportRowStatus::Error),
+            created_manufacturers: ctx.created_manufacturers,
+            rows,
+        })
+    }
+
+    async fn import_row(
+        &self,
+        row: usize,
+        parsed: ParsedRow,
+        request: &ImportModelsRequest,
+        ctx: &mut ImportContext,
+        edited_by: Option<i32>,
+    ) -> anyhow::Result<ImportRowResult> {
+        let name = Some(parsed.name.clone());
+
+        let key = (
+            parsed.manufacturer.to_lowercase(),
+            parsed.name.to_lowercase(),
+        );
+        if let Some(first_row) = ctx.seen.get(&key) {
+            let message = format!("与第{}行重复", first_row);
+            return Ok(row_result(
+                row,
+                ImportRowStatus::Skipped,
+                None,
+                name,
+                vec![message],
+            ));
+        }
+        ctx.seen.insert(key, row);
+
+        let series_id = match parsed.series.as_deref() {
+            Some(series_name) => match self
+                .series_repository
+                .find_by_name_or_alias(series_name)
+                .await?
+            {
+                Some(series) => Some(series.id),
+                None => {
+                    let message = format!("系列不存在: {}", series_name);
+                    return Ok(row_result(
+                        row,
+                        ImportRowStatus::Error,
+                        None,
+                        name,
+                        vec![message],
+                    ));
+                }
+            },
+            None => None,
+        };
+
+        let mut messages = Vec::new();
+        let manufacturer_id = match self.resolve_manufacturer(&parsed, request, ctx).await? {
+            ManufacturerResolution::Found(id) => id,
+            ManufacturerResolution::Created(id) => {
+                messages.push(format!("已自动创建厂商: {}", parsed.manufacturer));
+                id
+            }
+            ManufacturerResolution::Missing => {
+                let message = format!("厂商不存在: {}", parsed.manufacturer);
+                return Ok(row_result(
+                    row,
+                    ImportRowStatus::Error,
+                    None,
+                    name,
+                    vec![message],
+                ));
+            }
+        };
+
+        let existing = match manufacturer_id {
+            Some(manufacturer_id) => {
+                self.model_repository
+                    .find_by_manufacturer_and_name(manufacturer_id, &parsed.name)
+                    .await?
+            }
+            None => None,
+        };
+
+        match (manufacturer_id, existing) {
+            (_, Some(existing)) => {
+                self.update_existing(row, existing, parsed, series_id, request.dry_run, edited_by)
+                    .await
+            }
+            (Some(manufacturer_id), None) => {
+                let create_request = CreateModelRequest {
+                    name: parsed.name,
+                    series_id,
+                    category: parsed.category,
+                    rating: None,
+                    release_date: parsed.release_date,
+                    preorder_open_date: None,
+                    status: parsed.status.unwrap_or(ModelStatus::InStock),
+                    manufacturer_id,
+                    parent_id: None,
+                    notes: parsed.notes,
+                    tags: parsed.tags,
+                };
+                let model_id = if request.dry_run {
+                    self.model_service
+                        .check_create_model(&create_request, request.force)
+                        .await?;
+                    None
+                } else {
+                    let created = self
+                        .model_service
+                        .create_model(create_request, request.force, edited_by)
+                        .await?;
+                    Some(created.id)
+                };
+                Ok(row_result(
+                    row,
+                    ImportRowStatus::Created,
+                    model_id,
+                    name,
+                    messages,
+                ))
+            }
+            // dry_run 中厂商将在本次导入中创建：不存在同厂商的疑似重复，系列已在上面解析
+            (None, None) => {
+                validate_tags(parsed.tags.as_deref())?;
+                Ok(row_result(
+                    row,
+                    ImportRowStatus::Created,
+                    None,
+                    name,
+                    messages,
+                ))
+            }
+        }
+    }
+
+    /// 仅比较并更新该行中有值的字段，全部一致时跳过
+    async fn update_existing(
+        &self,
+        row: usize,
+        existing: model::Model,
+        parsed: ParsedRow,
+        series_id: Option<i32>,
+        dry_run: bool,
+        edited_by: Option<i32>,
+    ) -> anyhow::Result<ImportRowResult> {
+        let name = Some(parsed.name.clone());
+        let mut changed = Vec::new();
+        let mut update_request = UpdateModelRequest {
+            name: None,
+            series_id: None,
+            category: None,
+            rating: None,
+            release_date: None,
+            preorder_open_date: None,
+            status: None,
+            manufacturer_id: None,
+            parent_id: None,
+            notes: None,
+            tags: None,
+            version: None,
+        };
+
+        if series_id.is_some() && series_id != existing.series_id {
+            update_request.series_id = series_id;
+            changed.push("series");
+        }
+        if parsed.category.is_some() && parsed.category != existing.category {
+            update_request.category = parsed.category;
+            changed.push("category");
+        }
+        if let Some(status) = parsed.status.filter(|s| *s != existing.status) {
+            update_request.status = Some(status);
+            changed.push("status");
+        }
+        if parsed.release_date.is_some() && parsed.release_date != existing.release_date {
+            update_request.release_date = parsed.release_date;
+            changed.push("release_date");
+        }
+        if parsed.notes.is_some() && parsed.notes != existing.notes {
+            update_request.notes = parsed.notes;
+            changed.push("notes");
+        }
+
+        if let Some(tags) = parsed.tags {
+            let current = self
+                .model_repository
+                .find_tag_names(vec![existing.id])
+                .await?
+                .remove(&existing.id)
+                .unwrap_or_default();
+            if slug_set(&tags) != slug_set(&current) {
+                update_request.tags = Some(tags);
+                changed.push("tags");
+            }
+        }
+
+        if changed.is_empty() {
+            return Ok(row_result(
+                row,
+                ImportRowStatus::Skipped,
+                Some(existing.id),
+                name,
+                vec!["数据无变化".to_string()],
+            ));
+        }
+
+        if dry_run {
+            self.model_service
+                .check_update_model(existing.id, &update_request)
+                .await?;
+        } else {
+            self.model_service
+                .update_model(existing.id, update_request, edited_by)
+                .await?;
+        }
+        let messages = vec![format!("更新字段: {}", changed.join(", "))];
+        Ok(row_result(
+            row,
+            ImportRowStatus::Updated,
+            Some(existing.id),
+            name,
+            messages,
+        ))
+    }
+
+    async fn resolve_manufacturer(
+        &self,
+        parsed: &ParsedRow,
+        request: &ImportModelsRequest,
+        ctx: &mut ImportContext,
+    ) -> anyhow::Result<ManufacturerResolution> {
+        let key = parsed.manufacturer.to_lowercase();
+        if let Some(id) = ctx.manufacturers.get(&key) {
+            return Ok(ManufacturerResolution::Found(*id));
+        }
+
+        if let Some(found) = self
+            .manufacturer_repository
+            .find_by_name(&parsed.manufacturer)
+            .await?
+        {
+            ctx.manufacturers.insert(key, Some(found.id));
+            return Ok(ManufacturerResolution::Found(Some(found.id)));
+        }
+
+        if !request.auto_create_manufacturers {
+            return Ok(ManufacturerResolution::Missing);
+        }
+
+        let id = if request.dry_run {
+            None
+        } else {
+            let manufacturer_data = manufacturer::ActiveModel {
+                name: Set(parsed.manufacturer.clone()),
+                full_name: Set(None),
+                country: Set(parsed.manufacturer_country.clone().unwrap_or_default()),
+                active_period_start: Set(None),
+                active_period_end: Set(None),
+                updated_at: Set(chrono::Utc::now().naive_utc()),
+                ..Default::default()
+            };
+            Some(
+                self.manufacturer_repository
+                    .create(manufacturer_data)
+                    .await?
+                    .id,
+            )
+        };
+        ctx.manufacturers.insert(key, id);
+        ctx.created_manufacturers.push(parsed.manufacturer.clone());
+        Ok(ManufacturerResolution::Created(id))
+    }
+}
+
+/// 厂商解析结果；ID 为 `None` 表示 dry_run 中将要创建、尚未写入数据库
+enum ManufacturerResolution {
+    Found(Option<i32>),
+    /// 本行自动创建
+    Created(Option<i32>),
+    Missing,
+}
+
+fn row_result(
+    row: usize,
+    status: ImportRowStatus,
+    model_id: Option<i32>,
+    name: Option<String>,
+    messages: Vec<String>,
+) -> ImportRowResult {
+    ImportRowResult {
+        row,
+        status,
+        model_id,
+        name,
+        messages,
+    }
+}
+
+/// 单行失败时报告中的消息：字段校验错误逐条列出，疑似重复附带已有模型名称
+fn error_messages(err: &anyhow::Error) -> Vec<String> {
+    match err.downcast_ref::<ServiceError>() {
+        Some(ServiceError::Validation(errors)) => errors
+            .iter()
+            .map(|e| format!("{}: {}", e.field, e.message))
+            .collect(),
+        Some(e @ ServiceError::PossibleDuplicates(duplicates)) => {
+            let names: Vec<&str> = duplicates.iter().map(|d| d.name.as_str()).collect();
+            vec![format!("{}: {}", e, names.join(", "))]
+        }
+        _ => vec![err.to_string()],
+    }
+}
+
+fn slug_set(names: &[String]) -> Vec<String> {
+    let mut slugs: Vec<String> = names.iter().map(|n| tag::slugify(n)).collect();
+    slugs.sort();
+    slugs.dedup();
+    slugs
+}
+
+/// 将 CSV 文本或 JSON 对象数组统一转换为按列名取值的记录
+fn parse_records(format: ImportFormat, data: serde_json::Value) -> Result<Vec<Record>, String> {
+    match format {
+        ImportFormat::Csv => {
+            let text = data.as_str().ok_or("CSV 格式的 data 必须是字符串")?;
+            // Excel 导出的 CSV 常带 BOM
+            let text = text.trim_start_matches('\u{feff}');
+            let mut reader = csv::ReaderBuilder::new()
+                .flexible(true)
+                .trim(csv::Trim::All)
+                .from_reader(text.as_bytes());
+            let headers = reader
+                .headers()
+                .map_err(|e| format!("无法解析 CSV 表头: {}", e))?
+                .clone();
+
+            reader
+                .records()
+                .map(|record| {
+                    let record = record.map_err(|e| format!("无法解析 CSV: {}", e))?;
+                    Ok(headers
+                        .iter()
+                        .zip(record.iter())
+                        .map(|(h, v)| (h.to_string(), v.to_string()))
+                        .collect())
+                })
+                .collect()
+        }
+        ImportFormat::Json => {
+            let serde_json::Value::Array(items) = data else {
+                return Err("JSON 格式的 data 必须是对象数组".to_string());
+            };
+            items
+                .into_iter()
+                .enumerate()
+                .map(|(index, item)| {
+                    let serde_json::Value::Object(object) = item else {
+                        return Err(format!("第{}项不是对象", index + 1));
+                    };
+                    Ok(object
+                        .into_iter()
+                        .filter_map(|(key, value)| json_cell(value).map(|v| (key, v)))
+                        .collect())
+                })
+                .collect()
+        }
+    }
+}
+
+fn json_cell(value: serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::Null => None,
+        serde_json::Value::String(s) => Some(s),
+        serde_json::Value::Array(items) => Some(
+            items
+                .into_iter()
+                .filter_map(json_cell)
+                .collect::<Vec<_>>()
+                .join(","),
+        ),
+        other => Some(other.to_string()),
+    }
+}
+
+/// 按映射取出目标字段的值，空白单元格视为缺失
+fn mapped_value<'a>(
+    record: &'a Record,
+    mapping: &HashMap<String, String>,
+    field: &str,
+) -> Option<&'a str> {
+    let column = mapping.get(field).map_or(field, String::as_str);
+    record
+        .get(column)
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty())
+}
+
+fn parse_row(record: &Record, mapping: &HashMap<String, String>) -> Result<ParsedRow, Vec<String>> {
+    let get = |field| mapped_value(record, mapping, field);
+    let mut errors = Vec::new();
+
+    let name = get("name").unwrap_or_default().to_string();
+    if name.is_empty() {
+        errors.push("缺少模型名称".to_string());
+    } else if name.chars().count() > 255 {
+        errors.push("模型名称不能超过255个字符".to_string());
+    }
+    let manufacturer = get("manufacturer").unwrap_or_default().to_string();
+    if manufacturer.is_empty() {
+        errors.push("缺少厂商".to_string());
+    }
+
+    let category = get("category").and_then(|v| {
+        parse_enum::<Grade>(v)
+            .map_err(|_| errors.push(format!("无法识别的等级: {}", v)))
+            .ok()
+    });
+    let status = get("status").and_then(|v| {
+        parse_enum::<ModelStatus>(v)
+            .map_err(|_| errors.push(format!("无法识别的状态: {}", v)))
+            .ok()
+    });
+    let release_date = get("release_date").and_then(|v| {
+        parse_date(v)
+            .ok_or_else(|| errors.push(format!("无法识别的发售日期: {}", v)))
+            .ok()
+    });
+    let tags = get("tags").map(|v| {
+        v.split(TAG_SEPARATORS)
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(str::to_string)
+            .collect()
+    });
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    Ok(ParsedRow {
+        name,
+        manufacturer,
+        manufacturer_country: get("manufacturer_country").map(str::to_string),
+        series: get("series").map(str::to_string),
+        category,
+        status,
+        release_date,
+        notes: get("notes").map(str::to_string),
+        tags,
+    })
+}
+
+/// 复用枚举的 serde 别名（如 `HG`、`现货`）
+pub(crate) fn parse_enum<T: serde::de::DeserializeOwned>(
+    value: &str,
+) -> Result<T, serde_json::Error> {
+    serde_json::from_value(serde_json::Value::String(value.to_string()))
+}
+
+/// 支持 `2024-05-01`、`2024/5/1`、`2024.5.1`，以及只有年月的 `2024-05`（按当月1日）
+pub(crate) fn parse_date(value: &str) -> Option<chrono::NaiveDate> {
+    let normalized = value.replace(['/', '.'], "-");
+    chrono::NaiveDate::parse_from_str(&normalized, "%Y-%m-%d")
+        .or_else(|_| chrono::NaiveDate::parse_from_str(&format!("{}-1", normalized), "%Y-%m-%d"))
+        .ok()
+}
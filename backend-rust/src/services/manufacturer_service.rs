@@ -1,9 +1,13 @@
 #![allow(dead_code)]
 
 use crate::domain::entities::manufacturer;
+use crate::domain::models::event_dto::ChangeEventKind;
 use crate::domain::models::manufacturer_dto::*;
 use crate::infrastructure::repositories::manufacturer_repository::ManufacturerRepositoryTrait;
-use sea_orm::ActiveValue::Set;
+use crate::services::error::{map_update_error, ServiceError};
+use crate::services::event_hub::EventHub;
+use crate::services::version::{expected_updated_at, version_of};
+use sea_orm::ActiveValue::{NotSet, Set};
 
 #[derive(Clone)]
 pub struct ManufacturerService<T>
@@ -11,15 +15,17 @@ where
     T: ManufacturerRepositoryTrait + Clone,
 {
     manufacturer_repository: T,
+    events: EventHub,
 }
 
 impl<T> ManufacturerService<T>
 where
     T: ManufacturerRepositoryTrait + Clone,
 {
-    pub fn new(manufacturer_repository: T) -> Self {
+    pub fn new(manufacturer_repository: T, events: EventHub) -> Self {
         Self {
             manufacturer_repository,
+            events,
         }
     }
 
@@ -49,9 +55,11 @@ where
     ) -> anyhow::Result<ManufacturerResponse> {
         let manufacturer_data = manufacturer::ActiveModel {
             name: Set(request.name),
+            full_name: Set(request.full_name),
             country: Set(request.country),
             active_period_start: Set(request.active_period_start),
             active_period_end: Set(request.active_period_end),
+            updated_at: Set(chrono::Utc::now().naive_utc()),
             ..Default::default()
         };
 
@@ -59,7 +67,7 @@ where
             .manufacturer_repository
             .create(manufacturer_data)
             .await?;
-        Ok(self.convert_to_response(created_manufacturer))
+        Ok(self.publish(ChangeEventKind::ManufacturerCreated, created_manufacturer))
     }
 
     pub async fn update_manufacturer(
@@ -71,11 +79,14 @@ where
             .manufacturer_repository
             .find_by_id(id)
             .await?
-            .ok_or_else(|| anyhow::anyhow!("厂商不存在"))?;
+            .ok_or(ServiceError::NotFound)?;
+
+        let expected_updated_at = expected_updated_at(request.version)?;
 
         let manufacturer_data = manufacturer::ActiveModel {
             id: Set(existing_manufacturer.id),
             name: Set(request.name.unwrap_or(existing_manufacturer.name)),
+            full_name: Set(request.full_name.or(existing_manufacturer.full_name)),
             country: Set(request.country.unwrap_or(existing_manufacturer.country)),
             active_period_start: Set(request
                 .active_period_start
@@ -83,29 +94,89 @@ where
             active_period_end: Set(request
                 .active_period_end
                 .or(existing_manufacturer.active_period_end)),
-            ..Default::default()
+            updated_at: Set(chrono::Utc::now().naive_utc()),
         };
 
         let updated_manufacturer = self
             .manufacturer_repository
-            .update(id, manufacturer_data)
-            .await?;
-        Ok(self.convert_to_response(updated_manufacturer))
+            .update(id, manufacturer_data, expected_updated_at)
+            .await
+            .map_err(map_update_error)?;
+        Ok(self.publish(ChangeEventKind::ManufacturerUpdated, updated_manufacturer))
     }
 
-    pub async fn delete_manufacturer(&self, id: i32) -> anyhow::Result<()> {
-        self.manufacturer_repository.delete(id).await?;
+    pub async fn patch_manufacturer(
+        &self,
+        id: i32,
+        request: PatchManufacturerRequest,
+    ) -> anyhow::Result<ManufacturerResponse> {
+        let expected_updated_at = expected_updated_at(request.version)?;
+
+        // 只有请求中出现的字段才会被写入，其余列保持 NotSet
+        let manufacturer_data = manufacturer::ActiveModel {
+            id: Set(id),
+            name: request.name.map_or(NotSet, Set),
+            full_name: request.full_name.map_or(NotSet, Set),
+            country: request.country.map_or(NotSet, Set),
+            active_period_start: request.active_period_start.map_or(NotSet, Set),
+            active_period_end: request.active_period_end.map_or(NotSet, Set),
+            updated_at: Set(chrono::Utc::now().naive_utc()),
+        };
+
+        let patched_manufacturer = self
+            .manufacturer_repository
+            .update(id, manufacturer_data, expected_updated_at)
+            .await
+            .map_err(map_update_error)?;
+        Ok(self.publish(ChangeEventKind::ManufacturerUpdated, patched_manufacturer))
+    }
+
+    pub async fn delete_manufacturer(
+        &self,
+        id: i32,
+        expected_version: Option<i64>,
+    ) -> anyhow::Result<()> {
+        let expected_updated_at = expected_updated_at(expected_version)?;
+
+        let result = self
+            .manufacturer_repository
+            .delete(id, expected_updated_at)
+            .await?;
+        if expected_updated_at.is_some() && result.rows_affected == 0 {
+            return Err(ServiceError::PreconditionFailed.into());
+        }
+        if result.rows_affected > 0 {
+            self.events.publish(
+                ChangeEventKind::ManufacturerDeleted,
+                id,
+                None,
+                &serde_json::json!({ "id": id }),
+            );
+        }
         Ok(())
     }
 
+    /// 转换为响应并广播厂商变更事件
+    fn publish(
+        &self,
+        kind: ChangeEventKind,
+        manufacturer: manufacturer::Model,
+    ) -> ManufacturerResponse {
+        let response = self.convert_to_response(manufacturer);
+        self.events.publish(kind, response.id, None, &response);
+        response
+    }
+
     fn convert_to_response(&self, manufacturer: manufacturer::Model) -> ManufacturerResponse {
         ManufacturerResponse {
             id: manufacturer.id,
             name: manufacturer.name,
+            full_name: manufacturer.full_name,
             country: manufacturer.country,
             active_period_start: manufacturer.active_period_start,
             active_period_end: manufacturer.active_period_end,
             models_count: manufacturer.models_count,
+            version: version_of(manufacturer.updated_at),
         }
     }
 }
@@ -1,3 +1,26 @@
+pub mod activity_service;
 pub mod auth_service;
+pub mod backup_service;
+pub mod build_log_service;
+pub mod calendar_service;
+pub mod currency_service;
+pub mod error;
+pub mod event_hub;
+pub mod export_service;
+pub mod image_service;
+pub mod import_service;
 pub mod manufacturer_service;
 pub mod model_service;
+pub mod notification_dispatcher;
+pub mod notification_service;
+pub mod price_alert_service;
+pub mod price_watch_service;
+pub mod scheduler;
+pub mod search_service;
+pub mod search_text;
+pub mod series_service;
+pub mod shop_service;
+pub mod similarity;
+pub mod status_transition_service;
+pub mod tag_service;
+pub mod version;
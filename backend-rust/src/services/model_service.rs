@@ -1,42 +1,99 @@
 #![allow(dead_code)]
 
-use crate::domain::entities::model;
+use crate::domain::entities::model::MAX_VARIANT_DEPTH;
+use crate::domain::entities::{
+    enums::{Grade, ModelStatus},
+    model, model_revision, price_history, tag, user_model_favorite, user_model_purchase,
+};
+use crate::domain::models::event_dto::ChangeEventKind;
 use crate::domain::models::model_dto::*;
-use crate::infrastructure::repositories::model_repository::ModelRepositoryTrait;
-use sea_orm::ActiveValue::Set;
+use crate::domain::models::shop_dto::ShopPriceResponse;
+use crate::infrastructure::repositories::currency_repository::CurrencyRepositoryTrait;
+use crate::infrastructure::repositories::model_repository::{ModelRepositoryTrait, PurchaseToggle};
+use crate::infrastructure::repositories::notification_repository::NotificationRepositoryTrait;
+use crate::infrastructure::storage::SharedStorage;
+use crate::services::currency_service::{CurrencyService, DEFAULT_CURRENCY};
+use crate::services::error::{map_update_error, FieldError, ServiceError};
+use crate::services::event_hub::EventHub;
+use crate::services::image_service::remove_objects;
+use crate::services::price_alert_service::PriceAlertService;
+use crate::services::shop_service::convert_shop;
+use crate::services::similarity::{name_similarity, DUPLICATE_THRESHOLD};
+use crate::services::version::{expected_updated_at, version_of};
+use sea_orm::ActiveValue::{NotSet, Set};
+use sea_orm::DbErr;
+use std::collections::HashMap;
+
+/// 心愿单默认优先级（1-5）
+const DEFAULT_WISHLIST_PRIORITY: i16 = 3;
+
+/// 标签名与 slug 的长度上限（tags 表的 VARCHAR(50)）
+const MAX_TAG_LENGTH: usize = 50;
+
+/// 写入模型时需要校验的外键引用，`None` 表示本次未涉及该字段
+struct ModelReferences {
+    manufacturer_id: Option<i32>,
+    series_id: Option<i32>,
+    parent_id: Option<i32>,
+}
+
+/// 构建响应时需要从关联表批量查询的数据
+struct ResponseExtras {
+    series_name: Option<String>,
+    tags: Vec<String>,
+    primary_image_key: Option<String>,
+}
 
 #[derive(Clone)]
-pub struct ModelService<T>
+pub struct ModelService<T, C, N>
 where
     T: ModelRepositoryTrait + Clone,
+    C: CurrencyRepositoryTrait + Clone,
+    N: NotificationRepositoryTrait + Clone,
 {
     model_repository: T,
+    currency_service: CurrencyService<C>,
+    image_storage: SharedStorage,
+    events: EventHub,
+    price_alerts: PriceAlertService<N, C>,
 }
 
-impl<T> ModelService<T>
+impl<T, C, N> ModelService<T, C, N>
 where
     T: ModelRepositoryTrait + Clone,
+    C: CurrencyRepositoryTrait + Clone,
+    N: NotificationRepositoryTrait + Clone,
 {
-    pub fn new(model_repository: T) -> Self {
-        Self { model_repository }
+    pub fn new(
+        model_repository: T,
+        currency_service: CurrencyService<C>,
+        image_storage: SharedStorage,
+        events: EventHub,
+        price_alerts: PriceAlertService<N, C>,
+    ) -> Self {
+        Self {
+            model_repository,
+            currency_service,
+            image_storage,
+            events,
+            price_alerts,
+        }
     }
 
     pub async fn get_models(
         &self,
         user_id: Option<i32>,
+        filter: ModelFilter,
         page: u64,
         per_page: u64,
     ) -> anyhow::Result<ModelListResponse> {
         let (models, total) = self
             .model_repository
-            .find_all(user_id, page, per_page)
+            .find_all(user_id, &filter, page, per_page)
             .await?;
-        let total_pages = (total + per_page - 1) / per_page;
+        let total_pages = total.div_ceil(per_page);
 
-        let model_responses: Vec<ModelResponse> = models
-            .into_iter()
-            .map(|m| self.convert_to_model_response(m))
-            .collect();
+        let model_responses = self.build_model_responses(models).await?;
 
         Ok(ModelListResponse {
             models: model_responses,
@@ -53,12 +110,35 @@ where
         user_id: Option<i32>,
     ) -> anyhow::Result<Option<ModelResponse>> {
         if let Some(model) = self.model_repository.find_by_id(id, user_id).await? {
-            Ok(Some(self.convert_to_model_response(model)))
+            Ok(Some(self.build_model_response(model).await?))
         } else {
             Ok(None)
         }
     }
 
+    pub async fn get_models_by_series(
+        &self,
+        series_id: i32,
+        page: u64,
+        per_page: u64,
+    ) -> anyhow::Result<ModelListResponse> {
+        let (models, total) = self
+            .model_repository
+            .find_by_series(series_id, page, per_page)
+            .await?;
+        let total_pages = total.div_ceil(per_page);
+
+        let model_responses = self.build_model_responses(models).await?;
+
+        Ok(ModelListResponse {
+            models: model_responses,
+            total,
+            page,
+            per_page,
+            total_pages,
+        })
+    }
+
     pub async fn get_model_variants(
         &self,
         parent_id: i32,
@@ -68,49 +148,215 @@ where
             .model_repository
             .find_variants(parent_id, user_id)
             .await?;
-        let variant_responses: Vec<ModelResponse> = variants
-            .into_iter()
-            .map(|v| self.convert_to_model_response(v))
-            .collect();
+        let variant_responses = self.build_model_responses(variants).await?;
         Ok(variant_responses)
     }
 
-    pub async fn create_model(&self, request: CreateModelRequest) -> anyhow::Result<ModelResponse> {
+    /// 模型所在的整棵变体树：从根模型开始，包含所有后代与兄弟变体，
+    /// 每个节点附带最新价格、购买人数与分支汇总；价格换算到 `currency`（默认 CNY），
+    /// 模型不存在时返回 `None`
+    pub async fn get_model_family(
+        &self,
+        model_id: i32,
+        currency: Option<String>,
+    ) -> anyhow::Result<Option<ModelFamilyResponse>> {
+        let currency = self
+            .currency_service
+            .target(currency)
+            .await?
+            .unwrap_or_else(|| DEFAULT_CURRENCY.to_string());
+        let members = self.model_repository.find_family(model_id).await?;
+        let Some(root_id) = members.first().map(|m| m.id) else {
+            return Ok(None);
+        };
+
+        let ids: Vec<i32> = members.iter().map(|m| m.id).collect();
+        let models = self.model_repository.find_by_ids(ids.clone()).await?;
+        let mut prices = self.model_repository.find_latest_prices(ids).await?;
+        let rates = self
+            .currency_service
+            .rate_table(
+                prices
+                    .values()
+                    .map(|p| p.currency.clone())
+                    .chain([currency.clone()]),
+            )
+            .await?;
+
+        let mut parents: HashMap<i32, Option<i32>> = HashMap::new();
+        let mut children: HashMap<i32, Vec<i32>> = HashMap::new();
+        for model in &models {
+            parents.insert(model.id, model.parent_id);
+            if let Some(parent_id) = model.parent_id {
+                children.entry(parent_id).or_default().push(model.id);
+            }
+        }
+
+        let mut stats: HashMap<i32, _> = members.into_iter().map(|m| (m.id, m)).collect();
+        let mut nodes = HashMap::new();
+        for response in self.build_model_responses(models).await? {
+            let Some(member) = stats.remove(&response.id) else {
+                continue;
+            };
+            let latest_price = prices.remove(&response.id).map(|price| {
+                let converted = rates.convert(
+                    price.price,
+                    &price.currency,
+                    &currency,
+                    price.recorded_at.date(),
+                );
+                PriceHistoryResponse {
+                    converted: Some(converted),
+                    ..convert_price(price)
+                }
+            });
+            nodes.insert(
+                response.id,
+                FamilyNode {
+                    model: response,
+                    depth: member.depth,
+                    owner_count: member.owner_count,
+                    favorite_count: member.favorite_count,
+                    latest_price,
+                    branch: FamilyBranchSummary::default(),
+                    variants: Vec::new(),
+                },
+            );
+        }
+
+        let Some(tree) = build_family_node(root_id, &mut nodes, &children, &|amount| {
+            rates.round(amount, &currency)
+        }) else {
+            return Ok(None);
+        };
+
+        let mut path = vec![model_id];
+        let mut current = model_id;
+        while current != root_id {
+            let Some(Some(parent_id)) = parents.get(&current) else {
+                break;
+            };
+            path.push(*parent_id);
+            current = *parent_id;
+        }
+        path.reverse();
+
+        Ok(Some(ModelFamilyResponse {
+            model_id,
+            root_id,
+            path,
+            currency,
+            tree,
+        }))
+    }
+
+    /// `force` 为 false 时，若同厂商同等级下存在名称相近的模型则返回 `PossibleDuplicates`
+    pub async fn create_model(
+        &self,
+        request: CreateModelRequest,
+        force: bool,
+        created_by: Option<i32>,
+    ) -> anyhow::Result<ModelResponse> {
+        self.check_create_model(&request, force).await?;
+
         let model_data = model::ActiveModel {
             name: Set(request.name),
-            series: Set(request.series),
+            series_id: Set(request.series_id),
             category: Set(request.category),
             release_date: Set(request.release_date),
+            preorder_open_date: Set(request.preorder_open_date),
             status: Set(request.status),
             manufacturer_id: Set(request.manufacturer_id),
             parent_id: Set(request.parent_id),
             notes: Set(request.notes),
+            created_by: Set(created_by),
             created_at: Set(chrono::Utc::now().naive_utc()),
             updated_at: Set(chrono::Utc::now().naive_utc()),
             ..Default::default()
         };
 
-        let created_model = self.model_repository.create(model_data).await?;
-        Ok(self.convert_to_model_response(created_model))
+        let created_model = self
+            .model_repository
+            .create(model_data, request.tags)
+            .await
+            .map_err(map_update_error)?;
+        self.publish_model(ChangeEventKind::ModelCreated, created_model, created_by)
+            .await
     }
 
-    pub async fn update_model(
+    /// 新建模型前的校验：标签、外键引用，以及未强制创建时的疑似重复检测；
+    /// 导入预览（dry_run）复用以得到与实际写入相同的错误
+    pub async fn check_create_model(
+        &self,
+        request: &CreateModelRequest,
+        force: bool,
+    ) -> anyhow::Result<()> {
+        validate_tags(request.tags.as_deref())?;
+        self.validate_references(
+            None,
+            ModelReferences {
+                manufacturer_id: Some(request.manufacturer_id),
+                series_id: request.series_id,
+                parent_id: request.parent_id,
+            },
+        )
+        .await?;
+
+        if !force {
+            let duplicates = self
+                .find_possible_duplicates(&request.name, request.manufacturer_id, request.category)
+                .await?;
+            if !duplicates.is_empty() {
+                return Err(ServiceError::PossibleDuplicates(duplicates).into());
+            }
+        }
+        Ok(())
+    }
+
+    /// 更新模型前的校验：模型存在、标签与外键引用
+    pub async fn check_update_model(
         &self,
         id: i32,
-        request: UpdateModelRequest,
-    ) -> anyhow::Result<ModelResponse> {
+        request: &UpdateModelRequest,
+    ) -> anyhow::Result<model::Model> {
+        validate_tags(request.tags.as_deref())?;
         let existing_model = self
             .model_repository
             .find_by_id(id, None)
             .await?
-            .ok_or_else(|| anyhow::anyhow!("模型不存在"))?;
+            .ok_or(ServiceError::NotFound)?;
+
+        self.validate_references(
+            Some(id),
+            ModelReferences {
+                manufacturer_id: request.manufacturer_id,
+                series_id: request.series_id,
+                parent_id: request.parent_id,
+            },
+        )
+        .await?;
+        Ok(existing_model)
+    }
+
+    pub async fn update_model(
+        &self,
+        id: i32,
+        request: UpdateModelRequest,
+        edited_by: Option<i32>,
+    ) -> anyhow::Result<ModelResponse> {
+        let existing_model = self.check_update_model(id, &request).await?;
+
+        let expected_updated_at = expected_updated_at(request.version)?;
 
         let model_data = model::ActiveModel {
             id: Set(existing_model.id),
             name: Set(request.name.unwrap_or(existing_model.name)),
-            series: Set(request.series.or(existing_model.series)),
+            series_id: Set(request.series_id.or(existing_model.series_id)),
             category: Set(request.category.or(existing_model.category)),
             release_date: Set(request.release_date.or(existing_model.release_date)),
+            preorder_open_date: Set(request
+                .preorder_open_date
+                .or(existing_model.preorder_open_date)),
             status: Set(request.status.unwrap_or(existing_model.status)),
             manufacturer_id: Set(request
                 .manufacturer_id
@@ -121,15 +367,318 @@ where
             ..Default::default()
         };
 
-        let updated_model = self.model_repository.update(id, model_data).await?;
-        Ok(self.convert_to_model_response(updated_model))
+        let updated_model = self
+            .model_repository
+            .update_with_revision(
+                id,
+                model_data,
+                expected_updated_at,
+                edited_by,
+                None,
+                request.tags,
+            )
+            .await
+            .map_err(map_update_error)?;
+        self.publish_model(ChangeEventKind::ModelUpdated, updated_model, edited_by)
+            .await
+    }
+
+    pub async fn patch_model(
+        &self,
+        id: i32,
+        request: PatchModelRequest,
+        edited_by: Option<i32>,
+    ) -> anyhow::Result<ModelResponse> {
+        validate_tags(request.tags.as_deref())?;
+        self.validate_references(
+            Some(id),
+            ModelReferences {
+                manufacturer_id: request.manufacturer_id,
+                series_id: request.series_id.flatten(),
+                parent_id: request.parent_id.flatten(),
+            },
+        )
+        .await?;
+
+        let expected_updated_at = expected_updated_at(request.version)?;
+
+        // 只有请求中出现的字段才会被写入，其余列保持 NotSet
+        let model_data = model::ActiveModel {
+            id: Set(id),
+            name: request.name.map_or(NotSet, Set),
+            series_id: request.series_id.map_or(NotSet, Set),
+            category: request.category.map_or(NotSet, Set),
+            release_date: request.release_date.map_or(NotSet, Set),
+            preorder_open_date: request.preorder_open_date.map_or(NotSet, Set),
+            status: request.status.map_or(NotSet, Set),
+            manufacturer_id: request.manufacturer_id.map_or(NotSet, Set),
+            parent_id: request.parent_id.map_or(NotSet, Set),
+            notes: request.notes.map_or(NotSet, Set),
+            updated_at: Set(chrono::Utc::now().naive_utc()),
+            ..Default::default()
+        };
+
+        let patched_model = self
+            .model_repository
+            .update_with_revision(
+                id,
+                model_data,
+                expected_updated_at,
+                edited_by,
+                None,
+                request.tags,
+            )
+            .await
+            .map_err(map_update_error)?;
+        self.publish_model(ChangeEventKind::ModelUpdated, patched_model, edited_by)
+            .await
+    }
+
+    /// 按发售日期与价格更新时间自动调整模型状态，每次修改都保存修订并推送更新事件；
+    /// `stale_cutoff` 为空时不标记疑似停产
+    pub async fn apply_status_transitions(
+        &self,
+        today: chrono::NaiveDate,
+        stale_cutoff: Option<chrono::NaiveDateTime>,
+    ) -> anyhow::Result<StatusTransitionReport> {
+        let mut report = StatusTransitionReport::default();
+
+        for model in self
+            .model_repository
+            .find_released_pre_orders(today)
+            .await?
+        {
+            let id = model.id;
+            let model_data = model::ActiveModel {
+                status: Set(ModelStatus::InStock),
+                ..Default::default()
+            };
+            if self
+                .apply_transition(model, model_data, StatusChangeReason::Released)
+                .await?
+            {
+                report.released.push(id);
+            } else {
+                report.skipped.push(id);
+            }
+        }
+
+        let Some(cutoff) = stale_cutoff else {
+            return Ok(report);
+        };
+
+        for model in self.model_repository.find_refreshed_flagged(cutoff).await? {
+            let id = model.id;
+            let model_data = model::ActiveModel {
+                possibly_discontinued_at: Set(None),
+                ..Default::default()
+            };
+            if self
+                .apply_transition(model, model_data, StatusChangeReason::StaleCleared)
+                .await?
+            {
+                report.cleared.push(id);
+            } else {
+                report.skipped.push(id);
+            }
+        }
+
+        for model in self.model_repository.find_stale_in_stock(cutoff).await? {
+            let id = model.id;
+            let model_data = model::ActiveModel {
+                possibly_discontinued_at: Set(Some(chrono::Utc::now().naive_utc())),
+                ..Default::default()
+            };
+            if self
+                .apply_transition(model, model_data, StatusChangeReason::Stale)
+                .await?
+            {
+                report.flagged.push(id);
+            } else {
+                report.skipped.push(id);
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// 以查询时的 `updated_at` 作为乐观锁保存修改，期间被他人修改或删除时返回 `false`
+    async fn apply_transition(
+        &self,
+        model: model::Model,
+        mut model_data: model::ActiveModel,
+        reason: StatusChangeReason,
+    ) -> anyhow::Result<bool> {
+        model_data.updated_at = Set(chrono::Utc::now().naive_utc());
+        match self
+            .model_repository
+            .update_with_revision(
+                model.id,
+                model_data,
+                Some(model.updated_at),
+                None,
+                Some(reason.as_str()),
+                None,
+            )
+            .await
+        {
+            Ok(updated) => {
+                tracing::info!("模型 {} 自动状态流转: {}", updated.id, reason.as_str());
+                self.publish_model(ChangeEventKind::ModelUpdated, updated, None)
+                    .await?;
+                Ok(true)
+            }
+            Err(DbErr::RecordNotUpdated | DbErr::RecordNotFound(_)) => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn get_model_revisions(
+        &self,
+        model_id: i32,
+    ) -> anyhow::Result<Vec<ModelRevisionResponse>> {
+        let revisions = self.model_repository.find_revisions(model_id).await?;
+        let responses: Vec<ModelRevisionResponse> = revisions
+            .into_iter()
+            .map(|r| self.convert_to_revision_response(r))
+            .collect();
+        Ok(responses)
+    }
+
+    /// 将模型恢复到指定修订版本；恢复本身也会产生一条新的修订，便于再次撤销
+    pub async fn restore_model_revision(
+        &self,
+        model_id: i32,
+        revision: i32,
+        expected_version: Option<i64>,
+        edited_by: Option<i32>,
+    ) -> anyhow::Result<Option<ModelResponse>> {
+        let expected_updated_at = expected_updated_at(expected_version)?;
+        let Some(revision) = self
+            .model_repository
+            .find_revision(model_id, revision)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let current = self
+            .model_repository
+            .find_by_id(model_id, None)
+            .await?
+            .ok_or(ServiceError::NotFound)?;
+        let snapshot = merge_snapshot(&current, revision.snapshot)?;
+
+        self.validate_references(
+            Some(model_id),
+            ModelReferences {
+                manufacturer_id: Some(snapshot.manufacturer_id),
+                series_id: snapshot.series_id,
+                parent_id: snapshot.parent_id,
+            },
+        )
+        .await?;
+
+        let model_data = model::ActiveModel {
+            id: Set(model_id),
+            name: Set(snapshot.name),
+            series_id: Set(snapshot.series_id),
+            category: Set(snapshot.category),
+            release_date: Set(snapshot.release_date),
+            preorder_open_date: Set(snapshot.preorder_open_date),
+            status: Set(snapshot.status),
+            manufacturer_id: Set(snapshot.manufacturer_id),
+            parent_id: Set(snapshot.parent_id),
+            notes: Set(snapshot.notes),
+            updated_at: Set(chrono::Utc::now().naive_utc()),
+            ..Default::default()
+        };
+
+        let restored_model = self
+            .model_repository
+            .update_with_revision(
+                model_id,
+                model_data,
+                expected_updated_at,
+                edited_by,
+                None,
+                None,
+            )
+            .await
+            .map_err(map_update_error)?;
+        Ok(Some(
+            self.publish_model(ChangeEventKind::ModelUpdated, restored_model, edited_by)
+                .await?,
+        ))
     }
 
-    pub async fn delete_model(&self, id: i32) -> anyhow::Result<()> {
-        self.model_repository.delete(id).await?;
+    pub async fn delete_model(&self, id: i32, expected_version: Option<i64>) -> anyhow::Result<()> {
+        let expected_updated_at = expected_updated_at(expected_version)?;
+
+        let image_keys = self.model_repository.find_image_keys(id).await?;
+        let result = self
+            .model_repository
+            .delete(id, expected_updated_at)
+            .await?;
+        if expected_updated_at.is_some() && result.rows_affected == 0 {
+            return Err(ServiceError::PreconditionFailed.into());
+        }
+
+        // 图片记录随外键级联删除，存储中的文件需单独清理
+        if result.rows_affected > 0 {
+            let keys: Vec<&str> = image_keys.iter().map(String::as_str).collect();
+            remove_objects(&self.image_storage, &keys).await;
+            self.events.publish(
+                ChangeEventKind::ModelDeleted,
+                id,
+                None,
+                &serde_json::json!({ "id": id }),
+            );
+        }
         Ok(())
     }
 
+    /// 将 `source_id` 合并到 `id`：关联数据转移到目标模型后删除来源模型
+    pub async fn merge_models(
+        &self,
+        id: i32,
+        request: MergeModelRequest,
+        edited_by: Option<i32>,
+    ) -> anyhow::Result<MergeModelResponse> {
+        let expected_updated_at = expected_updated_at(request.version)?;
+        let source_id = request.source_id;
+
+        let target = self
+            .model_repository
+            .find_by_id(id, None)
+            .await?
+            .ok_or(ServiceError::NotFound)?;
+        if let Some(message) = self.check_merge_source(&target, source_id).await? {
+            return Err(
+                ServiceError::Validation(vec![FieldError::new("source_id", message)]).into(),
+            );
+        }
+
+        let (merged, moved) = self
+            .model_repository
+            .merge_models(id, source_id, expected_updated_at, edited_by)
+            .await
+            .map_err(map_update_error)?;
+
+        self.events.publish(
+            ChangeEventKind::ModelDeleted,
+            source_id,
+            edited_by,
+            &serde_json::json!({ "id": source_id, "merged_into": id }),
+        );
+        Ok(MergeModelResponse {
+            model: self
+                .publish_model(ChangeEventKind::ModelUpdated, merged, edited_by)
+                .await?,
+            moved,
+        })
+    }
+
     pub async fn toggle_favorite(
         &self,
         user_id: i32,
@@ -148,12 +697,297 @@ where
         model_id: i32,
         request: TogglePurchaseRequest,
     ) -> anyhow::Result<()> {
-        self.model_repository
+        let kind = match self
+            .model_repository
             .toggle_purchase(user_id, model_id, request.notes)
-            .await?;
+            .await?
+        {
+            PurchaseToggle::Added => ChangeEventKind::PurchaseAdded,
+            PurchaseToggle::Removed => ChangeEventKind::PurchaseRemoved,
+            PurchaseToggle::HasBuildLog(entries) => {
+                return Err(ServiceError::Conflict(format!(
+                    "该购买记录下还有 {} 条制作日志，无法取消购买",
+                    entries
+                ))
+                .into())
+            }
+        };
+        self.events.publish(
+            kind,
+            model_id,
+            Some(user_id),
+            &serde_json::json!({ "model_id": model_id, "user_id": user_id }),
+        );
         Ok(())
     }
 
+    /// 更新购买记录的备注与预售日期，未购买时返回 `NotFound`
+    pub async fn update_purchase(
+        &self,
+        user_id: i32,
+        model_id: i32,
+        request: UpdatePurchaseRequest,
+    ) -> anyhow::Result<PurchaseResponse> {
+        let purchase = user_model_purchase::ActiveModel {
+            notes: Set(request.notes),
+            payment_due_date: Set(request.payment_due_date),
+            ship_date: Set(request.ship_date),
+            ..Default::default()
+        };
+        let updated = self
+            .model_repository
+            .update_purchase(user_id, model_id, purchase)
+            .await?
+            .ok_or(ServiceError::NotFound)?;
+        Ok(PurchaseResponse {
+            model_id: updated.model_id,
+            notes: updated.notes,
+            payment_due_date: updated.payment_due_date,
+            ship_date: updated.ship_date,
+            created_at: updated.created_at,
+        })
+    }
+
+    /// 模型不存在时返回 `None`；`currency` 非空时按各条价格记录当天的汇率换算
+    pub async fn get_prices(
+        &self,
+        model_id: i32,
+        currency: Option<String>,
+    ) -> anyhow::Result<Option<Vec<PriceHistoryResponse>>> {
+        let currency = self.currency_service.target(currency).await?;
+        if self
+            .model_repository
+            .find_by_id(model_id, None)
+            .await?
+            .is_none()
+        {
+            return Ok(None);
+        }
+        let prices = self.model_repository.find_prices(model_id).await?;
+
+        let Some(currency) = currency else {
+            return Ok(Some(prices.into_iter().map(convert_price).collect()));
+        };
+        let rates = self
+            .currency_service
+            .rate_table(
+                prices
+                    .iter()
+                    .map(|p| p.currency.clone())
+                    .chain([currency.clone()]),
+            )
+            .await?;
+        Ok(Some(
+            prices
+                .into_iter()
+                .map(|price| {
+                    let converted = rates.convert(
+                        price.price,
+                        &price.currency,
+                        &currency,
+                        price.recorded_at.date(),
+                    );
+                    PriceHistoryResponse {
+                        converted: Some(converted),
+                        ..convert_price(price)
+                    }
+                })
+                .collect(),
+        ))
+    }
+
+    /// 已购模型按最新价格换算到 `currency`（默认 CNY）后的总价值
+    pub async fn get_collection_value(
+        &self,
+        user_id: i32,
+        currency: Option<String>,
+    ) -> anyhow::Result<CollectionValueResponse> {
+        let currency = self
+            .currency_service
+            .target(currency)
+            .await?
+            .unwrap_or_else(|| DEFAULT_CURRENCY.to_string());
+        let models = self.model_repository.find_purchased_models(user_id).await?;
+        let model_ids: Vec<i32> = models.iter().map(|m| m.id).collect();
+        let mut prices = self.model_repository.find_latest_prices(model_ids).await?;
+        let rates = self
+            .currency_service
+            .rate_table(
+                prices
+                    .values()
+                    .map(|p| p.currency.clone())
+                    .chain([currency.clone()]),
+            )
+            .await?;
+
+        let mut response = CollectionValueResponse {
+            currency: currency.clone(),
+            total_value: 0.0,
+            purchased: models.len() as u64,
+            missing_price: Vec::new(),
+            missing_rate: Vec::new(),
+            items: Vec::new(),
+        };
+        for model in models {
+            let Some(price) = prices.remove(&model.id) else {
+                response.missing_price.push(model.id);
+                continue;
+            };
+            let converted = rates.convert(
+                price.price,
+                &price.currency,
+                &currency,
+                price.recorded_at.date(),
+            );
+            match converted.amount {
+                Some(amount) => response.total_value += amount,
+                None => response.missing_rate.push(model.id),
+            }
+            response.items.push(CollectionValueItem {
+                model_id: model.id,
+                name: model.name,
+                price: price.price,
+                price_currency: price.currency,
+                recorded_at: price.recorded_at,
+                converted,
+            });
+        }
+        response.total_value = rates.round(response.total_value, &currency);
+        Ok(response)
+    }
+
+    /// 各店铺的最新报价，按换算到 `currency`（默认 CNY）后的价格从低到高排列，
+    /// 缺少汇率的排在最后；模型不存在时返回 `None`
+    pub async fn get_prices_by_shop(
+        &self,
+        model_id: i32,
+        currency: Option<String>,
+    ) -> anyhow::Result<Option<Vec<ShopPriceResponse>>> {
+        let currency = self
+            .currency_service
+            .target(currency)
+            .await?
+            .unwrap_or_else(|| DEFAULT_CURRENCY.to_string());
+        if self
+            .model_repository
+            .find_by_id(model_id, None)
+            .await?
+            .is_none()
+        {
+            return Ok(None);
+        }
+
+        let prices = self
+            .model_repository
+            .find_latest_prices_by_shop(model_id)
+            .await?;
+        let rates = self
+            .currency_service
+            .rate_table(
+                prices
+                    .iter()
+                    .map(|(p, _)| p.currency.clone())
+                    .chain([currency.clone()]),
+            )
+            .await?;
+
+        let mut responses: Vec<ShopPriceResponse> = prices
+            .into_iter()
+            .map(|(price, shop)| {
+                let converted = rates.convert(
+                    price.price,
+                    &price.currency,
+                    &currency,
+                    price.recorded_at.date(),
+                );
+                ShopPriceResponse {
+                    shop: convert_shop(shop),
+                    latest_price: PriceHistoryResponse {
+                        converted: Some(converted),
+                        ..convert_price(price)
+                    },
+                }
+            })
+            .collect();
+        let amount = |r: &ShopPriceResponse| {
+            r.latest_price
+                .converted
+                .as_ref()
+                .and_then(|c| c.amount)
+                .unwrap_or(f64::INFINITY)
+        };
+        responses.sort_by(|a, b| {
+            amount(a)
+                .total_cmp(&amount(b))
+                .then(b.latest_price.recorded_at.cmp(&a.latest_price.recorded_at))
+        });
+        Ok(Some(responses))
+    }
+
+    pub async fn add_price(
+        &self,
+        model_id: i32,
+        request: CreatePriceRequest,
+        user_id: i32,
+    ) -> anyhow::Result<PriceHistoryResponse> {
+        self.model_repository
+            .find_by_id(model_id, None)
+            .await?
+            .ok_or(ServiceError::NotFound)?;
+
+        let currency = self
+            .currency_service
+            .normalize(
+                request.currency.as_deref().unwrap_or(DEFAULT_CURRENCY),
+                "currency",
+            )
+            .await?;
+
+        let source = request
+            .source
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty());
+        let shop = match (request.shop_id, &source) {
+            (Some(shop_id), _) => Some(
+                self.model_repository
+                    .find_shop(shop_id)
+                    .await?
+                    .ok_or_else(|| {
+                        ServiceError::Validation(vec![FieldError::new("shop_id", "店铺不存在")])
+                    })?,
+            ),
+            (None, Some(source)) => self.model_repository.find_shop_by_name(source).await?,
+            (None, None) => None,
+        };
+
+        let price = self
+            .model_repository
+            .add_price(price_history::ActiveModel {
+                price: Set(request.price),
+                currency: Set(currency),
+                shop_id: Set(shop.as_ref().map(|s| s.id)),
+                source: Set(source.or(shop.map(|s| s.name))),
+                notes: Set(request.notes),
+                model_id: Set(model_id),
+                recorded_by: Set(Some(user_id)),
+                recorded_at: Set(chrono::Utc::now().naive_utc()),
+                ..Default::default()
+            })
+            .await?;
+        // 价格已保存，提醒生成失败只记日志
+        if let Err(e) = self.price_alerts.notify_price_drop(&price).await {
+            tracing::error!("生成降价提醒失败: {:?}", e);
+        }
+        let response = convert_price(price);
+        self.events.publish(
+            ChangeEventKind::PriceAdded,
+            model_id,
+            Some(user_id),
+            &response,
+        );
+        Ok(response)
+    }
+
     pub async fn get_favorites(
         &self,
         user_id: i32,
@@ -164,12 +998,9 @@ where
             .model_repository
             .get_favorites(user_id, page, per_page)
             .await?;
-        let total_pages = (total + per_page - 1) / per_page;
+        let total_pages = total.div_ceil(per_page);
 
-        let model_responses: Vec<ModelResponse> = models
-            .into_iter()
-            .map(|m| self.convert_to_model_response(m))
-            .collect();
+        let model_responses = self.build_model_responses(models).await?;
 
         Ok(ModelListResponse {
             models: model_responses,
@@ -180,6 +1011,109 @@ where
         })
     }
 
+    pub async fn update_wishlist(
+        &self,
+        user_id: i32,
+        model_id: i32,
+        request: UpdateWishlistRequest,
+    ) -> anyhow::Result<WishlistItemResponse> {
+        let currency = self
+            .currency_service
+            .normalize(
+                request
+                    .target_currency
+                    .as_deref()
+                    .unwrap_or(DEFAULT_CURRENCY),
+                "target_currency",
+            )
+            .await?;
+
+        let wishlist = user_model_favorite::ActiveModel {
+            notes: Set(request.notes),
+            target_price: Set(request.target_price),
+            target_currency: Set(currency),
+            priority: Set(request.priority.unwrap_or(DEFAULT_WISHLIST_PRIORITY)),
+            ..Default::default()
+        };
+        self.model_repository
+            .update_wishlist(user_id, model_id, wishlist)
+            .await?
+            .ok_or(ServiceError::NotFound)?;
+
+        let mut items = self.get_wishlist(user_id, None).await?;
+        let index = items
+            .iter()
+            .position(|item| item.model.id == model_id)
+            .ok_or(ServiceError::NotFound)?;
+        Ok(items.swap_remove(index))
+    }
+
+    /// `currency` 非空时额外返回换算后的最新价格
+    pub async fn get_wishlist(
+        &self,
+        user_id: i32,
+        currency: Option<String>,
+    ) -> anyhow::Result<Vec<WishlistItemResponse>> {
+        let currency = self.currency_service.target(currency).await?;
+        let rows = self.model_repository.find_wishlist(user_id).await?;
+        let (favorites, models): (Vec<_>, Vec<_>) = rows.into_iter().unzip();
+
+        let model_ids: Vec<i32> = models.iter().map(|m| m.id).collect();
+        let mut prices = self.model_repository.find_latest_prices(model_ids).await?;
+        let responses = self.build_model_responses(models).await?;
+        let rates = self
+            .currency_service
+            .rate_table(
+                prices
+                    .values()
+                    .map(|p| p.currency.clone())
+                    .chain(favorites.iter().map(|f| f.target_currency.clone()))
+                    .chain(currency.clone()),
+            )
+            .await?;
+
+        Ok(favorites
+            .into_iter()
+            .zip(responses)
+            .map(|(favorite, model)| {
+                let price = prices.remove(&model.id);
+                // 币种不同时按价格记录当天的汇率换算到目标币种后比较
+                let target_reached = match (&price, favorite.target_price) {
+                    (Some(price), Some(target)) => rates
+                        .convert(
+                            price.price,
+                            &price.currency,
+                            &favorite.target_currency,
+                            price.recorded_at.date(),
+                        )
+                        .amount
+                        .is_some_and(|amount| amount <= target),
+                    _ => false,
+                };
+                let latest_price_converted = match (&price, &currency) {
+                    (Some(price), Some(currency)) => Some(rates.convert(
+                        price.price,
+                        &price.currency,
+                        currency,
+                        price.recorded_at.date(),
+                    )),
+                    _ => None,
+                };
+                WishlistItemResponse {
+                    model,
+                    notes: favorite.notes,
+                    target_price: favorite.target_price,
+                    target_currency: favorite.target_currency,
+                    priority: favorite.priority,
+                    latest_price: price.as_ref().map(|p| p.price),
+                    latest_price_currency: price.map(|p| p.currency),
+                    latest_price_converted,
+                    target_reached,
+                }
+            })
+            .collect())
+    }
+
     pub async fn get_purchases(
         &self,
         user_id: i32,
@@ -190,12 +1124,9 @@ where
             .model_repository
             .get_purchases(user_id, page, per_page)
             .await?;
-        let total_pages = (total + per_page - 1) / per_page;
+        let total_pages = total.div_ceil(per_page);
 
-        let model_responses: Vec<ModelResponse> = models
-            .into_iter()
-            .map(|m| self.convert_to_model_response(m))
-            .collect();
+        let model_responses = self.build_model_responses(models).await?;
 
         Ok(ModelListResponse {
             models: model_responses,
@@ -206,19 +1137,510 @@ where
         })
     }
 
-    fn convert_to_model_response(&self, model: model::Model) -> ModelResponse {
+    /// 重复检测报表：同厂商、同等级下名称相似度不低于 `threshold` 的模型对，按相似度降序
+    pub async fn get_duplicate_report(
+        &self,
+        threshold: Option<f64>,
+    ) -> anyhow::Result<Vec<DuplicatePair>> {
+        let threshold = threshold.unwrap_or(DUPLICATE_THRESHOLD).clamp(0.0, 1.0);
+        let models = self.model_repository.find_all_for_duplicate_check().await?;
+
+        let mut pairs = Vec::new();
+        for group in models
+            .chunk_by(|a, b| a.manufacturer_id == b.manufacturer_id && a.category == b.category)
+        {
+            for (i, first) in group.iter().enumerate() {
+                for second in &group[i + 1..] {
+                    let similarity = name_similarity(&first.name, &second.name);
+                    if similarity >= threshold {
+                        pairs.push(DuplicatePair {
+                            manufacturer_id: first.manufacturer_id,
+                            category: first.category,
+                            first_id: first.id,
+                            first_name: first.name.clone(),
+                            second_id: second.id,
+                            second_name: second.name.clone(),
+                            similarity,
+                        });
+                    }
+                }
+            }
+        }
+
+        pairs.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+        Ok(pairs)
+    }
+
+    async fn find_possible_duplicates(
+        &self,
+        name: &str,
+        manufacturer_id: i32,
+        category: Option<Grade>,
+    ) -> anyhow::Result<Vec<DuplicateCandidate>> {
+        let existing = self
+            .model_repository
+            .find_by_manufacturer_and_category(manufacturer_id, category)
+            .await?;
+
+        let mut duplicates: Vec<DuplicateCandidate> = existing
+            .into_iter()
+            .filter_map(|m| {
+                let similarity = name_similarity(name, &m.name);
+                (similarity >= DUPLICATE_THRESHOLD).then_some(DuplicateCandidate {
+                    id: m.id,
+                    name: m.name,
+                    similarity,
+                })
+            })
+            .collect();
+        duplicates.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+        Ok(duplicates)
+    }
+
+    /// 校验厂商、系列与父模型引用，并保证变体关系不成环、不超过层级上限
+    async fn validate_references(
+        &self,
+        model_id: Option<i32>,
+        references: ModelReferences,
+    ) -> anyhow::Result<()> {
+        let mut errors = Vec::new();
+
+        if let Some(manufacturer_id) = references.manufacturer_id {
+            if !self
+                .model_repository
+                .manufacturer_exists(manufacturer_id)
+                .await?
+            {
+                errors.push(FieldError::new("manufacturer_id", "厂商不存在"));
+            }
+        }
+
+        if let Some(series_id) = references.series_id {
+            if !self.model_repository.series_exists(series_id).await? {
+                errors.push(FieldError::new("series_id", "系列不存在"));
+            }
+        }
+
+        if let Some(parent_id) = references.parent_id {
+            if let Some(message) = self.check_parent(model_id, parent_id).await? {
+                errors.push(FieldError::new("parent_id", message));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ServiceError::Validation(errors).into())
+        }
+    }
+
+    async fn check_parent(
+        &self,
+        model_id: Option<i32>,
+        parent_id: i32,
+    ) -> anyhow::Result<Option<String>> {
+        if model_id == Some(parent_id) {
+            return Ok(Some("模型不能以自身作为父模型".to_string()));
+        }
+
+        let Some(parent) = self.model_repository.find_by_id(parent_id, None).await? else {
+            return Ok(Some("父模型不存在".to_string()));
+        };
+
+        // 沿 parent_id 向上查找祖先，计算父模型深度并检测环
+        let mut parent_depth = 0;
+        let mut next_ancestor = parent.parent_id;
+        while let Some(ancestor_id) = next_ancestor {
+            if model_id == Some(ancestor_id) {
+                return Ok(Some("父子关系不能形成循环".to_string()));
+            }
+            parent_depth += 1;
+            if parent_depth >= MAX_VARIANT_DEPTH {
+                break;
+            }
+            next_ancestor = self
+                .model_repository
+                .find_by_id(ancestor_id, None)
+                .await?
+                .and_then(|m| m.parent_id);
+        }
+
+        let subtree_height = match model_id {
+            Some(id) => self.subtree_height(id).await?,
+            None => 0,
+        };
+        if parent_depth + 1 + subtree_height > MAX_VARIANT_DEPTH {
+            return Ok(Some(format!("变体层级不能超过{}层", MAX_VARIANT_DEPTH)));
+        }
+
+        Ok(None)
+    }
+
+    /// 校验合并来源：不能是目标自身或目标的间接祖先，且来源的变体挂到目标下后不超过层级上限
+    async fn check_merge_source(
+        &self,
+        target: &model::Model,
+        source_id: i32,
+    ) -> anyhow::Result<Option<String>> {
+        if source_id == target.id {
+            return Ok(Some("不能将模型与自身合并".to_string()));
+        }
+        if self
+            .model_repository
+            .find_by_id(source_id, None)
+            .await?
+            .is_none()
+        {
+            return Ok(Some("来源模型不存在".to_string()));
+        }
+        // 目标是来源的直接变体时，合并后目标接替来源的位置，层级不会加深
+        if target.parent_id == Some(source_id) {
+            return Ok(None);
+        }
+
+        let mut target_depth = 0;
+        let mut next_ancestor = target.parent_id;
+        while let Some(ancestor_id) = next_ancestor {
+            if ancestor_id == source_id {
+                return Ok(Some("不能将祖先模型合并到其间接变体中".to_string()));
+            }
+            target_depth += 1;
+            if target_depth > MAX_VARIANT_DEPTH {
+                break;
+            }
+            next_ancestor = self
+                .model_repository
+                .find_by_id(ancestor_id, None)
+                .await?
+                .and_then(|m| m.parent_id);
+        }
+
+        if target_depth + self.subtree_height(source_id).await? > MAX_VARIANT_DEPTH {
+            return Ok(Some(format!("合并后变体层级将超过{}层", MAX_VARIANT_DEPTH)));
+        }
+
+        Ok(None)
+    }
+
+    /// 模型下方变体树的高度，超过层级上限后不再继续向下查找
+    async fn subtree_height(&self, model_id: i32) -> anyhow::Result<usize> {
+        let mut height = 0;
+        let mut level = vec![model_id];
+
+        while height <= MAX_VARIANT_DEPTH {
+            let mut next_level = Vec::new();
+            for id in level {
+                let variants = self.model_repository.find_variants(id, None).await?;
+                next_level.extend(variants.into_iter().map(|v| v.id));
+            }
+            if next_level.is_empty() {
+                break;
+            }
+            height += 1;
+            level = next_level;
+        }
+
+        Ok(height)
+    }
+
+    /// 构建响应并广播模型变更事件
+    async fn publish_model(
+        &self,
+        kind: ChangeEventKind,
+        model: model::Model,
+        actor_id: Option<i32>,
+    ) -> anyhow::Result<ModelResponse> {
+        let response = self.build_model_response(model).await?;
+        self.events.publish(kind, response.id, actor_id, &response);
+        Ok(response)
+    }
+
+    async fn build_model_response(&self, model: model::Model) -> anyhow::Result<ModelResponse> {
+        let mut responses = self.build_model_responses(vec![model]).await?;
+        Ok(responses.remove(0))
+    }
+
+    /// 批量转换为响应，并一次性查询关联的系列名称与标签
+    async fn build_model_responses(
+        &self,
+        models: Vec<model::Model>,
+    ) -> anyhow::Result<Vec<ModelResponse>> {
+        let mut series_ids: Vec<i32> = models.iter().filter_map(|m| m.series_id).collect();
+        series_ids.sort_unstable();
+        series_ids.dedup();
+        let series_names = self.model_repository.find_series_names(series_ids).await?;
+
+        let model_ids: Vec<i32> = models.iter().map(|m| m.id).collect();
+        let mut tag_names = self
+            .model_repository
+            .find_tag_names(model_ids.clone())
+            .await?;
+        let mut primary_images = self
+            .model_repository
+            .find_primary_image_keys(model_ids)
+            .await?;
+
+        let responses = models
+            .into_iter()
+            .map(|m| {
+                let extras = ResponseExtras {
+                    series_name: m.series_id.and_then(|id| series_names.get(&id).cloned()),
+                    tags: tag_names.remove(&m.id).unwrap_or_default(),
+                    primary_image_key: primary_images.remove(&m.id),
+                };
+                self.convert_to_model_response(m, extras)
+            })
+            .collect();
+        Ok(responses)
+    }
+
+    fn convert_to_model_response(
+        &self,
+        model: model::Model,
+        extras: ResponseExtras,
+    ) -> ModelResponse {
         ModelResponse {
             id: model.id,
             name: model.name,
-            series: model.series,
+            series_id: model.series_id,
+            series: extras.series_name,
             category: model.category,
             release_date: model.release_date,
+            preorder_open_date: model.preorder_open_date,
             status: model.status,
             manufacturer_id: model.manufacturer_id,
             parent_id: model.parent_id,
             notes: model.notes,
+            possibly_discontinued_at: model.possibly_discontinued_at,
+            tags: extras.tags,
+            primary_image_url: extras
+                .primary_image_key
+                .map(|key| self.image_storage.public_url(&key)),
             created_at: model.created_at,
             updated_at: model.updated_at,
+            version: version_of(model.updated_at),
         }
     }
+
+    fn convert_to_revision_response(
+        &self,
+        revision: model_revision::Model,
+    ) -> ModelRevisionResponse {
+        ModelRevisionResponse {
+            id: revision.id,
+            model_id: revision.model_id,
+            revision: revision.revision,
+            snapshot: revision.snapshot,
+            edited_by: revision.edited_by,
+            change_reason: revision.change_reason,
+            created_at: revision.created_at,
+        }
+    }
+}
+
+/// 标签名及其 slug 不能超过数据库列长度，空白标签在保存时忽略
+pub(crate) fn validate_tags(tags: Option<&[String]>) -> Result<(), ServiceError> {
+    let too_long = tags.unwrap_or_default().iter().any(|name| {
+        let name = name.trim();
+        name.chars().count() > MAX_TAG_LENGTH || tag::slugify(name).chars().count() > MAX_TAG_LENGTH
+    });
+    if too_long {
+        return Err(ServiceError::Validation(vec![FieldError::new(
+            "tags",
+            format!("标签不能超过{}个字符", MAX_TAG_LENGTH),
+        )]));
+    }
+    Ok(())
+}
+
+/// 将修订快照覆盖到当前数据上：快照生成之后才新增的字段（如 `preorder_open_date`）
+/// 保留当前值，避免恢复旧版本时被清空
+fn merge_snapshot(
+    current: &model::Model,
+    snapshot: serde_json::Value,
+) -> anyhow::Result<model::Model> {
+    let serde_json::Value::Object(fields) = snapshot else {
+        return Err(invalid_snapshot("快照不是 JSON 对象").into());
+    };
+    let mut merged = serde_json::to_value(current)?;
+    if let Some(merged) = merged.as_object_mut() {
+        merged.extend(fields);
+    }
+    serde_json::from_value(merged).map_err(|e| invalid_snapshot(e.to_string()).into())
+}
+
+fn invalid_snapshot(message: impl std::fmt::Display) -> ServiceError {
+    ServiceError::Validation(vec![FieldError::new(
+        "revision",
+        format!("修订快照无法恢复: {}", message),
+    )])
+}
+
+/// 自底向上组装变体树并汇总各分支；`children` 中的 ID 已按名称排序，
+/// 取出的节点从 `nodes` 中移除，因此即使数据中有环也不会重复访问
+fn build_family_node(
+    id: i32,
+    nodes: &mut HashMap<i32, FamilyNode>,
+    children: &HashMap<i32, Vec<i32>>,
+    round: &dyn Fn(f64) -> f64,
+) -> Option<FamilyNode> {
+    let mut node = nodes.remove(&id)?;
+
+    let price = node
+        .latest_price
+        .as_ref()
+        .and_then(|p| p.converted.as_ref())
+        .and_then(|c| c.amount);
+    let mut branch = FamilyBranchSummary {
+        model_count: 1,
+        owned_count: u64::from(node.owner_count > 0),
+        owner_total: node.owner_count,
+        priced_count: u64::from(price.is_some()),
+        min_price: price,
+        max_price: price,
+        total_price: price.unwrap_or_default(),
+    };
+
+    for child_id in children.get(&id).into_iter().flatten() {
+        let Some(child) = build_family_node(*child_id, nodes, children, round) else {
+            continue;
+        };
+        let summary = &child.branch;
+        branch.model_count += summary.model_count;
+        branch.owned_count += summary.owned_count;
+        branch.owner_total += summary.owner_total;
+        branch.priced_count += summary.priced_count;
+        branch.min_price = match (branch.min_price, summary.min_price) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        branch.max_price = match (branch.max_price, summary.max_price) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            (a, b) => a.or(b),
+        };
+        branch.total_price += summary.total_price;
+        node.variants.push(child);
+    }
+
+    branch.total_price = round(branch.total_price);
+    node.branch = branch;
+    Some(node)
+}
+
+pub(crate) fn convert_price(price: price_history::Model) -> PriceHistoryResponse {
+    PriceHistoryResponse {
+        id: price.id,
+        price: price.price,
+        currency: price.currency,
+        source: price.source,
+        shop_id: price.shop_id,
+        notes: price.notes,
+        model_id: price.model_id,
+        recorded_at: price.recorded_at,
+        converted: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use serde_json::json;
+
+    fn current_model() -> model::Model {
+        let now = NaiveDate::from_ymd_opt(2026, 10, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        model::Model {
+            id: 7,
+            name: "RX-78-2 Ver.3.0".to_string(),
+            series_id: Some(3),
+            category: Some(Grade::Mg),
+            release_date: NaiveDate::from_ymd_opt(2015, 8, 1),
+            preorder_open_date: NaiveDate::from_ymd_opt(2015, 5, 20),
+            status: ModelStatus::InStock,
+            manufacturer_id: 1,
+            parent_id: None,
+            notes: None,
+            possibly_discontinued_at: None,
+            created_by: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn restore_pre_migration_snapshot_keeps_missing_fields() {
+        // 系列迁移与预售日期加入之前的快照：只有自由文本 series，没有 series_id / preorder_open_date
+        let snapshot = json!({
+            "id": 7,
+            "name": "RX-78-2",
+            "series": "UC",
+            "category": "mg",
+            "release_date": "2015-08-01",
+            "status": "现货",
+            "manufacturer_id": 1,
+            "parent_id": null,
+            "notes": "旧备注",
+            "created_at": "2026-01-01T00:00:00",
+            "updated_at": "2026-01-01T00:00:00"
+        });
+
+        let restored = merge_snapshot(&current_model(), snapshot).unwrap();
+
+        assert_eq!(restored.name, "RX-78-2");
+        assert_eq!(restored.notes.as_deref(), Some("旧备注"));
+        assert_eq!(restored.status, ModelStatus::InStock);
+        assert_eq!(restored.series_id, Some(3));
+        assert_eq!(
+            restored.preorder_open_date,
+            NaiveDate::from_ymd_opt(2015, 5, 20)
+        );
+    }
+
+    #[test]
+    fn restore_snapshot_applies_explicit_nulls() {
+        let mut snapshot = serde_json::to_value(current_model()).unwrap();
+        snapshot["series_id"] = json!(null);
+        snapshot["preorder_open_date"] = json!(null);
+
+        let restored = merge_snapshot(&current_model(), snapshot).unwrap();
+
+        assert_eq!(restored.series_id, None);
+        assert_eq!(restored.preorder_open_date, None);
+    }
+
+    #[test]
+    fn restore_snapshot_with_unknown_status_is_validation_error() {
+        let mut snapshot = serde_json::to_value(current_model()).unwrap();
+        snapshot["status"] = json!("sold_out");
+
+        let err = merge_snapshot(&current_model(), snapshot).unwrap_err();
+
+        assert!(matches!(
+            err.downcast_ref::<ServiceError>(),
+            Some(ServiceError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn tags_within_column_length_are_accepted() {
+        let tags = vec![
+            "  UC  ".to_string(),
+            "字".repeat(MAX_TAG_LENGTH),
+            String::new(),
+        ];
+        assert!(validate_tags(Some(&tags)).is_ok());
+        assert!(validate_tags(None).is_ok());
+    }
+
+    #[test]
+    fn overlong_tag_is_validation_error() {
+        let tags = vec!["UC".to_string(), "a".repeat(MAX_TAG_LENGTH + 1)];
+        assert!(matches!(
+            validate_tags(Some(&tags)),
+            Err(ServiceError::Validation(errors)) if errors[0].field == "tags"
+        ));
+    }
 }
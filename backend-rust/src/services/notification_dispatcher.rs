@@ -0,0 +1,331 @@
+#![allow(dead_code)]
+
+use crate::config::NotificationConfig;
+use crate::domain::entities::enums::DeliveryChannel;
+use crate::domain::entities::notification_preference;
+use crate::infrastructure::notifications::SharedChannel;
+use crate::infrastructure::repositories::notification_repository::NotificationRepositoryTrait;
+use std::collections::{hash_map::Entry, HashMap};
+use std::time::Duration;
+
+/// 每轮最多处理的通知数与投递数
+const DISPATCH_BATCH_SIZE: u64 = 100;
+/// 领取投递记录后的租期，租期内其他实例不会重复领取
+const DELIVERY_LEASE_SECS: i64 = 300;
+/// 重试退避的基数与上限（秒）
+const RETRY_BASE_SECS: i64 = 30;
+const RETRY_MAX_SECS: i64 = 3600;
+
+/// 后台分发任务：将新通知按用户偏好拆分为各渠道的投递记录，再逐条投递并在失败时退避重试
+#[derive(Clone)]
+pub struct NotificationDispatcher<T>
+where
+    T: NotificationRepositoryTrait + Clone,
+{
+    notification_repository: T,
+    channels: HashMap<DeliveryChannel, SharedChannel>,
+    poll_interval: Duration,
+    max_attempts: i32,
+}
+
+impl<T> NotificationDispatcher<T>
+where
+    T: NotificationRepositoryTrait + Clone + 'static,
+{
+    pub fn new(
+        notification_repository: T,
+        channels: HashMap<DeliveryChannel, SharedChannel>,
+        config: &NotificationConfig,
+    ) -> Self {
+        Self {
+            notification_repository,
+            channels,
+            poll_interval: Duration::from_secs(config.poll_interval_secs.max(1)),
+            max_attempts: config.max_attempts.max(1),
+        }
+    }
+
+    /// 在后台循环运行，单轮出错只记录日志，不会终止任务
+    pub fn spawn(self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.poll_interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.run_once().await {
+                    tracing::error!("通知分发失败: {:?}", e);
+                }
+            }
+        })
+    }
+
+    pub async fn run_once(&self) -> anyhow::Result<()> {
+        self.dispatch_new().await?;
+        self.deliver_due().await
+    }
+
+    async fn dispatch_new(&self) -> anyhow::Result<()> {
+        let notifications = self
+            .notification_repository
+            .find_undispatched(DISPATCH_BATCH_SIZE)
+            .await?;
+        let mut preferences: HashMap<i32, notification_preference::Model> = HashMap::new();
+
+        for notification in notifications {
+            if let Entry::Vacant(entry) = preferences.entry(notification.user_id) {
+                let found = self
+                    .notification_repository
+                    .find_preferences(notification.user_id)
+                    .await?
+                    .unwrap_or_else(|| {
+                        notification_preference::Model::default_for(notification.user_id)
+                    });
+                entry.insert(found);
+            }
+            let user_preferences = &preferences[&notification.user_id];
+
+            let channels: Vec<DeliveryChannel> = self
+                .channels
+                .values()
+                .filter(|channel| channel.enabled_for(user_preferences))
+                .map(|channel| channel.kind())
+                .collect();
+            self.notification_repository
+                .schedule_deliveries(notification.id, channels)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn deliver_due(&self) -> anyhow::Result<()> {
+        let deliveries = self
+            .notification_repository
+            .claim_due_deliveries(DISPATCH_BATCH_SIZE, DELIVERY_LEASE_SECS)
+            .await?;
+
+        for (delivery, notification) in deliveries {
+            let attempts = delivery.attempts + 1;
+            let result = match self.channels.get(&delivery.channel) {
+                Some(channel) => {
+                    // 投递时重新读取偏好，用户在重试期间修改的地址会立即生效
+                    let preferences = self
+                        .notification_repository
+                        .find_preferences(notification.user_id)
+                        .await?
+                        .unwrap_or_else(|| {
+                            notification_preference::Model::default_for(notification.user_id)
+                        });
+                    channel.deliver(&notification, &preferences).await
+                }
+                None => Err(anyhow::anyhow!(
+                    "channel {:?} is not configured",
+                    delivery.channel
+                )),
+            };
+
+            match result {
+                Ok(()) => {
+                    self.notification_repository
+                        .mark_delivered(delivery.id)
+                        .await?;
+                }
+                Err(e) => {
+                    let next_attempt_at = next_attempt_at(
+                        attempts,
+                        self.max_attempts,
+                        chrono::Utc::now().naive_utc(),
+                    );
+                    tracing::warn!(
+                        "通知 {} 通过 {:?} 投递失败（第 {} 次）: {:?}",
+                        notification.id,
+                        delivery.channel,
+                        attempts,
+                        e
+                    );
+                    self.notification_repository
+                        .mark_failed(delivery.id, e.to_string(), next_attempt_at)
+                        .await?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 第 `attempts` 次投递失败后的下次尝试时间，达到 `max_attempts` 次时为空，表示不再重试
+fn next_attempt_at(
+    attempts: i32,
+    max_attempts: i32,
+    now: chrono::NaiveDateTime,
+) -> Option<chrono::NaiveDateTime> {
+    (attempts < max_attempts).then(|| now + chrono::Duration::seconds(retry_delay_secs(attempts)))
+}
+
+/// 指数退避：30s、60s、120s……最长 1 小时
+fn retry_delay_secs(attempts: i32) -> i64 {
+    let exponent = (attempts - 1).clamp(0, 16) as u32;
+    (RETRY_BASE_SECS * 2_i64.pow(exponent)).min(RETRY_MAX_SECS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::notification;
+    use crate::infrastructure::notifications::NotificationChannel;
+    use crate::test_support::{sample_delivery, sample_notification, FakeNotificationRepository};
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    /// 按预设结果投递并计数的渠道
+    struct FakeChannel {
+        succeed: bool,
+        calls: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl NotificationChannel for FakeChannel {
+        fn kind(&self) -> DeliveryChannel {
+            DeliveryChannel::Webhook
+        }
+
+        fn enabled_for(&self, _preferences: &notification_preference::Model) -> bool {
+            true
+        }
+
+        async fn deliver(
+            &self,
+            _notification: &notification::Model,
+            _preferences: &notification_preference::Model,
+        ) -> anyhow::Result<()> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if self.succeed {
+                Ok(())
+            } else {
+                Err(anyhow::anyhow!("webhook responded with 500"))
+            }
+        }
+    }
+
+    fn dispatcher(
+        repository: FakeNotificationRepository,
+        succeed: bool,
+        max_attempts: i32,
+    ) -> (
+        NotificationDispatcher<FakeNotificationRepository>,
+        Arc<AtomicUsize>,
+    ) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let channel: SharedChannel = Arc::new(FakeChannel {
+            succeed,
+            calls: calls.clone(),
+        });
+        let config = NotificationConfig {
+            poll_interval_secs: 10,
+            max_attempts,
+            allow_private_webhooks: false,
+            smtp: None,
+        };
+        let channels = HashMap::from([(DeliveryChannel::Webhook, channel)]);
+        (
+            NotificationDispatcher::new(repository, channels, &config),
+            calls,
+        )
+    }
+
+    fn queue(repository: &FakeNotificationRepository, channel: DeliveryChannel, attempts: i32) {
+        repository
+            .state
+            .lock()
+            .unwrap()
+            .due
+            .push((sample_delivery(channel, attempts), sample_notification(1)));
+    }
+
+    #[test]
+    fn retry_delay_doubles_up_to_one_hour() {
+        let delays: Vec<i64> = (1..=9).map(retry_delay_secs).collect();
+        assert_eq!(delays, [30, 60, 120, 240, 480, 960, 1920, 3600, 3600]);
+        assert_eq!(retry_delay_secs(i32::MAX), 3600);
+        assert_eq!(retry_delay_secs(0), 30);
+    }
+
+    #[test]
+    fn next_attempt_stops_at_max_attempts() {
+        let now = chrono::NaiveDate::from_ymd_opt(2026, 10, 19)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap();
+        assert_eq!(
+            next_attempt_at(1, 3, now),
+            Some(now + chrono::Duration::seconds(30))
+        );
+        assert_eq!(
+            next_attempt_at(2, 3, now),
+            Some(now + chrono::Duration::seconds(60))
+        );
+        assert_eq!(next_attempt_at(3, 3, now), None);
+        assert_eq!(next_attempt_at(1, 1, now), None);
+    }
+
+    #[tokio::test]
+    async fn failed_delivery_is_retried_with_backoff() {
+        let repository = FakeNotificationRepository::default();
+        queue(&repository, DeliveryChannel::Webhook, 0);
+        let (dispatcher, calls) = dispatcher(repository.clone(), false, 3);
+
+        let before = chrono::Utc::now().naive_utc();
+        dispatcher.run_once().await.unwrap();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        let state = repository.state.lock().unwrap();
+        assert!(state.delivered.is_empty());
+        let (id, error, next) = &state.failed[0];
+        assert_eq!(*id, 7);
+        assert!(error.contains("500"));
+        let delay = next.unwrap() - before;
+        assert!((29..=31).contains(&delay.num_seconds()));
+    }
+
+    #[tokio::test]
+    async fn last_attempt_marks_delivery_failed() {
+        let repository = FakeNotificationRepository::default();
+        // 已失败两次，本次为第 3 次
+        queue(&repository, DeliveryChannel::Webhook, 2);
+        let (dispatcher, _) = dispatcher(repository.clone(), false, 3);
+
+        dispatcher.run_once().await.unwrap();
+
+        let state = repository.state.lock().unwrap();
+        assert_eq!(state.failed.len(), 1);
+        assert_eq!(state.failed[0].2, None);
+    }
+
+    #[tokio::test]
+    async fn successful_delivery_is_marked_delivered() {
+        let repository = FakeNotificationRepository::default();
+        queue(&repository, DeliveryChannel::Webhook, 1);
+        let (dispatcher, calls) = dispatcher(repository.clone(), true, 3);
+
+        dispatcher.run_once().await.unwrap();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        let state = repository.state.lock().unwrap();
+        assert_eq!(state.delivered, [7]);
+        assert!(state.failed.is_empty());
+    }
+
+    #[tokio::test]
+    async fn unconfigured_channel_counts_as_failure() {
+        let repository = FakeNotificationRepository::default();
+        queue(&repository, DeliveryChannel::Email, 0);
+        let (dispatcher, calls) = dispatcher(repository.clone(), true, 3);
+
+        dispatcher.run_once().await.unwrap();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+        let state = repository.state.lock().unwrap();
+        assert!(state.failed[0].1.contains("not configured"));
+        assert!(state.failed[0].2.is_some());
+    }
+}
@@ -0,0 +1,276 @@
+#![allow(dead_code)]
+
+use crate::domain::entities::{notification, notification_preference};
+use crate::domain::models::notification_dto::*;
+use crate::infrastructure::notifications::webhook::validate_webhook_url;
+use crate::infrastructure::repositories::notification_repository::NotificationRepositoryTrait;
+use crate::services::error::{FieldError, ServiceError};
+
+/// 每页最多返回的通知数
+const MAX_PER_PAGE: u64 = 100;
+
+#[derive(Clone)]
+pub struct NotificationService<T>
+where
+    T: NotificationRepositoryTrait + Clone,
+{
+    notification_repository: T,
+    email_available: bool,
+    allow_private_webhooks: bool,
+}
+
+impl<T> NotificationService<T>
+where
+    T: NotificationRepositoryTrait + Clone,
+{
+    pub fn new(
+        notification_repository: T,
+        email_available: bool,
+        allow_private_webhooks: bool,
+    ) -> Self {
+        Self {
+            notification_repository,
+            email_available,
+            allow_private_webhooks,
+        }
+    }
+
+    pub async fn get_notifications(
+        &self,
+        user_id: i32,
+        params: NotificationListParams,
+    ) -> anyhow::Result<NotificationListResponse> {
+        let per_page = params.per_page.clamp(1, MAX_PER_PAGE);
+        let (notifications, total) = self
+            .notification_repository
+            .find_by_user(user_id, params.unread, params.page, per_page)
+            .await?;
+        let unread = self.notification_repository.count_unread(user_id).await?;
+
+        Ok(NotificationListResponse {
+            notifications: notifications.into_iter().map(convert_to_response).collect(),
+            total,
+            unread,
+            page: params.page.max(1),
+            per_page,
+            total_pages: total.div_ceil(per_page),
+        })
+    }
+
+    pub async fn acknowledge(
+        &self,
+        user_id: i32,
+        request: AcknowledgeNotificationsRequest,
+    ) -> anyhow::Result<AcknowledgeNotificationsResponse> {
+        let acknowledged = self
+            .notification_repository
+            .acknowledge(user_id, request.ids)
+            .await?;
+        Ok(AcknowledgeNotificationsResponse { acknowledged })
+    }
+
+    pub async fn get_preferences(
+        &self,
+        user_id: i32,
+    ) -> anyhow::Result<NotificationPreferencesResponse> {
+        let preferences = self
+            .notification_repository
+            .find_preferences(user_id)
+            .await?
+            .unwrap_or_else(|| notification_preference::Model::default_for(user_id));
+        Ok(self.convert_preferences(preferences))
+    }
+
+    pub async fn update_preferences(
+        &self,
+        user_id: i32,
+        request: UpdateNotificationPreferencesRequest,
+    ) -> anyhow::Result<NotificationPreferencesResponse> {
+        let webhook_url = non_empty(request.webhook_url);
+        let email = non_empty(request.email);
+
+        let mut errors = Vec::new();
+        match webhook_url.as_deref() {
+            None if request.webhook_enabled => errors.push(FieldError::new(
+                "webhook_url",
+                "启用 webhook 时必须填写地址",
+            )),
+            None => {}
+            Some(url) => {
+                if let Err(message) = validate_webhook_url(url, self.allow_private_webhooks) {
+                    errors.push(FieldError::new("webhook_url", message));
+                }
+            }
+        }
+        if request.email_enabled {
+            if !self.email_available {
+                errors.push(FieldError::new("email_enabled", "服务器未配置邮件发送"));
+            }
+            if email.is_none() {
+                errors.push(FieldError::new("email", "启用邮件通知时必须填写邮箱"));
+            }
+        }
+        if let Some(address) = email.as_deref() {
+            if !is_valid_email(address) {
+                errors.push(FieldError::new("email", "邮箱格式无效"));
+            }
+        }
+        if !errors.is_empty() {
+            return Err(ServiceError::Validation(errors).into());
+        }
+
+        let existing = self
+            .notification_repository
+            .find_preferences(user_id)
+            .await?;
+        // 已有密钥时保留，避免用户每次保存设置都要更新接收端
+        let webhook_secret = match existing.and_then(|p| p.webhook_secret) {
+            Some(secret) if !request.regenerate_webhook_secret => Some(secret),
+            _ if request.webhook_enabled || request.regenerate_webhook_secret => {
+                Some(generate_webhook_secret())
+            }
+            previous => previous,
+        };
+
+        let preferences = self
+            .notification_repository
+            .save_preferences(notification_preference::Model {
+                user_id,
+                in_app_enabled: request.in_app_enabled,
+                webhook_enabled: request.webhook_enabled,
+                webhook_url,
+                webhook_secret,
+                email_enabled: request.email_enabled,
+                email,
+                updated_at: chrono::Utc::now().naive_utc(),
+            })
+            .await?;
+        Ok(self.convert_preferences(preferences))
+    }
+
+    fn convert_preferences(
+        &self,
+        preferences: notification_preference::Model,
+    ) -> NotificationPreferencesResponse {
+        NotificationPreferencesResponse {
+            in_app_enabled: preferences.in_app_enabled,
+            webhook_enabled: preferences.webhook_enabled,
+            webhook_url: preferences.webhook_url,
+            webhook_secret: preferences.webhook_secret,
+            email_enabled: preferences.email_enabled,
+            email: preferences.email,
+            email_available: self.email_available,
+        }
+    }
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// 按邮件渠道发送时的解析规则检查，只接受不带显示名的地址
+fn is_valid_email(address: &str) -> bool {
+    address.parse::<lettre::Address>().is_ok()
+}
+
+/// 64 位十六进制随机串
+fn generate_webhook_secret() -> String {
+    format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    )
+}
+
+fn convert_to_response(notification: notification::Model) -> NotificationResponse {
+    NotificationResponse {
+        id: notification.id,
+        kind: notification.kind,
+        model_id: notification.model_id,
+        title: notification.title,
+        body: notification.body,
+        payload: notification.payload,
+        read_at: notification.read_at,
+        created_at: notification.created_at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::FakeNotificationRepository;
+
+    fn request(
+        webhook_url: Option<&str>,
+        email: Option<&str>,
+    ) -> UpdateNotificationPreferencesRequest {
+        UpdateNotificationPreferencesRequest {
+            in_app_enabled: true,
+            webhook_enabled: webhook_url.is_some(),
+            webhook_url: webhook_url.map(str::to_string),
+            regenerate_webhook_secret: false,
+            email_enabled: email.is_some(),
+            email: email.map(str::to_string),
+        }
+    }
+
+    fn validation_fields(error: anyhow::Error) -> Vec<&'static str> {
+        match error.downcast::<ServiceError>() {
+            Ok(ServiceError::Validation(errors)) => errors.iter().map(|e| e.field).collect(),
+            other => panic!("应为校验错误: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn rejects_private_webhook_hosts() {
+        let service = NotificationService::new(FakeNotificationRepository::default(), true, false);
+        for url in [
+            "http://127.0.0.1:9000/hook",
+            "http://169.254.169.254/latest/meta-data",
+            "http://localhost/hook",
+        ] {
+            let error = service
+                .update_preferences(1, request(Some(url), None))
+                .await
+                .unwrap_err();
+            assert_eq!(validation_fields(error), ["webhook_url"]);
+        }
+    }
+
+    #[tokio::test]
+    async fn private_webhook_hosts_allowed_when_configured() {
+        let service = NotificationService::new(FakeNotificationRepository::default(), true, true);
+        let saved = service
+            .update_preferences(1, request(Some("http://192.168.1.20:8123/hook"), None))
+            .await
+            .unwrap();
+        assert_eq!(
+            saved.webhook_url.as_deref(),
+            Some("http://192.168.1.20:8123/hook")
+        );
+        assert!(saved.webhook_secret.is_some());
+    }
+
+    #[tokio::test]
+    async fn validates_email_address() {
+        let service = NotificationService::new(FakeNotificationRepository::default(), true, false);
+        for email in ["not-an-email", "a@", "ModelHub <a@example.com>"] {
+            let error = service
+                .update_preferences(1, request(None, Some(email)))
+                .await
+                .unwrap_err();
+            assert_eq!(validation_fields(error), ["email"]);
+        }
+
+        let saved = service
+            .update_preferences(
+                1,
+                request(Some("https://hooks.example.com/x"), Some(" a@example.com ")),
+            )
+            .await
+            .unwrap();
+        assert_eq!(saved.email.as_deref(), Some("a@example.com"));
+    }
+}
@@ -0,0 +1,163 @@
+#![allow(dead_code)]
+
+use crate::domain::entities::enums::NotificationKind;
+use crate::domain::entities::{notification, price_history};
+use crate::infrastructure::repositories::currency_repository::CurrencyRepositoryTrait;
+use crate::infrastructure::repositories::notification_repository::NotificationRepositoryTrait;
+use crate::services::currency_service::CurrencyService;
+use sea_orm::ActiveValue::Set;
+
+/// 心愿单降价提醒：新价格按记录当天的汇率换算到目标价币种后不高于目标价时，为收藏者生成通知。
+/// 同一用户在该模型上已有未读、且换算价不高于本次的提醒时不重复生成
+#[derive(Clone)]
+pub struct PriceAlertService<N, C>
+where
+    N: NotificationRepositoryTrait + Clone,
+    C: CurrencyRepositoryTrait + Clone,
+{
+    notification_repository: N,
+    currency_service: CurrencyService<C>,
+}
+
+impl<N, C> PriceAlertService<N, C>
+where
+    N: NotificationRepositoryTrait + Clone,
+    C: CurrencyRepositoryTrait + Clone,
+{
+    pub fn new(notification_repository: N, currency_service: CurrencyService<C>) -> Self {
+        Self {
+            notification_repository,
+            currency_service,
+        }
+    }
+
+    /// 新价格写入后调用，返回生成的提醒条数；缺少汇率无法换算的心愿单跳过
+    pub async fn notify_price_drop(&self, price: &price_history::Model) -> anyhow::Result<usize> {
+        let targets = self
+            .notification_repository
+            .find_wishlist_targets(price.model_id)
+            .await?;
+        if targets.is_empty() {
+            return Ok(0);
+        }
+
+        let rates = self
+            .currency_service
+            .rate_table(
+                targets
+                    .iter()
+                    .map(|t| t.target_currency.clone())
+                    .chain([price.currency.clone()]),
+            )
+            .await?;
+        let unread = self
+            .notification_repository
+            .find_unread_price_drops(price.model_id)
+            .await?;
+
+        let date = price.recorded_at.date();
+        let mut notifications = Vec::new();
+        for target in targets {
+            let converted =
+                rates.convert(price.price, &price.currency, &target.target_currency, date);
+            let Some(amount) = converted.amount else {
+                tracing::warn!(
+                    "缺少 {} 到 {} 的汇率，跳过用户 {} 的降价提醒",
+                    price.currency,
+                    target.target_currency,
+                    target.user_id
+                );
+                continue;
+            };
+            if amount > target.target_price {
+                continue;
+            }
+            let already_alerted = unread.iter().any(|n| {
+                n.user_id == target.user_id
+                    && alerted_amount(&n.payload, &target.target_currency)
+                        .is_some_and(|alerted| alerted <= amount)
+            });
+            if already_alerted {
+                continue;
+            }
+
+            let mut body = format!(
+                "已达到心愿单目标价 {} {}",
+                target.target_price, target.target_currency
+            );
+            if price.currency != target.target_currency {
+                body.push_str(&format!("（约 {} {}）", amount, target.target_currency));
+            }
+            if let Some(source) = &price.source {
+                body.push_str(&format!("（来源：{}）", source));
+            }
+            notifications.push(notification::ActiveModel {
+                user_id: Set(target.user_id),
+                kind: Set(NotificationKind::PriceDrop),
+                model_id: Set(Some(price.model_id)),
+                title: Set(format!(
+                    "{} 降价至 {} {}",
+                    target.model_name, price.price, price.currency
+                )),
+                body: Set(Some(body)),
+                payload: Set(serde_json::json!({
+                    "price_history_id": price.id,
+                    "price": price.price,
+                    "currency": price.currency,
+                    "converted_price": amount,
+                    "target_price": target.target_price,
+                    "target_currency": target.target_currency,
+                    "rate": converted.rate,
+                    "rate_date": converted.rate_date,
+                    "source": price.source,
+                })),
+                created_at: Set(chrono::Utc::now().naive_utc()),
+                ..Default::default()
+            });
+        }
+
+        let count = notifications.len();
+        self.notification_repository
+            .insert_notifications(notifications)
+            .await?;
+        Ok(count)
+    }
+}
+
+/// 已有提醒在目标价币种下的价格；早期由数据库触发器生成的提醒没有换算价，币种一致时取原价
+fn alerted_amount(payload: &serde_json::Value, target_currency: &str) -> Option<f64> {
+    match payload.get("target_currency").and_then(|c| c.as_str()) {
+        Some(currency) if currency == target_currency => payload.get("converted_price")?.as_f64(),
+        Some(_) => None,
+        None => {
+            (payload.get("currency")?.as_str()? == target_currency).then_some(())?;
+            payload.get("price")?.as_f64()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn alerted_amount_uses_converted_price_in_target_currency() {
+        let payload = json!({
+            "price": 50.0,
+            "currency": "USD",
+            "converted_price": 360.0,
+            "target_currency": "CNY",
+        });
+        assert_eq!(alerted_amount(&payload, "CNY"), Some(360.0));
+        assert_eq!(alerted_amount(&payload, "JPY"), None);
+    }
+
+    #[test]
+    fn alerted_amount_falls_back_to_trigger_payload_with_same_currency() {
+        // 数据库触发器生成的提醒只有原价与币种
+        let payload = json!({ "price": 399.0, "currency": "CNY", "target_price": 420.0 });
+        assert_eq!(alerted_amount(&payload, "CNY"), Some(399.0));
+        assert_eq!(alerted_amount(&payload, "USD"), None);
+    }
+}
@@ -0,0 +1,533 @@
+#![allow(dead_code)]
+
+use crate::config::ScraperConfig;
+use crate::domain::entities::enums::PriceSourceKind;
+use crate::domain::entities::{price_history, price_watch};
+use crate::domain::models::event_dto::ChangeEventKind;
+use crate::domain::models::price_watch_dto::*;
+use crate::infrastructure::price_sources::SharedSource;
+use crate::infrastructure::repositories::currency_repository::CurrencyRepositoryTrait;
+use crate::infrastructure::repositories::notification_repository::NotificationRepositoryTrait;
+use crate::infrastructure::repositories::price_watch_repository::PriceWatchRepositoryTrait;
+use crate::services::currency_service::{CurrencyService, DEFAULT_CURRENCY};
+use crate::services::error::{map_update_error, FieldError, ServiceError};
+use crate::services::event_hub::EventHub;
+use crate::services::model_service::convert_price;
+use crate::services::price_alert_service::PriceAlertService;
+use crate::services::scheduler::{next_run_after, parse_schedule, ScheduledJob};
+use async_trait::async_trait;
+use sea_orm::ActiveValue::{NotSet, Set};
+use std::collections::HashMap;
+
+/// 未指定时的抓取计划：每 6 小时一次
+pub const DEFAULT_WATCH_SCHEDULE: &str = "0 0 */6 * * *";
+
+/// 每轮最多抓取的监控数
+const SCRAPE_BATCH_SIZE: u64 = 20;
+/// 领取监控后的租期，租期内其他实例不会重复抓取
+const SCRAPE_LEASE_SECS: i64 = 600;
+/// 记录的错误信息最大长度
+const MAX_ERROR_CHARS: usize = 500;
+
+/// 价格监控：登记商品地址与解析规则，由定时任务按各自的 cron 计划抓取并写入价格记录
+#[derive(Clone)]
+pub struct PriceWatchService<T, C, N>
+where
+    T: PriceWatchRepositoryTrait + Clone,
+    C: CurrencyRepositoryTrait + Clone,
+    N: NotificationRepositoryTrait + Clone,
+{
+    price_watch_repository: T,
+    currency_service: CurrencyService<C>,
+    sources: HashMap<PriceSourceKind, SharedSource>,
+    events: EventHub,
+    price_alerts: PriceAlertService<N, C>,
+    max_failures: i32,
+}
+
+impl<T, C, N> PriceWatchService<T, C, N>
+where
+    T: PriceWatchRepositoryTrait + Clone,
+    C: CurrencyRepositoryTrait + Clone,
+    N: NotificationRepositoryTrait + Clone,
+{
+    pub fn new(
+        price_watch_repository: T,
+        currency_service: CurrencyService<C>,
+        sources: HashMap<PriceSourceKind, SharedSource>,
+        events: EventHub,
+        price_alerts: PriceAlertService<N, C>,
+        config: &ScraperConfig,
+    ) -> Self {
+        Self {
+            price_watch_repository,
+            currency_service,
+            sources,
+            events,
+            price_alerts,
+            max_failures: config.max_failures.max(1),
+        }
+    }
+
+    /// 模型不存在时返回 `None`
+    pub async fn get_watches(
+        &self,
+        model_id: i32,
+    ) -> anyhow::Result<Option<Vec<PriceWatchResponse>>> {
+        if self
+            .price_watch_repository
+            .find_model(model_id)
+            .await?
+            .is_none()
+        {
+            return Ok(None);
+        }
+        let watches = self.price_watch_repository.find_by_model(model_id).await?;
+        Ok(Some(watches.into_iter().map(convert_watch).collect()))
+    }
+
+    pub async fn create_watch(
+        &self,
+        model_id: i32,
+        request: CreatePriceWatchRequest,
+        user_id: i32,
+    ) -> anyhow::Result<PriceWatchResponse> {
+        self.price_watch_repository
+            .find_model(model_id)
+            .await?
+            .ok_or(ServiceError::NotFound)?;
+
+        let url = request.url.trim().to_string();
+        if self
+            .price_watch_repository
+            .find_by_model_and_url(model_id, &url)
+            .await?
+            .is_some()
+        {
+            return Err(ServiceError::Validation(vec![FieldError::new(
+                "url",
+                "该模型已登记此地址的价格监控",
+            )])
+            .into());
+        }
+        self.check_selector(request.source_kind, &request.selector)?;
+        let schedule = request
+            .schedule
+            .map(|s| s.trim().to_string())
+            .unwrap_or_else(|| DEFAULT_WATCH_SCHEDULE.to_string());
+        let next_run_at = first_run(&schedule)?;
+        let currency = self
+            .currency_service
+            .normalize(
+                request.currency.as_deref().unwrap_or(DEFAULT_CURRENCY),
+                "currency",
+            )
+            .await?;
+        if let Some(shop_id) = request.shop_id {
+            self.ensure_shop_exists(shop_id).await?;
+        }
+
+        let now = chrono::Utc::now().naive_utc();
+        let watch = self
+            .price_watch_repository
+            .create(price_watch::ActiveModel {
+                model_id: Set(model_id),
+                shop_id: Set(request.shop_id),
+                url: Set(url),
+                source_kind: Set(request.source_kind),
+                selector: Set(request.selector.trim().to_string()),
+                currency: Set(currency),
+                schedule: Set(schedule),
+                enabled: Set(request.enabled.unwrap_or(true)),
+                next_run_at: Set(next_run_at),
+                consecutive_failures: Set(0),
+                created_by: Set(Some(user_id)),
+                created_at: Set(now),
+                updated_at: Set(now),
+                ..Default::default()
+            })
+            .await?;
+        Ok(convert_watch(watch))
+    }
+
+    pub async fn patch_watch(
+        &self,
+        id: i32,
+        request: PatchPriceWatchRequest,
+    ) -> anyhow::Result<PriceWatchResponse> {
+        let existing = self
+            .price_watch_repository
+            .find_by_id(id)
+            .await?
+            .ok_or(ServiceError::NotFound)?;
+
+        let url = request.url.map(|u| u.trim().to_string());
+        if let Some(url) = url.as_deref().filter(|u| *u != existing.url) {
+            if self
+                .price_watch_repository
+                .find_by_model_and_url(existing.model_id, url)
+                .await?
+                .is_some()
+            {
+                return Err(ServiceError::Validation(vec![FieldError::new(
+                    "url",
+                    "该模型已登记此地址的价格监控",
+                )])
+                .into());
+            }
+        }
+        let source_kind = request.source_kind.unwrap_or(existing.source_kind);
+        let selector = request
+            .selector
+            .map(|s| s.trim().to_string())
+            .unwrap_or_else(|| existing.selector.clone());
+        self.check_selector(source_kind, &selector)?;
+        let schedule = request.schedule.map(|s| s.trim().to_string());
+        let next_run_at = match &schedule {
+            Some(schedule) => Some(first_run(schedule)?),
+            None => None,
+        };
+        let currency = match request.currency {
+            Some(code) => Some(self.currency_service.normalize(&code, "currency").await?),
+            None => None,
+        };
+        if let Some(Some(shop_id)) = request.shop_id {
+            self.ensure_shop_exists(shop_id).await?;
+        }
+        // 重新启用时清零失败次数，并尽快抓取一次
+        let reenabled = request.enabled == Some(true) && !existing.enabled;
+
+        let watch = price_watch::ActiveModel {
+            url: url.map_or(NotSet, Set),
+            source_kind: Set(source_kind),
+            selector: Set(selector),
+            currency: currency.map_or(NotSet, Set),
+            schedule: schedule.map_or(NotSet, Set),
+            shop_id: request.shop_id.map_or(NotSet, Set),
+            enabled: request.enabled.map_or(NotSet, Set),
+            next_run_at: if reenabled {
+                Set(chrono::Utc::now().naive_utc())
+            } else {
+                next_run_at.map_or(NotSet, Set)
+            },
+            consecutive_failures: if reenabled { Set(0) } else { NotSet },
+            updated_at: Set(chrono::Utc::now().naive_utc()),
+            ..Default::default()
+        };
+        let updated = self
+            .price_watch_repository
+            .update(id, watch)
+            .await
+            .map_err(map_update_error)?;
+        Ok(convert_watch(updated))
+    }
+
+    pub async fn delete_watch(&self, id: i32) -> anyhow::Result<bool> {
+        let result = self.price_watch_repository.delete(id).await?;
+        Ok(result.rows_affected > 0)
+    }
+
+    /// 按给定规则抓取一次但不保存，模型不存在时返回 `None`
+    pub async fn preview(
+        &self,
+        model_id: i32,
+        request: PreviewPriceRequest,
+    ) -> anyhow::Result<Option<PreviewPriceResponse>> {
+        let Some(model) = self.price_watch_repository.find_model(model_id).await? else {
+            return Ok(None);
+        };
+        self.check_selector(request.source_kind, &request.selector)?;
+        let source = self.source(request.source_kind)?;
+        let fetched = source
+            .fetch(&model, request.url.trim(), request.selector.trim())
+            .await
+            .map_err(|e| {
+                ServiceError::Validation(vec![FieldError::new("url", format!("抓取失败: {}", e))])
+            })?;
+        Ok(Some(PreviewPriceResponse {
+            price: fetched.price,
+            raw: fetched.raw,
+        }))
+    }
+
+    /// 立即抓取一次，并按计划重新计算下次抓取时间
+    pub async fn check_now(&self, id: i32) -> anyhow::Result<PriceCheckResponse> {
+        let watch = self
+            .price_watch_repository
+            .find_by_id(id)
+            .await?
+            .ok_or(ServiceError::NotFound)?;
+        self.check(watch).await
+    }
+
+    /// 抓取全部到期的监控，单个监控失败只记录在该监控上
+    pub async fn run_due(&self) -> anyhow::Result<()> {
+        let watches = self
+            .price_watch_repository
+            .claim_due(SCRAPE_BATCH_SIZE, SCRAPE_LEASE_SECS)
+            .await?;
+        for watch in watches {
+            let id = watch.id;
+            match self.check(watch).await {
+                Ok(result) => {
+                    if let Some(error) = result.error {
+                        tracing::warn!("价格监控 {} 抓取失败: {}", id, error);
+                    }
+                }
+                Err(e) => tracing::error!("价格监控 {} 处理失败: {:?}", id, e),
+            }
+        }
+        Ok(())
+    }
+
+    async fn check(&self, watch: price_watch::Model) -> anyhow::Result<PriceCheckResponse> {
+        let now = chrono::Utc::now().naive_utc();
+        // 计划无法解析（如直接改库）时一天后再试，避免反复抓取
+        let next_run_at = parse_schedule(&watch.schedule)
+            .ok()
+            .and_then(|schedule| next_run_after(&schedule, now))
+            .unwrap_or(now + chrono::Duration::days(1));
+
+        let fetched = match self
+            .price_watch_repository
+            .find_model(watch.model_id)
+            .await?
+        {
+            Some(model) => match self.source(watch.source_kind) {
+                Ok(source) => source.fetch(&model, &watch.url, &watch.selector).await,
+                Err(e) => Err(e),
+            },
+            None => Err(anyhow::anyhow!("模型 {} 不存在", watch.model_id)),
+        };
+
+        let fetched = match fetched {
+            Ok(fetched) => fetched,
+            Err(e) => {
+                let error: String = e.to_string().chars().take(MAX_ERROR_CHARS).collect();
+                let disable = watch.consecutive_failures + 1 >= self.max_failures;
+                self.price_watch_repository
+                    .record_failure(watch.id, error.clone(), next_run_at, disable)
+                    .await?;
+                if disable {
+                    tracing::warn!(
+                        "价格监控 {} 连续失败 {} 次，已停用",
+                        watch.id,
+                        self.max_failures
+                    );
+                }
+                let watch = self.reload(watch).await?;
+                return Ok(PriceCheckResponse {
+                    watch: convert_watch(watch),
+                    price: None,
+                    recorded: None,
+                    error: Some(error),
+                });
+            }
+        };
+
+        // 去重：与该监控上一次写入的价格相同则只更新抓取时间
+        let latest = self
+            .price_watch_repository
+            .find_latest_price(watch.id)
+            .await?;
+        let unchanged = same_as_latest(latest.as_ref(), &watch.currency, fetched.price);
+        let price = if unchanged {
+            None
+        } else {
+            let source = match watch.shop_id {
+                Some(shop_id) => self
+                    .price_watch_repository
+                    .find_shop(shop_id)
+                    .await?
+                    .map(|shop| shop.name),
+                None => None,
+            };
+            Some(price_history::ActiveModel {
+                price: Set(fetched.price),
+                currency: Set(watch.currency.clone()),
+                source: Set(source.or_else(|| url_host(&watch.url))),
+                shop_id: Set(watch.shop_id),
+                watch_id: Set(Some(watch.id)),
+                notes: Set(None),
+                model_id: Set(watch.model_id),
+                recorded_by: Set(None),
+                recorded_at: Set(now),
+                ..Default::default()
+            })
+        };
+
+        let recorded = self
+            .price_watch_repository
+            .record_success(watch.id, fetched.price, price, next_run_at)
+            .await?;
+        if let Some(recorded) = &recorded {
+            // 价格已保存，提醒生成失败只记日志
+            if let Err(e) = self.price_alerts.notify_price_drop(recorded).await {
+                tracing::error!("生成降价提醒失败: {:?}", e);
+            }
+        }
+        let recorded = recorded.map(convert_price);
+        if let Some(recorded) = &recorded {
+            self.events
+                .publish(ChangeEventKind::PriceAdded, watch.model_id, None, recorded);
+        }
+
+        let watch = self.reload(watch).await?;
+        Ok(PriceCheckResponse {
+            watch: convert_watch(watch),
+            price: Some(fetched.price),
+            recorded,
+            error: None,
+        })
+    }
+
+    async fn reload(&self, watch: price_watch::Model) -> anyhow::Result<price_watch::Model> {
+        Ok(self
+            .price_watch_repository
+            .find_by_id(watch.id)
+            .await?
+            .unwrap_or(watch))
+    }
+
+    fn source(&self, kind: PriceSourceKind) -> anyhow::Result<&SharedSource> {
+        self.sources
+            .get(&kind)
+            .ok_or_else(|| anyhow::anyhow!("未注册的抓取方式: {:?}", kind))
+    }
+
+    fn check_selector(&self, kind: PriceSourceKind, selector: &str) -> anyhow::Result<()> {
+        self.source(kind)?
+            .validate_selector(selector.trim())
+            .map_err(|e| {
+                ServiceError::Validation(vec![FieldError::new("selector", e.to_string())])
+            })?;
+        Ok(())
+    }
+
+    async fn ensure_shop_exists(&self, shop_id: i32) -> anyhow::Result<()> {
+        if self
+            .price_watch_repository
+            .find_shop(shop_id)
+            .await?
+            .is_none()
+        {
+            return Err(
+                ServiceError::Validation(vec![FieldError::new("shop_id", "店铺不存在")]).into(),
+            );
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<T, C, N> ScheduledJob for PriceWatchService<T, C, N>
+where
+    T: PriceWatchRepositoryTrait + Clone,
+    C: CurrencyRepositoryTrait + Clone,
+    N: NotificationRepositoryTrait + Clone,
+{
+    fn name(&self) -> &'static str {
+        "price_watch"
+    }
+
+    async fn run(&self) -> anyhow::Result<()> {
+        self.run_due().await
+    }
+}
+
+/// 校验 cron 表达式并返回首次执行时间
+fn first_run(schedule: &str) -> anyhow::Result<chrono::NaiveDateTime> {
+    let invalid =
+        |message: String| ServiceError::Validation(vec![FieldError::new("schedule", message)]);
+    let parsed = parse_schedule(schedule).map_err(|e| invalid(e.to_string()))?;
+    Ok(next_run_after(&parsed, chrono::Utc::now().naive_utc())
+        .ok_or_else(|| invalid("该计划没有后续执行时间".to_string()))?)
+}
+
+/// 抓取到的价格与该监控上一次写入的价格（币种与金额）是否相同
+fn same_as_latest(latest: Option<&price_history::Model>, currency: &str, price: f64) -> bool {
+    latest.is_some_and(|latest| latest.currency == currency && (latest.price - price).abs() < 1e-9)
+}
+
+/// 未关联店铺时以域名作为价格来源
+fn url_host(url: &str) -> Option<String> {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let host = rest.split(['/', '?', '#']).next()?;
+    let host = host.rsplit_once('@').map_or(host, |(_, host)| host);
+    (!host.is_empty()).then(|| host.to_string())
+}
+
+fn convert_watch(watch: price_watch::Model) -> PriceWatchResponse {
+    PriceWatchResponse {
+        id: watch.id,
+        model_id: watch.model_id,
+        shop_id: watch.shop_id,
+        url: watch.url,
+        source_kind: watch.source_kind,
+        selector: watch.selector,
+        currency: watch.currency,
+        schedule: watch.schedule,
+        enabled: watch.enabled,
+        next_run_at: watch.next_run_at,
+        last_run_at: watch.last_run_at,
+        last_price: watch.last_price,
+        last_error: watch.last_error,
+        consecutive_failures: watch.consecutive_failures,
+        created_at: watch.created_at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn latest(price: f64, currency: &str) -> price_history::Model {
+        price_history::Model {
+            id: 1,
+            price,
+            currency: currency.to_string(),
+            source: None,
+            shop_id: None,
+            watch_id: Some(1),
+            notes: None,
+            model_id: 1,
+            recorded_by: None,
+            recorded_at: chrono::NaiveDate::from_ymd_opt(2026, 10, 1)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap(),
+        }
+    }
+
+    #[test]
+    fn same_price_and_currency_is_deduplicated() {
+        assert!(same_as_latest(Some(&latest(1299.0, "CNY")), "CNY", 1299.0));
+        // 浮点运算误差不视为变价
+        assert!(same_as_latest(Some(&latest(0.3, "CNY")), "CNY", 0.1 + 0.2));
+    }
+
+    #[test]
+    fn changed_price_or_currency_is_recorded() {
+        assert!(!same_as_latest(Some(&latest(1299.0, "CNY")), "CNY", 1199.0));
+        // 监控改了币种后同一数值也要重新记录
+        assert!(!same_as_latest(Some(&latest(1299.0, "CNY")), "JPY", 1299.0));
+    }
+
+    #[test]
+    fn first_price_is_recorded() {
+        assert!(!same_as_latest(None, "CNY", 1299.0));
+    }
+
+    #[test]
+    fn url_host_strips_scheme_path_and_credentials() {
+        assert_eq!(
+            url_host("https://user:pw@shop.example.com/item?id=1").as_deref(),
+            Some("shop.example.com")
+        );
+        assert_eq!(
+            url_host("shop.example.com/item").as_deref(),
+            Some("shop.example.com")
+        );
+        assert_eq!(url_host("https:///item"), None);
+    }
+}
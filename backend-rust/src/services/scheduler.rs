@@ -0,0 +1,126 @@
+#![allow(dead_code)]
+
+use async_trait::async_trait;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// 由 `JobScheduler` 按 cron 表达式定时执行的后台任务
+#[async_trait]
+pub trait ScheduledJob: Send + Sync {
+    /// 用于日志的任务名
+    fn name(&self) -> &'static str;
+    async fn run(&self) -> anyhow::Result<()>;
+}
+
+/// 解析 cron 表达式：支持带秒的 6 段（或带年份的 7 段）格式，
+/// 也接受常见的 5 段格式，按每分钟第 0 秒执行
+pub fn parse_schedule(expression: &str) -> anyhow::Result<cron::Schedule> {
+    let expression = expression.trim();
+    let normalized = if expression.split_whitespace().count() == 5 {
+        format!("0 {}", expression)
+    } else {
+        expression.to_string()
+    };
+    cron::Schedule::from_str(&normalized)
+        .map_err(|e| anyhow::anyhow!("无效的 cron 表达式 {}: {}", expression, e))
+}
+
+/// `schedule` 在 `after` 之后的下一次执行时间（UTC），按服务器本地时区解释 cron 表达式
+pub fn next_run_after(
+    schedule: &cron::Schedule,
+    after: chrono::NaiveDateTime,
+) -> Option<chrono::NaiveDateTime> {
+    let after = after.and_utc().with_timezone(&chrono::Local);
+    schedule.after(&after).next().map(|t| t.naive_utc())
+}
+
+/// 后台任务调度器：每个任务在独立的 tokio 任务中按计划循环执行。
+/// 同一任务不会并发执行，执行时间超过间隔时跳过错过的时间点
+#[derive(Default)]
+pub struct JobScheduler {
+    jobs: Vec<(cron::Schedule, Arc<dyn ScheduledJob>)>,
+}
+
+impl JobScheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add<J>(&mut self, schedule: &str, job: J) -> anyhow::Result<()>
+    where
+        J: ScheduledJob + 'static,
+    {
+        let schedule = parse_schedule(schedule)?;
+        tracing::info!("注册定时任务 {}: {}", job.name(), schedule);
+        self.jobs.push((schedule, Arc::new(job)));
+        Ok(())
+    }
+
+    /// 启动全部任务，单次执行出错只记录日志，不会终止任务
+    pub fn spawn(self) -> Vec<tokio::task::JoinHandle<()>> {
+        self.jobs
+            .into_iter()
+            .map(|(schedule, job)| {
+                tokio::spawn(async move {
+                    loop {
+                        let now = chrono::Utc::now().naive_utc();
+                        let Some(next) = next_run_after(&schedule, now) else {
+                            tracing::warn!("定时任务 {} 没有后续执行时间，已停止", job.name());
+                            return;
+                        };
+                        let wait = (next - now).to_std().unwrap_or_default();
+                        tokio::time::sleep(wait).await;
+
+                        if let Err(e) = job.run().await {
+                            tracing::error!("定时任务 {} 执行失败: {:?}", job.name(), e);
+                        }
+                    }
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Local, NaiveDate, TimeZone, Timelike};
+
+    #[test]
+    fn parse_schedule_accepts_five_six_and_seven_fields() {
+        let five = parse_schedule("*/15 * * * *").unwrap();
+        assert_eq!(five.to_string(), "0 */15 * * * *");
+        assert!(parse_schedule(" 0 30 3 * * * ").is_ok());
+        assert!(parse_schedule("0 0 4 * * * 2026").is_ok());
+    }
+
+    #[test]
+    fn parse_schedule_rejects_invalid_expressions() {
+        assert!(parse_schedule("").is_err());
+        assert!(parse_schedule("every day").is_err());
+        assert!(parse_schedule("0 61 * * * *").is_err());
+    }
+
+    #[test]
+    fn next_run_after_interprets_schedule_in_local_time() {
+        // 每天本地时间 03:30
+        let schedule = parse_schedule("30 3 * * *").unwrap();
+        let local_start = Local
+            .from_local_datetime(
+                &NaiveDate::from_ymd_opt(2026, 10, 19)
+                    .unwrap()
+                    .and_hms_opt(12, 0, 0)
+                    .unwrap(),
+            )
+            .single()
+            .unwrap();
+
+        let next = next_run_after(&schedule, local_start.naive_utc()).unwrap();
+        let next_local = next.and_utc().with_timezone(&Local);
+        assert_eq!(
+            next_local.date_naive(),
+            NaiveDate::from_ymd_opt(2026, 10, 20).unwrap()
+        );
+        assert_eq!((next_local.hour(), next_local.minute()), (3, 30));
+    }
+}
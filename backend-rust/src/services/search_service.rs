@@ -0,0 +1,233 @@
+#![allow(dead_code)]
+
+use crate::domain::entities::{manufacturer, model};
+use crate::domain::models::search_dto::{
+    SearchHighlight, SearchHit, SearchHitKind, SearchResponse,
+};
+use crate::infrastructure::repositories::search_repository::SearchRepositoryTrait;
+use crate::services::error::{FieldError, ServiceError};
+use crate::services::search_text::{self, SearchField};
+use std::collections::HashMap;
+
+/// 单次检索返回的最大条数
+const MAX_LIMIT: u64 = 100;
+/// 回退检索时每类实体最多取出的候选记录数，在内存中打分后再截断
+const FALLBACK_CANDIDATES: u64 = 500;
+
+#[derive(Clone)]
+pub struct SearchService<T>
+where
+    T: SearchRepositoryTrait + Clone,
+{
+    search_repository: T,
+}
+
+impl<T> SearchService<T>
+where
+    T: SearchRepositoryTrait + Clone,
+{
+    pub fn new(search_repository: T) -> Self {
+        Self { search_repository }
+    }
+
+    /// 跨模型与厂商检索，Postgres 下使用 tsvector 排序，其他数据库回退为 LIKE 匹配后内存打分
+    pub async fn search(&self, query: &str, limit: u64) -> anyhow::Result<SearchResponse> {
+        let terms = search_text::terms(query);
+        if terms.is_empty() {
+            return Err(
+                ServiceError::Validation(vec![FieldError::new("q", "搜索关键词不能为空")]).into(),
+            );
+        }
+        let limit = limit.clamp(1, MAX_LIMIT);
+
+        let mut hits = if self.search_repository.supports_full_text() {
+            self.search_full_text(&terms, limit).await?
+        } else {
+            self.search_fallback(&terms).await?
+        };
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+        hits.truncate(limit as usize);
+
+        Ok(SearchResponse {
+            query: query.trim().to_string(),
+            total: hits.len(),
+            hits,
+        })
+    }
+
+    async fn search_full_text(
+        &self,
+        terms: &[String],
+        limit: u64,
+    ) -> anyhow::Result<Vec<SearchHit>> {
+        let tsquery = search_text::to_tsquery(terms);
+
+        let ranked_models = self.search_repository.rank_models(&tsquery, limit).await?;
+        let model_ranks: HashMap<i32, f32> = ranked_models.iter().map(|r| (r.id, r.rank)).collect();
+        let models = self
+            .search_repository
+            .find_models(ranked_models.iter().map(|r| r.id).collect())
+            .await?;
+        let series_names = self.series_names_of(&models).await?;
+
+        let ranked_manufacturers = self
+            .search_repository
+            .rank_manufacturers(&tsquery, limit)
+            .await?;
+        let manufacturer_ranks: HashMap<i32, f32> = ranked_manufacturers
+            .iter()
+            .map(|r| (r.id, r.rank))
+            .collect();
+        let manufacturers = self
+            .search_repository
+            .find_manufacturers(ranked_manufacturers.iter().map(|r| r.id).collect())
+            .await?;
+
+        let mut hits = Vec::with_capacity(models.len() + manufacturers.len());
+        for m in models {
+            let series_name = m.series_id.and_then(|id| series_names.get(&id).cloned());
+            let fields = model_fields(&m, series_name.as_deref());
+            let score = model_ranks.get(&m.id).copied().unwrap_or_default();
+            hits.push(build_hit(
+                SearchHitKind::Model,
+                m.id,
+                &m.name,
+                score,
+                &fields,
+                terms,
+            ));
+        }
+        for mf in manufacturers {
+            let fields = manufacturer_fields(&mf);
+            let score = manufacturer_ranks.get(&mf.id).copied().unwrap_or_default();
+            hits.push(build_hit(
+                SearchHitKind::Manufacturer,
+                mf.id,
+                &mf.name,
+                score,
+                &fields,
+                terms,
+            ));
+        }
+        Ok(hits)
+    }
+
+    async fn search_fallback(&self, terms: &[String]) -> anyhow::Result<Vec<SearchHit>> {
+        let models = self
+            .search_repository
+            .find_model_candidates(terms, FALLBACK_CANDIDATES)
+            .await?;
+        let series_names = self.series_names_of(&models).await?;
+        let manufacturers = self
+            .search_repository
+            .find_manufacturer_candidates(terms, FALLBACK_CANDIDATES)
+            .await?;
+
+        let mut hits = Vec::new();
+        for m in models {
+            let series_name = m.series_id.and_then(|id| series_names.get(&id).cloned());
+            let fields = model_fields(&m, series_name.as_deref());
+            if let Some(score) = search_text::score(&fields, terms) {
+                hits.push(build_hit(
+                    SearchHitKind::Model,
+                    m.id,
+                    &m.name,
+                    score,
+                    &fields,
+                    terms,
+                ));
+            }
+        }
+        for mf in manufacturers {
+            let fields = manufacturer_fields(&mf);
+            if let Some(score) = search_text::score(&fields, terms) {
+                hits.push(build_hit(
+                    SearchHitKind::Manufacturer,
+                    mf.id,
+                    &mf.name,
+                    score,
+                    &fields,
+                    terms,
+                ));
+            }
+        }
+        Ok(hits)
+    }
+
+    async fn series_names_of(
+        &self,
+        models: &[model::Model],
+    ) -> anyhow::Result<HashMap<i32, String>> {
+        let mut series_ids: Vec<i32> = models.iter().filter_map(|m| m.series_id).collect();
+        series_ids.sort_unstable();
+        series_ids.dedup();
+        Ok(self.search_repository.find_series_names(series_ids).await?)
+    }
+}
+
+/// 权重与迁移中 setweight 的 A/B/C 对应
+fn model_fields<'a>(m: &'a model::Model, series_name: Option<&'a str>) -> Vec<SearchField<'a>> {
+    let mut fields = vec![SearchField {
+        name: "name",
+        weight: 1.0,
+        text: &m.name,
+    }];
+    if let Some(series_name) = series_name {
+        fields.push(SearchField {
+            name: "series",
+            weight: 0.4,
+            text: series_name,
+        });
+    }
+    if let Some(notes) = m.notes.as_deref() {
+        fields.push(SearchField {
+            name: "notes",
+            weight: 0.2,
+            text: notes,
+        });
+    }
+    fields
+}
+
+fn manufacturer_fields(mf: &manufacturer::Model) -> Vec<SearchField<'_>> {
+    let mut fields = vec![SearchField {
+        name: "name",
+        weight: 1.0,
+        text: &mf.name,
+    }];
+    if let Some(full_name) = mf.full_name.as_deref() {
+        fields.push(SearchField {
+            name: "full_name",
+            weight: 0.4,
+            text: full_name,
+        });
+    }
+    fields
+}
+
+fn build_hit(
+    kind: SearchHitKind,
+    id: i32,
+    title: &str,
+    score: f32,
+    fields: &[SearchField<'_>],
+    terms: &[String],
+) -> SearchHit {
+    let highlights = fields
+        .iter()
+        .filter_map(|f| {
+            search_text::highlight(f.text, terms).map(|snippet| SearchHighlight {
+                field: f.name,
+                snippet,
+            })
+        })
+        .collect();
+
+    SearchHit {
+        kind,
+        id,
+        title: title.to_string(),
+        score,
+        highlights,
+    }
+}
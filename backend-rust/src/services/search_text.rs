@@ -0,0 +1,141 @@
+//! 检索词切分与高亮
+//!
+//! 切分规则需与迁移中的 `modelhub_search_text` 函数保持一致：
+//! 字母数字连续片段为一个词，中日文按字切分并生成相邻二元组。
+
+/// 高亮片段中命中位置前后保留的字符数
+const SNIPPET_CONTEXT: usize = 30;
+
+/// 参与检索的字段及其权重
+pub struct SearchField<'a> {
+    pub name: &'static str,
+    pub weight: f32,
+    pub text: &'a str,
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(c, '\u{3040}'..='\u{30ff}' | '\u{3400}'..='\u{9fff}' | '\u{f900}'..='\u{faff}')
+}
+
+/// 将用户输入切分为小写检索词，如 `RX-78-2 元祖` → `rx` `78` `2` `元祖`
+pub fn terms(query: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    let mut current = String::new();
+    let mut current_cjk = false;
+
+    for c in query.to_lowercase().chars() {
+        let cjk = is_cjk(c);
+        if !cjk && !c.is_alphanumeric() {
+            if !current.is_empty() {
+                terms.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        if !current.is_empty() && cjk != current_cjk {
+            terms.push(std::mem::take(&mut current));
+        }
+        current.push(c);
+        current_cjk = cjk;
+    }
+    if !current.is_empty() {
+        terms.push(current);
+    }
+
+    terms.dedup();
+    terms
+}
+
+/// 生成 Postgres `to_tsquery('simple', ...)` 表达式，所有检索词均需命中；
+/// 字母数字词按前缀匹配，中日文词拆成二元组（单字则直接匹配单字）
+pub fn to_tsquery(terms: &[String]) -> String {
+    let mut lexemes = Vec::new();
+    for term in terms {
+        let chars: Vec<char> = term.chars().collect();
+        if !chars.first().copied().is_some_and(is_cjk) {
+            lexemes.push(format!("'{}':*", term));
+        } else if chars.len() == 1 {
+            lexemes.push(format!("'{}'", term));
+        } else {
+            lexemes.extend(
+                chars
+                    .windows(2)
+                    .map(|pair| format!("'{}'", pair.iter().collect::<String>())),
+            );
+        }
+    }
+    lexemes.join(" & ")
+}
+
+/// 无全文索引时的打分：每个检索词取命中字段的最高权重，任一检索词未命中则不返回
+pub fn score(fields: &[SearchField<'_>], terms: &[String]) -> Option<f32> {
+    if terms.is_empty() {
+        return None;
+    }
+
+    let lowered: Vec<(f32, String)> = fields
+        .iter()
+        .map(|f| (f.weight, f.text.to_lowercase()))
+        .collect();
+
+    let mut total = 0.0;
+    for term in terms {
+        total += lowered
+            .iter()
+            .filter(|(_, text)| text.contains(term.as_str()))
+            .map(|(weight, _)| *weight)
+            .reduce(f32::max)?;
+    }
+    Some(total / terms.len() as f32)
+}
+
+/// 截取首个命中位置附近的片段，命中部分以 `<mark>` 包裹，其余内容做 HTML 转义
+pub fn highlight(text: &str, terms: &[String]) -> Option<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let lowered: Vec<char> = chars
+        .iter()
+        .map(|c| c.to_lowercase().next().unwrap_or(*c))
+        .collect();
+
+    let mut marked = vec![false; chars.len()];
+    for term in terms {
+        let needle: Vec<char> = term.chars().collect();
+        if needle.is_empty() || needle.len() > lowered.len() {
+            continue;
+        }
+        for start in 0..=lowered.len() - needle.len() {
+            if lowered[start..start + needle.len()] == needle[..] {
+                marked[start..start + needle.len()].fill(true);
+            }
+        }
+    }
+
+    let first = marked.iter().position(|m| *m)?;
+    let start = first.saturating_sub(SNIPPET_CONTEXT);
+    let end = (first + SNIPPET_CONTEXT * 2).min(chars.len());
+
+    let mut snippet = String::new();
+    if start > 0 {
+        snippet.push('…');
+    }
+    let mut in_mark = false;
+    for (c, &m) in chars[start..end].iter().zip(&marked[start..end]) {
+        if m != in_mark {
+            snippet.push_str(if m { "<mark>" } else { "</mark>" });
+            in_mark = m;
+        }
+        match c {
+            '<' => snippet.push_str("&lt;"),
+            '>' => snippet.push_str("&gt;"),
+            '&' => snippet.push_str("&amp;"),
+            '"' => snippet.push_str("&quot;"),
+            _ => snippet.push(*c),
+        }
+    }
+    if in_mark {
+        snippet.push_str("</mark>");
+    }
+    if end < chars.len() {
+        snippet.push('…');
+    }
+    Some(snippet)
+}
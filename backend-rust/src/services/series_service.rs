@@ -0,0 +1,149 @@
+#![allow(dead_code)]
+
+use crate::domain::entities::series;
+use crate::domain::models::series_dto::*;
+use crate::infrastructure::repositories::series_repository::SeriesRepositoryTrait;
+use crate::services::error::{map_update_error, FieldError, ServiceError};
+use sea_orm::ActiveValue::Set;
+
+#[derive(Clone)]
+pub struct SeriesService<T>
+where
+    T: SeriesRepositoryTrait + Clone,
+{
+    series_repository: T,
+}
+
+impl<T> SeriesService<T>
+where
+    T: SeriesRepositoryTrait + Clone,
+{
+    pub fn new(series_repository: T) -> Self {
+        Self { series_repository }
+    }
+
+    pub async fn get_series(&self) -> anyhow::Result<Vec<SeriesResponse>> {
+        let series_list = self.series_repository.find_all().await?;
+        let counts = self.series_repository.count_models().await?;
+
+        let responses: Vec<SeriesResponse> = series_list
+            .into_iter()
+            .map(|mut s| {
+                s.models_count = Some(counts.get(&s.id).copied().unwrap_or(0));
+                self.convert_to_response(s)
+            })
+            .collect();
+        Ok(responses)
+    }
+
+    pub async fn get_series_by_id(&self, id: i32) -> anyhow::Result<Option<SeriesResponse>> {
+        if let Some(series) = self.series_repository.find_by_id(id).await? {
+            Ok(Some(self.convert_to_response(series)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub async fn create_series(
+        &self,
+        request: CreateSeriesRequest,
+    ) -> anyhow::Result<SeriesResponse> {
+        self.ensure_name_available(None, &request.name).await?;
+
+        let now = chrono::Utc::now().naive_utc();
+        let series_data = series::ActiveModel {
+            name: Set(request.name.trim().to_string()),
+            aliases: Set(normalize_aliases(request.aliases)),
+            universe: Set(request.universe),
+            air_start_year: Set(request.air_start_year),
+            air_end_year: Set(request.air_end_year),
+            created_at: Set(now),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+
+        let created_series = self.series_repository.create(series_data).await?;
+        Ok(self.convert_to_response(created_series))
+    }
+
+    pub async fn update_series(
+        &self,
+        id: i32,
+        request: UpdateSeriesRequest,
+    ) -> anyhow::Result<SeriesResponse> {
+        let existing_series = self
+            .series_repository
+            .find_by_id(id)
+            .await?
+            .ok_or(ServiceError::NotFound)?;
+
+        if let Some(name) = &request.name {
+            self.ensure_name_available(Some(id), name).await?;
+        }
+
+        let series_data = series::ActiveModel {
+            id: Set(existing_series.id),
+            name: Set(request
+                .name
+                .map(|n| n.trim().to_string())
+                .unwrap_or(existing_series.name)),
+            aliases: Set(request
+                .aliases
+                .map(normalize_aliases)
+                .unwrap_or(existing_series.aliases)),
+            universe: Set(request.universe.or(existing_series.universe)),
+            air_start_year: Set(request.air_start_year.or(existing_series.air_start_year)),
+            air_end_year: Set(request.air_end_year.or(existing_series.air_end_year)),
+            updated_at: Set(chrono::Utc::now().naive_utc()),
+            ..Default::default()
+        };
+
+        let updated_series = self
+            .series_repository
+            .update(id, series_data)
+            .await
+            .map_err(map_update_error)?;
+        Ok(self.convert_to_response(updated_series))
+    }
+
+    pub async fn delete_series(&self, id: i32) -> anyhow::Result<()> {
+        self.series_repository.delete(id).await?;
+        Ok(())
+    }
+
+    /// 名称不能与其他系列的名称或别名重复，避免再次出现同一作品多种写法
+    async fn ensure_name_available(&self, id: Option<i32>, name: &str) -> anyhow::Result<()> {
+        match self.series_repository.find_by_name_or_alias(name).await? {
+            Some(existing) if Some(existing.id) != id => {
+                Err(ServiceError::Validation(vec![FieldError::new(
+                    "name",
+                    format!("与已有系列「{}」重复", existing.name),
+                )])
+                .into())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn convert_to_response(&self, series: series::Model) -> SeriesResponse {
+        SeriesResponse {
+            id: series.id,
+            name: series.name,
+            aliases: series.aliases,
+            universe: series.universe,
+            air_start_year: series.air_start_year,
+            air_end_year: series.air_end_year,
+            models_count: series.models_count,
+        }
+    }
+}
+
+fn normalize_aliases(aliases: Vec<String>) -> Vec<String> {
+    let mut normalized: Vec<String> = aliases
+        .into_iter()
+        .map(|a| a.trim().to_string())
+        .filter(|a| !a.is_empty())
+        .collect();
+    normalized.dedup();
+    normalized
+}
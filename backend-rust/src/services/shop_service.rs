@@ -0,0 +1,123 @@
+#![allow(dead_code)]
+
+use crate::domain::entities::shop;
+use crate::domain::models::shop_dto::*;
+use crate::infrastructure::repositories::shop_repository::ShopRepositoryTrait;
+use crate::services::error::{map_update_error, FieldError, ServiceError};
+use sea_orm::ActiveValue::{NotSet, Set};
+
+#[derive(Clone)]
+pub struct ShopService<T>
+where
+    T: ShopRepositoryTrait + Clone,
+{
+    shop_repository: T,
+}
+
+impl<T> ShopService<T>
+where
+    T: ShopRepositoryTrait + Clone,
+{
+    pub fn new(shop_repository: T) -> Self {
+        Self { shop_repository }
+    }
+
+    pub async fn get_shops(&self) -> anyhow::Result<Vec<ShopResponse>> {
+        let shops = self.shop_repository.find_all().await?;
+        Ok(shops.into_iter().map(convert_shop).collect())
+    }
+
+    pub async fn get_shop_by_id(&self, id: i32) -> anyhow::Result<Option<ShopResponse>> {
+        Ok(self.shop_repository.find_by_id(id).await?.map(convert_shop))
+    }
+
+    pub async fn create_shop(&self, request: CreateShopRequest) -> anyhow::Result<ShopResponse> {
+        self.ensure_name_available(None, &request.name).await?;
+
+        let now = chrono::Utc::now().naive_utc();
+        let shop_data = shop::ActiveModel {
+            name: Set(request.name.trim().to_string()),
+            platform: Set(normalize_platform(request.platform)),
+            url: Set(request.url),
+            region: Set(request.region),
+            trust_notes: Set(request.trust_notes),
+            created_at: Set(now),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+
+        let created_shop = self.shop_repository.create(shop_data).await?;
+        Ok(convert_shop(created_shop))
+    }
+
+    pub async fn patch_shop(
+        &self,
+        id: i32,
+        request: PatchShopRequest,
+    ) -> anyhow::Result<ShopResponse> {
+        self.shop_repository
+            .find_by_id(id)
+            .await?
+            .ok_or(ServiceError::NotFound)?;
+
+        if let Some(name) = &request.name {
+            self.ensure_name_available(Some(id), name).await?;
+        }
+
+        let shop_data = shop::ActiveModel {
+            name: request.name.map_or(NotSet, |n| Set(n.trim().to_string())),
+            platform: request
+                .platform
+                .map_or(NotSet, |p| Set(normalize_platform(p))),
+            url: request.url.map_or(NotSet, Set),
+            region: request.region.map_or(NotSet, Set),
+            trust_notes: request.trust_notes.map_or(NotSet, Set),
+            updated_at: Set(chrono::Utc::now().naive_utc()),
+            ..Default::default()
+        };
+
+        let updated_shop = self
+            .shop_repository
+            .update(id, shop_data)
+            .await
+            .map_err(map_update_error)?;
+        Ok(convert_shop(updated_shop))
+    }
+
+    /// 删除店铺后其价格记录保留，仅解除关联
+    pub async fn delete_shop(&self, id: i32) -> anyhow::Result<bool> {
+        let result = self.shop_repository.delete(id).await?;
+        Ok(result.rows_affected > 0)
+    }
+
+    async fn ensure_name_available(&self, id: Option<i32>, name: &str) -> anyhow::Result<()> {
+        match self.shop_repository.find_by_name(name).await? {
+            Some(existing) if Some(existing.id) != id => {
+                Err(ServiceError::Validation(vec![FieldError::new(
+                    "name",
+                    format!("店铺「{}」已存在", existing.name),
+                )])
+                .into())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+pub(crate) fn convert_shop(shop: shop::Model) -> ShopResponse {
+    ShopResponse {
+        id: shop.id,
+        name: shop.name,
+        platform: shop.platform,
+        url: shop.url,
+        region: shop.region,
+        trust_notes: shop.trust_notes,
+    }
+}
+
+/// 平台代码统一为小写，空字符串视为未填写
+fn normalize_platform(platform: Option<String>) -> Option<String> {
+    platform
+        .map(|p| p.trim().to_lowercase())
+        .filter(|p| !p.is_empty())
+}
@@ -0,0 +1,14 @@
+use crate::services::search_text;
+
+/// 名称相似度达到该值即视为疑似重复
+pub const DUPLICATE_THRESHOLD: f64 = 0.85;
+
+/// 归一化名称：转小写并去掉空格、连字符等分隔符，如 `RX-78-2 元祖` → `rx782元祖`
+pub fn normalize_name(name: &str) -> String {
+    search_text::terms(name).concat()
+}
+
+/// 基于归一化名称的编辑距离相似度，取值 0~1
+pub fn name_similarity(a: &str, b: &str) -> f64 {
+    strsim::normalized_levenshtein(&normalize_name(a), &normalize_name(b))
+}
@@ -0,0 +1,76 @@
+#![allow(dead_code)]
+
+use crate::config::StatusTransitionConfig;
+use crate::domain::models::model_dto::StatusTransitionReport;
+use crate::infrastructure::repositories::currency_repository::CurrencyRepositoryTrait;
+use crate::infrastructure::repositories::model_repository::ModelRepositoryTrait;
+use crate::infrastructure::repositories::notification_repository::NotificationRepositoryTrait;
+use crate::services::model_service::ModelService;
+use crate::services::scheduler::ScheduledJob;
+use async_trait::async_trait;
+use chrono::Months;
+
+/// 模型状态流转：预售模型过了发售日期转为现货，现货模型长期没有新价格时标记为疑似停产
+#[derive(Clone)]
+pub struct StatusTransitionService<T, C, N>
+where
+    T: ModelRepositoryTrait + Clone,
+    C: CurrencyRepositoryTrait + Clone,
+    N: NotificationRepositoryTrait + Clone,
+{
+    model_service: ModelService<T, C, N>,
+    stale_after_months: u32,
+}
+
+impl<T, C, N> StatusTransitionService<T, C, N>
+where
+    T: ModelRepositoryTrait + Clone,
+    C: CurrencyRepositoryTrait + Clone,
+    N: NotificationRepositoryTrait + Clone,
+{
+    pub fn new(model_service: ModelService<T, C, N>, config: &StatusTransitionConfig) -> Self {
+        Self {
+            model_service,
+            stale_after_months: config.stale_after_months,
+        }
+    }
+
+    /// 立即执行一次，发售日期按服务器本地日期判断
+    pub async fn run_transitions(&self) -> anyhow::Result<StatusTransitionReport> {
+        let today = chrono::Local::now().date_naive();
+        let stale_cutoff = (self.stale_after_months > 0)
+            .then(|| {
+                chrono::Utc::now()
+                    .naive_utc()
+                    .checked_sub_months(Months::new(self.stale_after_months))
+            })
+            .flatten();
+        self.model_service
+            .apply_status_transitions(today, stale_cutoff)
+            .await
+    }
+}
+
+#[async_trait]
+impl<T, C, N> ScheduledJob for StatusTransitionService<T, C, N>
+where
+    T: ModelRepositoryTrait + Clone,
+    C: CurrencyRepositoryTrait + Clone,
+    N: NotificationRepositoryTrait + Clone,
+{
+    fn name(&self) -> &'static str {
+        "status_transition"
+    }
+
+    async fn run(&self) -> anyhow::Result<()> {
+        let report = self.run_transitions().await?;
+        tracing::info!(
+            "状态流转完成: 转为现货 {} 个，标记疑似停产 {} 个，清除标记 {} 个，跳过 {} 个",
+            report.released.len(),
+            report.flagged.len(),
+            report.cleared.len(),
+            report.skipped.len()
+        );
+        Ok(())
+    }
+}
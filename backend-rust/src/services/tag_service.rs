@@ -0,0 +1,39 @@
+#![allow(dead_code)]
+
+use crate::domain::models::tag_dto::TagResponse;
+use crate::infrastructure::repositories::tag_repository::TagRepositoryTrait;
+
+#[derive(Clone)]
+pub struct TagService<T>
+where
+    T: TagRepositoryTrait + Clone,
+{
+    tag_repository: T,
+}
+
+impl<T> TagService<T>
+where
+    T: TagRepositoryTrait + Clone,
+{
+    pub fn new(tag_repository: T) -> Self {
+        Self { tag_repository }
+    }
+
+    /// 标签云：按使用次数从多到少排列
+    pub async fn get_tags(&self) -> anyhow::Result<Vec<TagResponse>> {
+        let tags = self.tag_repository.find_all().await?;
+        let counts = self.tag_repository.count_models().await?;
+
+        let mut responses: Vec<TagResponse> = tags
+            .into_iter()
+            .map(|t| TagResponse {
+                models_count: counts.get(&t.id).copied().unwrap_or(0),
+                id: t.id,
+                name: t.name,
+                slug: t.slug,
+            })
+            .collect();
+        responses.sort_by_key(|t| std::cmp::Reverse(t.models_count));
+        Ok(responses)
+    }
+}
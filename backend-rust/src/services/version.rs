@@ -0,0 +1,18 @@
+use crate::services::error::ServiceError;
+use chrono::{DateTime, NaiveDateTime};
+
+/// 以 updated_at 的微秒时间戳作为乐观锁版本号，同时用于生成 ETag
+pub fn version_of(updated_at: NaiveDateTime) -> i64 {
+    updated_at.and_utc().timestamp_micros()
+}
+
+/// 将客户端提交的版本号还原为 updated_at；无法还原的版本号不可能匹配，直接视为冲突
+pub fn expected_updated_at(version: Option<i64>) -> Result<Option<NaiveDateTime>, ServiceError> {
+    version
+        .map(|v| {
+            DateTime::from_timestamp_micros(v)
+                .map(|dt| dt.naive_utc())
+                .ok_or(ServiceError::PreconditionFailed)
+        })
+        .transpose()
+}
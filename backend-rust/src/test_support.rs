@@ -0,0 +1,337 @@
+//! 测试辅助：记录请求并返回预设响应的本地 HTTP 服务、内存中的仓储，以及占位数据
+
+#![allow(dead_code)]
+
+use crate::domain::entities::enums::{DeliveryChannel, ModelStatus, NotificationKind};
+use crate::domain::entities::{
+    model, notification, notification_delivery, notification_preference,
+};
+use crate::infrastructure::repositories::notification_repository::{
+    NotificationRepositoryTrait, WishlistTarget,
+};
+use async_trait::async_trait;
+use sea_orm::DbErr;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// 收到的一次请求
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: String,
+    /// 含查询字符串
+    pub path: String,
+    /// 请求头名称统一为小写
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl RecordedRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: String,
+}
+
+impl MockResponse {
+    pub fn new(status: u16, content_type: &'static str, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            content_type,
+            body: body.into(),
+        }
+    }
+}
+
+pub struct MockServer {
+    /// 形如 `http://127.0.0.1:12345`，不带结尾的 `/`
+    pub url: String,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+    handle: tokio::task::JoinHandle<()>,
+}
+
+impl MockServer {
+    /// 依次返回 `responses`，用完后重复最后一个
+    pub async fn start(responses: Vec<MockResponse>) -> Self {
+        assert!(!responses.is_empty(), "至少需要一个预设响应");
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let recorded = requests.clone();
+        let handle = tokio::spawn(async move {
+            let mut served = 0;
+            loop {
+                let Ok((mut stream, _)) = listener.accept().await else {
+                    return;
+                };
+                let Some(request) = read_request(&mut stream).await else {
+                    continue;
+                };
+                recorded.lock().unwrap().push(request);
+                let response = &responses[served.min(responses.len() - 1)];
+                served += 1;
+                let _ = write_response(&mut stream, response).await;
+            }
+        });
+
+        Self {
+            url,
+            requests,
+            handle,
+        }
+    }
+
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+/// 只需要占位模型的测试使用
+pub fn sample_model() -> model::Model {
+    let now = chrono::NaiveDate::from_ymd_opt(2026, 10, 1)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap();
+    model::Model {
+        id: 1,
+        name: "RX-78-2".to_string(),
+        series_id: None,
+        category: None,
+        release_date: None,
+        preorder_open_date: None,
+        status: ModelStatus::InStock,
+        manufacturer_id: 1,
+        parent_id: None,
+        notes: None,
+        possibly_discontinued_at: None,
+        created_by: None,
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+pub fn sample_notification(user_id: i32) -> notification::Model {
+    notification::Model {
+        id: 42,
+        user_id,
+        kind: NotificationKind::PriceDrop,
+        model_id: Some(1),
+        title: "RX-78-2 降价至 399 CNY".to_string(),
+        body: Some("已达到心愿单目标价 420 CNY".to_string()),
+        payload: serde_json::json!({ "price": 399.0, "currency": "CNY" }),
+        read_at: None,
+        dispatched_at: None,
+        created_at: chrono::NaiveDate::from_ymd_opt(2026, 10, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap(),
+    }
+}
+
+pub fn sample_delivery(channel: DeliveryChannel, attempts: i32) -> notification_delivery::Model {
+    let now = chrono::Utc::now().naive_utc();
+    notification_delivery::Model {
+        id: 7,
+        notification_id: 42,
+        channel,
+        status: crate::domain::entities::enums::DeliveryStatus::Pending,
+        attempts,
+        next_attempt_at: now,
+        last_error: None,
+        delivered_at: None,
+        created_at: now,
+    }
+}
+
+/// 内存中的通知仓储，只实现偏好与投递相关的方法
+#[derive(Clone, Default)]
+pub struct FakeNotificationRepository {
+    pub state: Arc<Mutex<FakeNotificationState>>,
+}
+
+#[derive(Default)]
+pub struct FakeNotificationState {
+    pub preferences: HashMap<i32, notification_preference::Model>,
+    /// 下次 `claim_due_deliveries` 返回的投递记录
+    pub due: Vec<(notification_delivery::Model, notification::Model)>,
+    pub delivered: Vec<i32>,
+    pub failed: Vec<(i32, String, Option<chrono::NaiveDateTime>)>,
+}
+
+#[async_trait]
+impl NotificationRepositoryTrait for FakeNotificationRepository {
+    async fn find_by_user(
+        &self,
+        _user_id: i32,
+        _unread_only: bool,
+        _page: u64,
+        _per_page: u64,
+    ) -> Result<(Vec<notification::Model>, u64), DbErr> {
+        unimplemented!()
+    }
+
+    async fn count_unread(&self, _user_id: i32) -> Result<u64, DbErr> {
+        unimplemented!()
+    }
+
+    async fn acknowledge(&self, _user_id: i32, _ids: Option<Vec<i32>>) -> Result<u64, DbErr> {
+        unimplemented!()
+    }
+
+    async fn find_preferences(
+        &self,
+        user_id: i32,
+    ) -> Result<Option<notification_preference::Model>, DbErr> {
+        Ok(self
+            .state
+            .lock()
+            .unwrap()
+            .preferences
+            .get(&user_id)
+            .cloned())
+    }
+
+    async fn save_preferences(
+        &self,
+        preferences: notification_preference::Model,
+    ) -> Result<notification_preference::Model, DbErr> {
+        self.state
+            .lock()
+            .unwrap()
+            .preferences
+            .insert(preferences.user_id, preferences.clone());
+        Ok(preferences)
+    }
+
+    async fn find_undispatched(&self, _limit: u64) -> Result<Vec<notification::Model>, DbErr> {
+        Ok(Vec::new())
+    }
+
+    async fn schedule_deliveries(
+        &self,
+        _notification_id: i32,
+        _channels: Vec<DeliveryChannel>,
+    ) -> Result<(), DbErr> {
+        unimplemented!()
+    }
+
+    async fn claim_due_deliveries(
+        &self,
+        _limit: u64,
+        _lease_secs: i64,
+    ) -> Result<Vec<(notification_delivery::Model, notification::Model)>, DbErr> {
+        Ok(std::mem::take(&mut self.state.lock().unwrap().due))
+    }
+
+    async fn mark_delivered(&self, delivery_id: i32) -> Result<(), DbErr> {
+        self.state.lock().unwrap().delivered.push(delivery_id);
+        Ok(())
+    }
+
+    async fn mark_failed(
+        &self,
+        delivery_id: i32,
+        error: String,
+        next_attempt_at: Option<chrono::NaiveDateTime>,
+    ) -> Result<(), DbErr> {
+        self.state
+            .lock()
+            .unwrap()
+            .failed
+            .push((delivery_id, error, next_attempt_at));
+        Ok(())
+    }
+
+    async fn find_wishlist_targets(&self, _model_id: i32) -> Result<Vec<WishlistTarget>, DbErr> {
+        unimplemented!()
+    }
+
+    async fn find_unread_price_drops(
+        &self,
+        _model_id: i32,
+    ) -> Result<Vec<notification::Model>, DbErr> {
+        unimplemented!()
+    }
+
+    async fn insert_notifications(
+        &self,
+        _notifications: Vec<notification::ActiveModel>,
+    ) -> Result<(), DbErr> {
+        unimplemented!()
+    }
+}
+
+async fn read_request(stream: &mut TcpStream) -> Option<RecordedRequest> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let header_end = loop {
+        let read = stream.read(&mut chunk).await.ok()?;
+        if read == 0 {
+            return None;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+        if let Some(position) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+            break position + 4;
+        }
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..header_end]).to_string();
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next()?.split_whitespace();
+    let method = request_line.next()?.to_string();
+    let path = request_line.next()?.to_string();
+    let headers: Vec<(String, String)> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(key, value)| (key.trim().to_ascii_lowercase(), value.trim().to_string()))
+        .collect();
+
+    let content_length = headers
+        .iter()
+        .find(|(key, _)| key == "content-length")
+        .and_then(|(_, value)| value.parse::<usize>().ok())
+        .unwrap_or(0);
+    let mut body = buffer[header_end..].to_vec();
+    while body.len() < content_length {
+        let read = stream.read(&mut chunk).await.ok()?;
+        if read == 0 {
+            break;
+        }
+        body.extend_from_slice(&chunk[..read]);
+    }
+
+    Some(RecordedRequest {
+        method,
+        path,
+        headers,
+        body,
+    })
+}
+
+async fn write_response(stream: &mut TcpStream, response: &MockResponse) -> std::io::Result<()> {
+    let head = format!(
+        "HTTP/1.1 {} Mock\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        response.content_type,
+        response.body.len()
+    );
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(response.body.as_bytes()).await?;
+    stream.shutdown().await
+}